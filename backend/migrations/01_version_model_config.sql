@@ -0,0 +1,9 @@
+-- Model configuration the version was authored and tested against
+ALTER TABLE versions
+    ADD COLUMN model_provider VARCHAR(20),
+    ADD COLUMN model_name VARCHAR(255),
+    ADD COLUMN temperature DOUBLE PRECISION,
+    ADD COLUMN top_p DOUBLE PRECISION,
+    ADD COLUMN max_tokens INTEGER,
+    ADD COLUMN stop_sequences JSONB,
+    ADD COLUMN seed BIGINT;
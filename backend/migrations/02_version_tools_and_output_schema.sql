@@ -0,0 +1,4 @@
+-- Tool definitions and structured output schema sent alongside the version
+ALTER TABLE versions
+    ADD COLUMN tools JSONB,
+    ADD COLUMN output_schema JSONB;
@@ -0,0 +1,7 @@
+-- Token count of each version's content and per-prompt render budget
+ALTER TABLE versions
+    ADD COLUMN token_count INTEGER,
+    ADD COLUMN token_encoding VARCHAR(50);
+
+ALTER TABLE prompts
+    ADD COLUMN max_render_tokens INTEGER CHECK (max_render_tokens > 0);
@@ -0,0 +1,37 @@
+-- Outbound webhook subscriptions and their delivery log
+CREATE TABLE webhooks (
+                          id UUID PRIMARY KEY,
+                          user_id UUID NOT NULL REFERENCES auth.users(id) ON DELETE CASCADE,
+                          prompt_id UUID REFERENCES prompts(id) ON DELETE CASCADE,
+                          url TEXT NOT NULL,
+                          secret VARCHAR(100) NOT NULL,
+                          event_types JSONB NOT NULL DEFAULT '[]',
+                          is_active BOOLEAN NOT NULL DEFAULT TRUE,
+                          created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_webhooks_user_id ON webhooks(user_id);
+
+ALTER TABLE webhooks ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY "Users can manage own webhooks"
+    ON webhooks FOR ALL USING (auth.uid() = user_id);
+
+CREATE TABLE webhook_deliveries (
+                                    id UUID PRIMARY KEY,
+                                    webhook_id UUID NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+                                    event_id UUID NOT NULL,
+                                    event_type VARCHAR(50) NOT NULL,
+                                    payload TEXT NOT NULL,
+                                    status VARCHAR(20) NOT NULL CHECK (status IN ('pending', 'succeeded', 'failed')),
+                                    attempts INTEGER NOT NULL DEFAULT 0,
+                                    response_status INTEGER,
+                                    last_error TEXT,
+                                    next_attempt_at TIMESTAMPTZ,
+                                    created_at TIMESTAMPTZ NOT NULL,
+                                    updated_at TIMESTAMPTZ NOT NULL,
+                                    UNIQUE(webhook_id, event_id)
+);
+
+CREATE INDEX idx_webhook_deliveries_webhook_id ON webhook_deliveries(webhook_id, created_at DESC);
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at) WHERE status = 'pending';
@@ -0,0 +1,2 @@
+-- Revision counter for optimistic concurrency; bumped by every save
+ALTER TABLE prompts ADD COLUMN revision BIGINT NOT NULL DEFAULT 1;
@@ -0,0 +1,31 @@
+-- Append-only record of every mutation. No foreign keys: entries must
+-- outlive the prompts, keys and webhooks they describe.
+CREATE TABLE audit_log (
+                           id UUID PRIMARY KEY,
+                           occurred_at TIMESTAMPTZ NOT NULL,
+                           user_id UUID NOT NULL,
+                           api_key_id UUID,
+                           action VARCHAR(50) NOT NULL,
+                           prompt_id UUID,
+                           target_id UUID,
+                           before JSONB,
+                           after JSONB
+);
+
+CREATE INDEX idx_audit_log_user_id ON audit_log(user_id, occurred_at, id);
+CREATE INDEX idx_audit_log_prompt_id ON audit_log(prompt_id, occurred_at, id) WHERE prompt_id IS NOT NULL;
+
+ALTER TABLE audit_log ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY "Users can read own audit log"
+    ON audit_log FOR SELECT USING (auth.uid() = user_id);
+
+CREATE FUNCTION audit_log_is_append_only() RETURNS trigger AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_log_append_only
+    BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW EXECUTE FUNCTION audit_log_is_append_only();
@@ -0,0 +1,19 @@
+-- Who created each version, feedback and suggestion, and how: 'ui' for a
+-- user session, 'api_key', or 'integration' for an API key used by a named
+-- client such as n8n. NULL for rows written before this was recorded.
+ALTER TABLE versions ADD COLUMN created_by UUID;
+ALTER TABLE versions ADD COLUMN created_by_api_key_id UUID;
+ALTER TABLE versions ADD COLUMN created_by_source VARCHAR(20);
+
+ALTER TABLE feedbacks ADD COLUMN created_by UUID;
+ALTER TABLE feedbacks ADD COLUMN created_by_api_key_id UUID;
+ALTER TABLE feedbacks ADD COLUMN created_by_source VARCHAR(20);
+
+ALTER TABLE improvement_suggestions ADD COLUMN created_by UUID;
+ALTER TABLE improvement_suggestions ADD COLUMN created_by_api_key_id UUID;
+ALTER TABLE improvement_suggestions ADD COLUMN created_by_source VARCHAR(20);
+
+CREATE INDEX idx_feedbacks_created_by ON feedbacks(created_by);
+
+-- Existing audit entries keep NULL and are read by their api_key_id
+ALTER TABLE audit_log ADD COLUMN source VARCHAR(20);
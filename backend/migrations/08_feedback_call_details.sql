@@ -0,0 +1,13 @@
+-- Details of the production call a feedback rates, as reported by the caller.
+-- All NULL for feedback submitted without them.
+ALTER TABLE feedbacks ADD COLUMN model VARCHAR(255);
+ALTER TABLE feedbacks ADD COLUMN latency_ms BIGINT;
+ALTER TABLE feedbacks ADD COLUMN input_tokens INTEGER;
+ALTER TABLE feedbacks ADD COLUMN output_tokens INTEGER;
+ALTER TABLE feedbacks ADD COLUMN cost_usd DOUBLE PRECISION;
+ALTER TABLE feedbacks ADD COLUMN render_context JSONB;
+ALTER TABLE feedbacks ADD COLUMN trace_id VARCHAR(255);
+ALTER TABLE feedbacks ADD COLUMN metadata JSONB;
+
+CREATE INDEX idx_feedbacks_model ON feedbacks(model);
+CREATE INDEX idx_feedbacks_trace_id ON feedbacks(trace_id);
@@ -0,0 +1,27 @@
+-- One row per successful render, so feedback can reference the exact input
+-- that produced a call. No foreign keys, like audit_log: inserts stay cheap
+-- on the render path and rows outlive the prompts they describe.
+CREATE TABLE render_events (
+                               id UUID PRIMARY KEY,
+                               rendered_at TIMESTAMPTZ NOT NULL,
+                               user_id UUID NOT NULL,
+                               api_key_id UUID,
+                               source VARCHAR(20),
+                               prompt_id UUID NOT NULL,
+                               version_id UUID NOT NULL,
+                               tag VARCHAR(255),
+                               context_hash VARCHAR(80) NOT NULL,
+                               -- NULL when the server only keeps the hash
+                               context JSONB
+);
+
+CREATE INDEX idx_render_events_prompt_id ON render_events(prompt_id, rendered_at);
+
+ALTER TABLE render_events ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY "Users can read own render events"
+    ON render_events FOR SELECT USING (auth.uid() = user_id);
+
+ALTER TABLE feedbacks ADD COLUMN render_id UUID;
+
+CREATE INDEX idx_feedbacks_render_id ON feedbacks(render_id);
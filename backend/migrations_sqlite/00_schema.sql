@@ -0,0 +1,126 @@
+-- Schema for self-hosted installs on SQLite. Mirrors the Postgres
+-- migrations without the Supabase auth schema. UUIDs are stored as 16-byte
+-- BLOBs, timestamps as RFC 3339 TEXT and JSON as TEXT.
+
+CREATE TABLE prompts (
+                         id BLOB PRIMARY KEY,
+                         user_id BLOB NOT NULL,
+                         name TEXT NOT NULL,
+                         description TEXT,
+                         prompt_type TEXT NOT NULL DEFAULT 'system',
+                         max_render_tokens INTEGER CHECK (max_render_tokens > 0),
+                         created_at TEXT NOT NULL,
+                         updated_at TEXT NOT NULL,
+                         revision INTEGER NOT NULL DEFAULT 1
+);
+
+CREATE INDEX idx_prompts_user_id ON prompts(user_id);
+
+CREATE TABLE versions (
+                          id BLOB PRIMARY KEY,
+                          prompt_id BLOB NOT NULL REFERENCES prompts(id) ON DELETE CASCADE,
+                          version TEXT NOT NULL,
+                          digest TEXT NOT NULL,
+                          content TEXT NOT NULL,
+                          content_type TEXT NOT NULL DEFAULT 'static',
+                          variables TEXT,
+                          changelog TEXT,
+                          created_at TEXT NOT NULL,
+                          model_provider TEXT,
+                          model_name TEXT,
+                          temperature REAL,
+                          top_p REAL,
+                          max_tokens INTEGER,
+                          stop_sequences TEXT,
+                          seed INTEGER,
+                          tools TEXT,
+                          output_schema TEXT,
+                          token_count INTEGER,
+                          token_encoding TEXT,
+                          UNIQUE(prompt_id, version)
+);
+
+CREATE INDEX idx_versions_prompt_id ON versions(prompt_id);
+
+CREATE TABLE tags (
+                      id BLOB PRIMARY KEY,
+                      prompt_id BLOB NOT NULL REFERENCES prompts(id) ON DELETE CASCADE,
+                      version_id BLOB NOT NULL REFERENCES versions(id) ON DELETE CASCADE,
+                      name TEXT NOT NULL,
+                      updated_at TEXT NOT NULL,
+                      UNIQUE(prompt_id, name)
+);
+
+CREATE INDEX idx_tags_name ON tags(name);
+
+CREATE TABLE feedbacks (
+                           id BLOB PRIMARY KEY,
+                           version_id BLOB NOT NULL REFERENCES versions(id) ON DELETE CASCADE,
+                           rating INTEGER NOT NULL CHECK (rating >= 1 AND rating <= 5),
+                           comment TEXT,
+                           test_input TEXT,
+                           test_actual_output TEXT,
+                           test_expected_output TEXT,
+                           created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_feedbacks_version_id ON feedbacks(version_id);
+
+CREATE TABLE improvement_suggestions (
+                                         id BLOB PRIMARY KEY,
+                                         source_version_id BLOB NOT NULL REFERENCES versions(id) ON DELETE CASCADE,
+                                         suggested_content TEXT NOT NULL,
+                                         ai_rationale TEXT NOT NULL,
+                                         status TEXT NOT NULL CHECK (status IN ('pending', 'accepted', 'declined')),
+                                         decline_reason TEXT,
+                                         created_at TEXT NOT NULL,
+                                         resolved_at TEXT,
+                                         resulting_version_id BLOB REFERENCES versions(id) ON DELETE SET NULL
+);
+
+CREATE INDEX idx_improvement_suggestions_source_version ON improvement_suggestions(source_version_id);
+
+CREATE TABLE api_keys (
+                          id BLOB PRIMARY KEY,
+                          user_id BLOB NOT NULL,
+                          name TEXT NOT NULL,
+                          key_hash TEXT NOT NULL UNIQUE,
+                          key_prefix TEXT NOT NULL,
+                          last_used_at TEXT,
+                          created_at TEXT NOT NULL,
+                          is_active INTEGER NOT NULL DEFAULT 1
+);
+
+CREATE INDEX idx_api_keys_user_id ON api_keys(user_id);
+
+CREATE TABLE webhooks (
+                          id BLOB PRIMARY KEY,
+                          user_id BLOB NOT NULL,
+                          prompt_id BLOB REFERENCES prompts(id) ON DELETE CASCADE,
+                          url TEXT NOT NULL,
+                          secret TEXT NOT NULL,
+                          event_types TEXT NOT NULL DEFAULT '[]',
+                          is_active INTEGER NOT NULL DEFAULT 1,
+                          created_at TEXT NOT NULL
+);
+
+CREATE INDEX idx_webhooks_user_id ON webhooks(user_id);
+
+CREATE TABLE webhook_deliveries (
+                                    id BLOB PRIMARY KEY,
+                                    webhook_id BLOB NOT NULL REFERENCES webhooks(id) ON DELETE CASCADE,
+                                    event_id BLOB NOT NULL,
+                                    event_type TEXT NOT NULL,
+                                    payload TEXT NOT NULL,
+                                    status TEXT NOT NULL CHECK (status IN ('pending', 'succeeded', 'failed')),
+                                    attempts INTEGER NOT NULL DEFAULT 0,
+                                    response_status INTEGER,
+                                    last_error TEXT,
+                                    next_attempt_at TEXT,
+                                    created_at TEXT NOT NULL,
+                                    updated_at TEXT NOT NULL,
+                                    UNIQUE(webhook_id, event_id)
+);
+
+CREATE INDEX idx_webhook_deliveries_webhook_id ON webhook_deliveries(webhook_id, created_at);
+CREATE INDEX idx_webhook_deliveries_due ON webhook_deliveries(next_attempt_at) WHERE status = 'pending';
@@ -0,0 +1,26 @@
+-- Append-only record of every mutation. No foreign keys: entries must
+-- outlive the prompts, keys and webhooks they describe.
+CREATE TABLE audit_log (
+                           id BLOB PRIMARY KEY,
+                           occurred_at TEXT NOT NULL,
+                           user_id BLOB NOT NULL,
+                           api_key_id BLOB,
+                           action TEXT NOT NULL,
+                           prompt_id BLOB,
+                           target_id BLOB,
+                           before TEXT,
+                           after TEXT
+);
+
+CREATE INDEX idx_audit_log_user_id ON audit_log(user_id, occurred_at, id);
+CREATE INDEX idx_audit_log_prompt_id ON audit_log(prompt_id, occurred_at, id) WHERE prompt_id IS NOT NULL;
+
+CREATE TRIGGER audit_log_no_update BEFORE UPDATE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append-only');
+END;
+
+CREATE TRIGGER audit_log_no_delete BEFORE DELETE ON audit_log
+BEGIN
+    SELECT RAISE(ABORT, 'audit_log is append-only');
+END;
@@ -0,0 +1,22 @@
+-- One row per successful render, so feedback can reference the exact input
+-- that produced a call. No foreign keys, like audit_log: inserts stay cheap
+-- on the render path and rows outlive the prompts they describe.
+CREATE TABLE render_events (
+                               id BLOB PRIMARY KEY,
+                               rendered_at TEXT NOT NULL,
+                               user_id BLOB NOT NULL,
+                               api_key_id BLOB,
+                               source TEXT,
+                               prompt_id BLOB NOT NULL,
+                               version_id BLOB NOT NULL,
+                               tag TEXT,
+                               context_hash TEXT NOT NULL,
+                               -- NULL when the server only keeps the hash
+                               context TEXT
+);
+
+CREATE INDEX idx_render_events_prompt_id ON render_events(prompt_id, rendered_at);
+
+ALTER TABLE feedbacks ADD COLUMN render_id BLOB;
+
+CREATE INDEX idx_feedbacks_render_id ON feedbacks(render_id);
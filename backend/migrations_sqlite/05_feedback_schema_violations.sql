@@ -0,0 +1,4 @@
+-- Output-schema violations found in a feedback's actual output when it was
+-- submitted, so loads need not validate again. NULL when the version has no
+-- output schema, the feedback has no test scenario, or it predates this column.
+ALTER TABLE feedbacks ADD COLUMN test_schema_violations TEXT;
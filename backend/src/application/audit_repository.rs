@@ -0,0 +1,23 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::audit::{AuditAction, AuditEntry};
+
+/// Filters for reading one account's audit log, oldest entry first.
+pub struct AuditQuery {
+    pub user_id: Uuid,
+    pub prompt_id: Option<Uuid>,
+    pub action: Option<AuditAction>,
+    /// Inclusive lower bound on `occurred_at`.
+    pub since: Option<DateTime<Utc>>,
+    /// Exclusive upper bound on `occurred_at`.
+    pub until: Option<DateTime<Utc>>,
+    /// Only entries after this one, for paging.
+    pub after: Option<Uuid>,
+    pub limit: i64,
+}
+
+#[async_trait]
+pub trait AuditRepository: Send + Sync {
+    async fn find(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, String>;
+}
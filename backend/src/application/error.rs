@@ -0,0 +1,70 @@
+use crate::domain::error::DomainError;
+use std::fmt;
+
+/// Failure of a use case, classified so the web layer can pick a status
+/// code and clients can tell retryable errors from permanent ones.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppError {
+    NotFound(String),
+    Conflict(String),
+    PreconditionFailed(String),
+    Validation { field: Option<String>, message: String },
+    Forbidden(String),
+    /// The AI provider failed or returned something unusable.
+    Upstream(String),
+    /// The feature is switched off or not configured on this server.
+    Unavailable(String),
+    /// Storage or other infrastructure failure. The message is for logs
+    /// only and must not reach clients.
+    Internal(String),
+}
+
+impl AppError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound(message.into())
+    }
+
+    pub fn validation(field: &str, message: impl Into<String>) -> Self {
+        Self::Validation { field: Some(field.to_string()), message: message.into() }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound(message)
+            | Self::Conflict(message)
+            | Self::PreconditionFailed(message)
+            | Self::Validation { message, .. }
+            | Self::Forbidden(message)
+            | Self::Upstream(message)
+            | Self::Unavailable(message)
+            | Self::Internal(message) => message,
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<DomainError> for AppError {
+    fn from(error: DomainError) -> Self {
+        match error {
+            DomainError::NotFound(message) => Self::NotFound(message),
+            DomainError::Conflict(message) => Self::Conflict(message),
+            DomainError::PreconditionFailed(message) => Self::PreconditionFailed(message),
+            DomainError::Validation { field, message } => Self::Validation { field, message },
+        }
+    }
+}
+
+/// Repositories, the tokenizer and other ports report failures as plain
+/// strings; anything they return unclassified is an internal error.
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        Self::Internal(message)
+    }
+}
@@ -0,0 +1,204 @@
+use crate::application::CacheStats;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Distinct `(prompt, tag)` render series kept before further ones are
+/// counted under `other`, so many prompts cannot blow up the scrape.
+const MAX_RENDER_SERIES: usize = 500;
+/// Longer tag names are reported as `other`.
+const MAX_TAG_LABEL_LEN: usize = 64;
+const OTHER: &str = "other";
+
+/// Upper bounds in seconds, covering fast renders through slow AI calls.
+const LATENCY_BUCKETS: &[f64] = &[0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0];
+
+#[derive(Default)]
+struct Histogram {
+    // Cumulative counts per entry of `LATENCY_BUCKETS`.
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if seconds <= *bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += seconds;
+        self.count += 1;
+    }
+
+    fn encode(&self, out: &mut String, name: &str, labels: &str) {
+        let sep = if labels.is_empty() { "" } else { "," };
+        for (count, bound) in self.buckets.iter().zip(LATENCY_BUCKETS) {
+            let _ = writeln!(out, "{}_bucket{{{}{}le=\"{}\"}} {}", name, labels, sep, bound, count);
+        }
+        let _ = writeln!(out, "{}_bucket{{{}{}le=\"+Inf\"}} {}", name, labels, sep, self.count);
+        let _ = writeln!(out, "{}_sum{{{}}} {}", name, labels, self.sum);
+        let _ = writeln!(out, "{}_count{{{}}} {}", name, labels, self.count);
+    }
+}
+
+/// Process-wide counters exposed at `/metrics` in the Prometheus text format.
+/// Series live for the life of the process; counters reset on restart.
+#[derive(Default)]
+pub struct Metrics {
+    // (method, route, status)
+    requests: Mutex<BTreeMap<(String, String, u16), u64>>,
+    // (method, route)
+    request_durations: Mutex<BTreeMap<(String, String), Histogram>>,
+    // (prompt, tag), with an empty tag for renders by version id; capped at
+    // `MAX_RENDER_SERIES`.
+    renders: Mutex<BTreeMap<(String, String), u64>>,
+    // (provider, outcome)
+    ai_calls: Mutex<BTreeMap<(&'static str, &'static str), u64>>,
+    ai_durations: Mutex<BTreeMap<&'static str, Histogram>>,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `route` is the matched route template, not the raw path, so ids do
+    /// not create a series per request.
+    pub fn record_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        *self.requests
+            .lock()
+            .unwrap()
+            .entry((method.to_string(), route.to_string(), status))
+            .or_default() += 1;
+        self.request_durations
+            .lock()
+            .unwrap()
+            .entry((method.to_string(), route.to_string()))
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// `tag` is the tag the version was picked by, `None` when it was
+    /// picked by id.
+    pub fn record_render(&self, prompt_id: Uuid, tag: Option<&str>) {
+        let tag = match tag {
+            Some(tag) if tag.len() > MAX_TAG_LABEL_LEN => OTHER.to_string(),
+            Some(tag) => tag.to_string(),
+            None => String::new(),
+        };
+        let mut renders = self.renders.lock().unwrap();
+        let mut key = (prompt_id.to_string(), tag);
+        if !renders.contains_key(&key) && renders.len() >= MAX_RENDER_SERIES {
+            key = (OTHER.to_string(), OTHER.to_string());
+        }
+        *renders.entry(key).or_default() += 1;
+    }
+
+    pub fn record_ai_call(&self, provider: &'static str, succeeded: bool, elapsed: Duration) {
+        let outcome = if succeeded { "success" } else { "failure" };
+        *self.ai_calls.lock().unwrap().entry((provider, outcome)).or_default() += 1;
+        self.ai_durations
+            .lock()
+            .unwrap()
+            .entry(provider)
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+
+    /// Renders every series; `cache` is `None` when the prompt cache is off.
+    pub fn encode(&self, cache: Option<CacheStats>) -> String {
+        let mut out = String::new();
+
+        header(&mut out, "http_requests_total", "counter", "HTTP requests by method, route and status.");
+        for ((method, route, status), count) in self.requests.lock().unwrap().iter() {
+            let _ = writeln!(
+                out,
+                "http_requests_total{{method=\"{}\",route=\"{}\",status=\"{}\"}} {}",
+                escape(method), escape(route), status, count
+            );
+        }
+
+        header(&mut out, "http_request_duration_seconds", "histogram", "HTTP request latency by method and route.");
+        for ((method, route), histogram) in self.request_durations.lock().unwrap().iter() {
+            let labels = format!("method=\"{}\",route=\"{}\"", escape(method), escape(route));
+            histogram.encode(&mut out, "http_request_duration_seconds", &labels);
+        }
+
+        header(&mut out, "prompt_renders_total", "counter", "Successful renders by prompt and tag; tag is empty for renders by version.");
+        for ((prompt, tag), count) in self.renders.lock().unwrap().iter() {
+            let _ = writeln!(out, "prompt_renders_total{{prompt=\"{}\",tag=\"{}\"}} {}", prompt, escape(tag), count);
+        }
+
+        header(&mut out, "ai_requests_total", "counter", "Calls to the AI provider by outcome.");
+        for ((provider, outcome), count) in self.ai_calls.lock().unwrap().iter() {
+            let _ = writeln!(out, "ai_requests_total{{provider=\"{}\",outcome=\"{}\"}} {}", provider, outcome, count);
+        }
+
+        header(&mut out, "ai_request_duration_seconds", "histogram", "AI provider call latency, including failures.");
+        for (provider, histogram) in self.ai_durations.lock().unwrap().iter() {
+            histogram.encode(&mut out, "ai_request_duration_seconds", &format!("provider=\"{}\"", provider));
+        }
+
+        if let Some(cache) = cache {
+            let lookups = cache.hits + cache.misses;
+            let hit_ratio = if lookups > 0 { cache.hits as f64 / lookups as f64 } else { 0.0 };
+            for (name, kind, help, value) in [
+                ("prompt_cache_hits_total", "counter", "Prompt cache lookups served from memory.", cache.hits as f64),
+                ("prompt_cache_misses_total", "counter", "Prompt cache lookups that went to storage.", cache.misses as f64),
+                ("prompt_cache_invalidations_total", "counter", "Prompt cache entries dropped by writes or change notifications.", cache.invalidations as f64),
+                ("prompt_cache_entries", "gauge", "Prompts currently cached.", cache.entries as f64),
+                ("prompt_cache_hit_ratio", "gauge", "Hits over lookups since start, 0 before the first lookup.", hit_ratio),
+            ] {
+                header(&mut out, name, kind, help);
+                let _ = writeln!(out, "{} {}", name, value);
+            }
+        }
+
+        out
+    }
+}
+
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Escapes a label value as the exposition format requires.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_renders_by_prompt_and_tag() {
+        let metrics = Metrics::new();
+        let prompt_id = Uuid::new_v4();
+
+        metrics.record_render(prompt_id, Some("production"));
+        metrics.record_render(prompt_id, Some("production"));
+        metrics.record_render(prompt_id, None);
+
+        let out = metrics.encode(None);
+        assert!(out.contains(&format!("prompt_renders_total{{prompt=\"{}\",tag=\"production\"}} 2", prompt_id)));
+        assert!(out.contains(&format!("prompt_renders_total{{prompt=\"{}\",tag=\"\"}} 1", prompt_id)));
+    }
+
+    #[test]
+    fn folds_renders_past_the_series_cap_into_other() {
+        let metrics = Metrics::new();
+        for _ in 0..MAX_RENDER_SERIES + 3 {
+            metrics.record_render(Uuid::new_v4(), Some("production"));
+        }
+
+        let out = metrics.encode(None);
+        assert_eq!(out.lines().filter(|line| line.starts_with("prompt_renders_total{")).count(), MAX_RENDER_SERIES + 1);
+        assert!(out.contains("prompt_renders_total{prompt=\"other\",tag=\"other\"} 3"));
+    }
+}
@@ -1,8 +1,38 @@
+pub mod error;
 pub mod prompt_repository;
 pub mod use_cases;
 pub mod api_key_repository;
 pub mod ai_service;
+pub mod provider_payload;
+pub mod tokenizer;
+pub mod pricing;
+pub mod read_cache;
+pub mod prompt_changes;
+pub mod prompt_event_payload;
+pub mod webhook_repository;
+pub mod webhook_sender;
+pub mod webhook_dispatcher;
+pub mod metrics;
+pub mod readiness;
+pub mod audit_repository;
+pub mod render_repository;
+pub mod render_log;
 
-pub use prompt_repository::PromptRepository;
+pub use error::AppError;
+pub use prompt_repository::{PromptRepository, ETAG_MISMATCH, REVISION_CONFLICT};
 pub use api_key_repository::ApiKeyRepository;
-pub use ai_service::AIService;
\ No newline at end of file
+pub use ai_service::AIService;
+pub use provider_payload::build_provider_payload;
+pub use tokenizer::Tokenizer;
+pub use pricing::{PricingTable, CostEstimate};
+pub use read_cache::{ReadCache, CacheStats};
+pub use prompt_changes::{PromptChange, PromptChangeKind};
+pub use prompt_event_payload::event_payload;
+pub use webhook_repository::{WebhookRepository, CLAIM_LOST};
+pub use webhook_sender::{WebhookSender, SEND_TIMEOUT};
+pub use webhook_dispatcher::WebhookDispatcher;
+pub use metrics::Metrics;
+pub use readiness::ReadinessProbe;
+pub use audit_repository::{AuditRepository, AuditQuery};
+pub use render_repository::RenderRepository;
+pub use render_log::RenderLog;
\ No newline at end of file
@@ -0,0 +1,115 @@
+use serde::Deserialize;
+use crate::domain::prompt::{ModelConfig, ModelProvider};
+
+/// Price of input tokens for every model whose name starts with `model_prefix`.
+#[derive(Debug, Clone)]
+pub struct ModelPrice {
+    provider: ModelProvider,
+    model_prefix: String,
+    input_per_million_tokens: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostEstimate {
+    pub input_cost_usd: f64,
+    pub input_per_million_tokens: f64,
+}
+
+#[derive(Deserialize)]
+struct ModelPriceEntry {
+    provider: String,
+    model: String,
+    input_per_million_tokens: f64,
+}
+
+/// USD list prices per million input tokens, matched by the longest model
+/// name prefix so dated snapshots (e.g. `gpt-4o-2024-08-06`) resolve to
+/// their family.
+pub struct PricingTable {
+    prices: Vec<ModelPrice>,
+}
+
+impl PricingTable {
+    pub fn with_defaults() -> Self {
+        const DEFAULTS: &[(ModelProvider, &str, f64)] = &[
+            (ModelProvider::OpenAI, "gpt-4.1", 2.00),
+            (ModelProvider::OpenAI, "gpt-4.1-mini", 0.40),
+            (ModelProvider::OpenAI, "gpt-4.1-nano", 0.10),
+            (ModelProvider::OpenAI, "gpt-4o", 2.50),
+            (ModelProvider::OpenAI, "gpt-4o-mini", 0.15),
+            (ModelProvider::OpenAI, "gpt-4-turbo", 10.00),
+            (ModelProvider::OpenAI, "gpt-4", 30.00),
+            (ModelProvider::OpenAI, "gpt-3.5-turbo", 0.50),
+            (ModelProvider::OpenAI, "o1", 15.00),
+            (ModelProvider::OpenAI, "o1-mini", 1.10),
+            (ModelProvider::OpenAI, "o3", 2.00),
+            (ModelProvider::OpenAI, "o3-mini", 1.10),
+            (ModelProvider::OpenAI, "o4-mini", 1.10),
+            (ModelProvider::Anthropic, "claude-opus-4", 15.00),
+            (ModelProvider::Anthropic, "claude-sonnet-4", 3.00),
+            (ModelProvider::Anthropic, "claude-3-7-sonnet", 3.00),
+            (ModelProvider::Anthropic, "claude-3-5-sonnet", 3.00),
+            (ModelProvider::Anthropic, "claude-3-5-haiku", 0.80),
+            (ModelProvider::Anthropic, "claude-3-opus", 15.00),
+            (ModelProvider::Anthropic, "claude-3-haiku", 0.25),
+            (ModelProvider::Gemini, "gemini-2.5-pro", 1.25),
+            (ModelProvider::Gemini, "gemini-2.5-flash", 0.30),
+            (ModelProvider::Gemini, "gemini-2.0-flash", 0.10),
+            (ModelProvider::Gemini, "gemini-1.5-pro", 1.25),
+            (ModelProvider::Gemini, "gemini-1.5-flash", 0.075),
+        ];
+
+        Self {
+            prices: DEFAULTS
+                .iter()
+                .map(|(provider, model_prefix, price)| ModelPrice {
+                    provider: *provider,
+                    model_prefix: model_prefix.to_string(),
+                    input_per_million_tokens: *price,
+                })
+                .collect(),
+        }
+    }
+
+    /// Applies overrides from a JSON array of
+    /// `{"provider", "model", "input_per_million_tokens"}` objects. An entry
+    /// replaces the default with the same provider and model prefix.
+    pub fn apply_overrides(&mut self, json: &str) -> Result<(), String> {
+        let entries: Vec<ModelPriceEntry> = serde_json::from_str(json)
+            .map_err(|e| format!("Invalid pricing table: {}", e))?;
+
+        for entry in entries {
+            let provider = ModelProvider::from_str(&entry.provider)?;
+            if entry.model.trim().is_empty() {
+                return Err("Pricing model cannot be empty".to_string());
+            }
+            if !entry.input_per_million_tokens.is_finite() || entry.input_per_million_tokens < 0.0 {
+                return Err(format!("Invalid price for model '{}'", entry.model));
+            }
+
+            self.prices.retain(|p| !(p.provider == provider && p.model_prefix == entry.model));
+            self.prices.push(ModelPrice {
+                provider,
+                model_prefix: entry.model,
+                input_per_million_tokens: entry.input_per_million_tokens,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Estimated cost of sending `input_tokens` to the configured model.
+    /// `None` when no model is configured or it has no known price.
+    pub fn estimate_input_cost(&self, model_config: Option<&ModelConfig>, input_tokens: u32) -> Option<CostEstimate> {
+        let config = model_config?;
+        let price = self.prices
+            .iter()
+            .filter(|p| p.provider == config.provider() && config.model().starts_with(&p.model_prefix))
+            .max_by_key(|p| p.model_prefix.len())?;
+
+        Some(CostEstimate {
+            input_cost_usd: input_tokens as f64 * price.input_per_million_tokens / 1_000_000.0,
+            input_per_million_tokens: price.input_per_million_tokens,
+        })
+    }
+}
@@ -0,0 +1,32 @@
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptChangeKind {
+    Saved,
+    Deleted,
+}
+
+impl PromptChangeKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromptChangeKind::Saved => "saved",
+            PromptChangeKind::Deleted => "deleted",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "saved" => Ok(PromptChangeKind::Saved),
+            "deleted" => Ok(PromptChangeKind::Deleted),
+            _ => Err(format!("Unknown prompt change kind: {}", s)),
+        }
+    }
+}
+
+/// A prompt was written or removed by any backend instance.
+#[derive(Debug, Clone)]
+pub struct PromptChange {
+    pub prompt_id: Uuid,
+    pub user_id: Uuid,
+    pub kind: PromptChangeKind,
+}
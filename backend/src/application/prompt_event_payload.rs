@@ -0,0 +1,39 @@
+use serde_json::json;
+use crate::domain::prompt::{PromptEvent, PromptEventKind};
+
+/// JSON form of a domain event as delivered to clients: its fields plus
+/// `id`, `type`, `prompt_id` and `occurred_at`.
+pub fn event_payload(event: &PromptEvent) -> serde_json::Value {
+    let mut data = match event.kind() {
+        PromptEventKind::VersionCreated { version_id, version } => json!({
+            "version_id": version_id.to_string(),
+            "version": version,
+        }),
+        PromptEventKind::TagMoved { tag_name, version_id, previous_version_id } => json!({
+            "tag_name": tag_name,
+            "version_id": version_id.to_string(),
+            "previous_version_id": previous_version_id.map(|id| id.to_string()),
+        }),
+        PromptEventKind::SuggestionCreated { version_id, suggestion_id }
+        | PromptEventKind::SuggestionDeclined { version_id, suggestion_id } => json!({
+            "version_id": version_id.to_string(),
+            "suggestion_id": suggestion_id.to_string(),
+        }),
+        PromptEventKind::SuggestionAccepted { version_id, suggestion_id, resulting_version_id } => json!({
+            "version_id": version_id.to_string(),
+            "suggestion_id": suggestion_id.to_string(),
+            "resulting_version_id": resulting_version_id.to_string(),
+        }),
+        PromptEventKind::FeedbackSubmitted { version_id, feedback_id, rating } => json!({
+            "version_id": version_id.to_string(),
+            "feedback_id": feedback_id.to_string(),
+            "rating": rating,
+        }),
+    };
+
+    data["id"] = json!(event.id().to_string());
+    data["type"] = json!(event.kind().as_str());
+    data["prompt_id"] = json!(event.prompt_id().to_string());
+    data["occurred_at"] = json!(event.occurred_at().to_rfc3339());
+    data
+}
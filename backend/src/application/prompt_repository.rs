@@ -1,14 +1,41 @@
+use crate::application::AppError;
+use crate::domain::audit::AuditEntry;
 use crate::domain::prompt::Prompt;
 use async_trait::async_trait;
 use uuid::Uuid;
 
+/// Message of the `PreconditionFailed` that `save` returns when the prompt
+/// no longer has the revision the caller's `If-Match` named.
+pub const ETAG_MISMATCH: &str = "Prompt changed since the supplied ETag";
+/// Message of the `Conflict` that `save` returns when another writer saved
+/// the prompt after it was loaded and no `If-Match` was sent.
+pub const REVISION_CONFLICT: &str = "Prompt was modified concurrently; reload and retry";
+
 #[async_trait]
 pub trait PromptRepository: Send + Sync {
-    async fn save(&self, prompt: &Prompt) -> Result<(), String>;
+    /// Persists the prompt's pending changes and returns its stored revision.
+    /// With `expected_revision` (from `If-Match`) the save fails with
+    /// `PreconditionFailed` if another writer saved since; without it, a
+    /// save that changes anything but new feedback or suggestions fails with
+    /// `Conflict` if another writer saved since load, while appends never
+    /// conflict. `audit` is written in the same transaction.
+    async fn save(&self, prompt: &Prompt, audit: &[AuditEntry], expected_revision: Option<u64>) -> Result<u64, AppError>;
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Prompt>, String>;
     async fn find_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String>;
+    /// Like `find_by_id_and_user` for a load that leads to `save` or
+    /// `delete`: always read from storage, never from a cache, so the change
+    /// is made against the latest stored state.
+    async fn find_for_update(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String> {
+        self.find_by_id_and_user(id, user_id).await
+    }
+    /// Like `find_by_id_and_user` but without feedback and improvement
+    /// suggestions, for read-only paths such as rendering.
+    async fn find_content_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String>;
+    /// The caller's prompts among `ids`, without feedback and improvement suggestions.
+    async fn find_content_by_ids(&self, ids: &[Uuid], user_id: Uuid) -> Result<Vec<Prompt>, String>;
     async fn find_all(&self) -> Result<Vec<Prompt>, String>;
     async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Prompt>, String>;
-    async fn find_by_tag(&self, tag_name: &str) -> Result<Vec<Prompt>, String>;
-    async fn delete(&self, id: Uuid) -> Result<(), String>;
+    /// The caller's prompts carrying the tag, without feedback and improvement suggestions.
+    async fn find_content_by_tag(&self, user_id: Uuid, tag_name: &str) -> Result<Vec<Prompt>, String>;
+    async fn delete(&self, id: Uuid, audit: &[AuditEntry]) -> Result<(), String>;
 }
\ No newline at end of file
@@ -0,0 +1,166 @@
+use serde_json::{json, Map, Value};
+use crate::domain::prompt::{ModelProvider, PromptType, PromptVersion};
+
+/// Builds a request body for the provider's chat API from a rendered version.
+///
+/// Fields the version does not configure are left out so the caller can fill
+/// them in (e.g. `model` for OpenAI, `max_tokens` for Anthropic). Gemini takes
+/// the model in the URL rather than the body.
+///
+/// A system prompt goes in the provider's system field; Anthropic's
+/// `messages` and Gemini's `contents` are then left out, as both APIs reject
+/// an empty list, and the caller adds them with the user turn.
+pub fn build_provider_payload(
+    provider: ModelProvider,
+    prompt_type: PromptType,
+    rendered_content: &str,
+    version: &PromptVersion,
+) -> Value {
+    match provider {
+        ModelProvider::OpenAI => openai_payload(prompt_type, rendered_content, version),
+        ModelProvider::Anthropic => anthropic_payload(prompt_type, rendered_content, version),
+        ModelProvider::Gemini => gemini_payload(prompt_type, rendered_content, version),
+    }
+}
+
+fn openai_payload(prompt_type: PromptType, rendered_content: &str, version: &PromptVersion) -> Value {
+    let role = match prompt_type {
+        PromptType::System => "system",
+        PromptType::User => "user",
+    };
+
+    let mut body = Map::new();
+    body.insert("messages".to_string(), json!([{ "role": role, "content": rendered_content }]));
+
+    if let Some(config) = version.model_config() {
+        body.insert("model".to_string(), json!(config.model()));
+        insert_opt(&mut body, "temperature", config.temperature());
+        insert_opt(&mut body, "top_p", config.top_p());
+        insert_opt(&mut body, "max_completion_tokens", config.max_tokens());
+        if !config.stop_sequences().is_empty() {
+            body.insert("stop".to_string(), json!(config.stop_sequences()));
+        }
+        insert_opt(&mut body, "seed", config.seed());
+    }
+
+    if !version.tools().is_empty() {
+        let tools: Vec<Value> = version.tools().iter().map(|tool| json!({
+            "type": "function",
+            "function": without_nulls(json!({
+                "name": tool.name(),
+                "description": tool.description(),
+                "parameters": tool.parameters(),
+            })),
+        })).collect();
+        body.insert("tools".to_string(), Value::Array(tools));
+    }
+
+    if let Some(schema) = version.output_schema() {
+        body.insert("response_format".to_string(), json!({
+            "type": "json_schema",
+            "json_schema": {
+                "name": schema.name(),
+                "schema": schema.schema(),
+                "strict": schema.is_strict(),
+            },
+        }));
+    }
+
+    Value::Object(body)
+}
+
+fn anthropic_payload(prompt_type: PromptType, rendered_content: &str, version: &PromptVersion) -> Value {
+    let mut body = Map::new();
+
+    match prompt_type {
+        PromptType::System => {
+            body.insert("system".to_string(), json!(rendered_content));
+        }
+        PromptType::User => {
+            body.insert("messages".to_string(), json!([{ "role": "user", "content": rendered_content }]));
+        }
+    }
+
+    if let Some(config) = version.model_config() {
+        body.insert("model".to_string(), json!(config.model()));
+        insert_opt(&mut body, "max_tokens", config.max_tokens());
+        insert_opt(&mut body, "temperature", config.temperature());
+        insert_opt(&mut body, "top_p", config.top_p());
+        if !config.stop_sequences().is_empty() {
+            body.insert("stop_sequences".to_string(), json!(config.stop_sequences()));
+        }
+    }
+
+    if !version.tools().is_empty() {
+        let tools: Vec<Value> = version.tools().iter().map(|tool| without_nulls(json!({
+            "name": tool.name(),
+            "description": tool.description(),
+            "input_schema": tool.parameters(),
+        }))).collect();
+        body.insert("tools".to_string(), Value::Array(tools));
+    }
+
+    if let Some(schema) = version.output_schema() {
+        body.insert("output_format".to_string(), json!({
+            "type": "json_schema",
+            "schema": schema.schema(),
+        }));
+    }
+
+    Value::Object(body)
+}
+
+fn gemini_payload(prompt_type: PromptType, rendered_content: &str, version: &PromptVersion) -> Value {
+    let mut body = Map::new();
+
+    match prompt_type {
+        PromptType::System => {
+            body.insert("systemInstruction".to_string(), json!({ "parts": [{ "text": rendered_content }] }));
+        }
+        PromptType::User => {
+            body.insert("contents".to_string(), json!([{ "role": "user", "parts": [{ "text": rendered_content }] }]));
+        }
+    }
+
+    let mut generation_config = Map::new();
+    if let Some(config) = version.model_config() {
+        insert_opt(&mut generation_config, "temperature", config.temperature());
+        insert_opt(&mut generation_config, "topP", config.top_p());
+        insert_opt(&mut generation_config, "maxOutputTokens", config.max_tokens());
+        if !config.stop_sequences().is_empty() {
+            generation_config.insert("stopSequences".to_string(), json!(config.stop_sequences()));
+        }
+        insert_opt(&mut generation_config, "seed", config.seed());
+    }
+    if let Some(schema) = version.output_schema() {
+        generation_config.insert("responseMimeType".to_string(), json!("application/json"));
+        generation_config.insert("responseJsonSchema".to_string(), schema.schema().clone());
+    }
+    if !generation_config.is_empty() {
+        body.insert("generationConfig".to_string(), Value::Object(generation_config));
+    }
+
+    if !version.tools().is_empty() {
+        let declarations: Vec<Value> = version.tools().iter().map(|tool| without_nulls(json!({
+            "name": tool.name(),
+            "description": tool.description(),
+            "parameters": tool.parameters(),
+        }))).collect();
+        body.insert("tools".to_string(), json!([{ "functionDeclarations": declarations }]));
+    }
+
+    Value::Object(body)
+}
+
+fn insert_opt<T: serde::Serialize>(body: &mut Map<String, Value>, key: &str, value: Option<T>) {
+    if let Some(v) = value {
+        body.insert(key.to_string(), json!(v));
+    }
+}
+
+fn without_nulls(mut value: Value) -> Value {
+    if let Value::Object(map) = &mut value {
+        map.retain(|_, v| !v.is_null());
+    }
+    value
+}
@@ -0,0 +1,12 @@
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub invalidations: u64,
+    pub entries: usize,
+}
+
+/// Read-through cache in front of the prompt repository.
+pub trait ReadCache: Send + Sync {
+    fn stats(&self) -> CacheStats;
+}
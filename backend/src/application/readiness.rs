@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+
+/// Checks that a dependency the server cannot work without is usable.
+#[async_trait]
+pub trait ReadinessProbe: Send + Sync {
+    /// Name reported in the `/readyz` body, e.g. `database`.
+    fn name(&self) -> &'static str;
+
+    /// Returns a short, client-safe reason when the dependency is not ready.
+    async fn check(&self) -> Result<(), String>;
+}
@@ -0,0 +1,53 @@
+use crate::application::RenderRepository;
+use crate::domain::render::RenderEvent;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+const QUEUE_CAPACITY: usize = 10_000;
+const WRITE_BATCH: usize = 500;
+
+/// Record of every successful render, written by the render use cases.
+/// Events are queued and inserted in batches by a background writer, so a
+/// render never waits on storage.
+pub struct RenderLog {
+    queue: mpsc::Sender<RenderEvent>,
+    store_context: bool,
+}
+
+impl RenderLog {
+    /// With `store_context` off only a hash of each render's context is kept.
+    /// Spawns the writer, so it must be called inside the runtime.
+    pub fn new(repository: Arc<dyn RenderRepository>, store_context: bool) -> Self {
+        let (queue, events) = mpsc::channel(QUEUE_CAPACITY);
+        tokio::spawn(write_batches(repository, events));
+        Self { queue, store_context }
+    }
+
+    /// Renders are reads, so events that don't fit in the queue or fail to
+    /// write are logged instead of failing the request; feedback that
+    /// references a render not (yet) written is rejected.
+    pub fn record(&self, events: &[RenderEvent]) {
+        let mut dropped = 0;
+        for event in events {
+            let event = if self.store_context { event.clone() } else { event.without_context() };
+            if self.queue.try_send(event).is_err() {
+                dropped += 1;
+            }
+        }
+        if dropped > 0 {
+            tracing::error!(render_count = dropped, "Render log queue is full; dropping render events");
+        }
+    }
+}
+
+/// Drains the queue until every `RenderLog` is gone, writing whatever has
+/// piled up since the last insert in one go.
+async fn write_batches(repository: Arc<dyn RenderRepository>, mut events: mpsc::Receiver<RenderEvent>) {
+    let mut batch = Vec::with_capacity(WRITE_BATCH);
+    while events.recv_many(&mut batch, WRITE_BATCH).await > 0 {
+        if let Err(e) = repository.append(&batch).await {
+            tracing::error!(error = %e, render_count = batch.len(), "Failed to write render events");
+        }
+        batch.clear();
+    }
+}
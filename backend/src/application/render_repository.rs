@@ -0,0 +1,9 @@
+use async_trait::async_trait;
+use uuid::Uuid;
+use crate::domain::render::RenderEvent;
+
+#[async_trait]
+pub trait RenderRepository: Send + Sync {
+    async fn append(&self, events: &[RenderEvent]) -> Result<(), String>;
+    async fn find_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<RenderEvent>, String>;
+}
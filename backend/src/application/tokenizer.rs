@@ -0,0 +1,10 @@
+use crate::domain::prompt::{ModelConfig, TokenCount};
+
+pub trait Tokenizer: Send + Sync {
+    /// Counts tokens using the encoding of the configured model, falling back
+    /// to a default encoding when no model is configured or it is unknown.
+    fn count_tokens(&self, text: &str, model_config: Option<&ModelConfig>) -> TokenCount;
+
+    /// Context window of the configured model, if known.
+    fn context_window(&self, model_config: &ModelConfig) -> Option<u32>;
+}
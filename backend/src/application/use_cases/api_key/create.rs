@@ -1,8 +1,12 @@
 use std::sync::Arc;
+use serde_json::json;
 use uuid::Uuid;
 
 use crate::application::api_key_repository::ApiKeyRepository;
+use crate::application::AppError;
 use crate::domain::api_key::{ApiKey, generate_api_key, hash_api_key, get_key_prefix};
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
 
 pub struct CreateApiKey {
     repository: Arc<dyn ApiKeyRepository>,
@@ -13,14 +17,14 @@ impl CreateApiKey {
         Self { repository }
     }
 
-    pub async fn execute(&self, user_id: Uuid, name: String) -> Result<(Uuid, String), String> {
+    pub async fn execute(&self, actor: Actor, name: String) -> Result<(Uuid, String), AppError> {
         // Validate name
         if name.trim().is_empty() {
-            return Err("API key name cannot be empty".to_string());
+            return Err(AppError::validation("name", "API key name cannot be empty"));
         }
 
         if name.len() > 255 {
-            return Err("API key name too long (max 255 characters)".to_string());
+            return Err(AppError::validation("name", "API key name too long (max 255 characters)"));
         }
 
         // Generate the actual API key (this will be shown to user only once)
@@ -33,14 +37,38 @@ impl CreateApiKey {
         let key_prefix = get_key_prefix(&api_key_value);
 
         // Create the API key entity
-        let api_key = ApiKey::new(user_id, name, key_hash, key_prefix);
+        let api_key = ApiKey::new(actor.user_id, name, key_hash, key_prefix);
 
         let api_key_id = api_key.id();
 
         // Save to repository
-        self.repository.create(&api_key).await?;
+        let entry = AuditEntry::new(actor, AuditAction::ApiKeyCreated)
+            .with_target(api_key_id)
+            .with_after(api_key_summary(&api_key));
+        self.repository.create(&api_key, &[entry]).await?;
 
         // Return the ID and the PLAIN TEXT key (only time we return it!)
         Ok((api_key_id, api_key_value))
     }
-}
\ No newline at end of file
+}
+
+/// Identifies a key in audit entries; never includes the hash.
+pub(super) fn api_key_summary(api_key: &ApiKey) -> serde_json::Value {
+    json!({
+        "name": api_key.name(),
+        "key_prefix": api_key.key_prefix(),
+    })
+}
+
+/// The caller's key with `id`, for the `before` side of an audit entry.
+pub(super) async fn find_api_key(
+    repository: &dyn ApiKeyRepository,
+    id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<ApiKey>, String> {
+    Ok(repository
+        .find_by_user_id(user_id)
+        .await?
+        .into_iter()
+        .find(|key| key.id() == id))
+}
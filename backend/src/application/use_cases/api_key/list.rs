@@ -2,6 +2,7 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::application::api_key_repository::ApiKeyRepository;
+use crate::application::AppError;
 use crate::domain::api_key::ApiKey;
 
 pub struct ListApiKeys {
@@ -13,7 +14,7 @@ impl ListApiKeys {
         Self { repository }
     }
 
-    pub async fn execute(&self, user_id: Uuid) -> Result<Vec<ApiKey>, String> {
-        self.repository.find_by_user_id(user_id).await
+    pub async fn execute(&self, user_id: Uuid) -> Result<Vec<ApiKey>, AppError> {
+        Ok(self.repository.find_by_user_id(user_id).await?)
     }
 }
\ No newline at end of file
@@ -2,6 +2,10 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::application::api_key_repository::ApiKeyRepository;
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use super::create::{api_key_summary, find_api_key};
 
 pub struct RevokeApiKey {
     repository: Arc<dyn ApiKeyRepository>,
@@ -12,7 +16,14 @@ impl RevokeApiKey {
         Self { repository }
     }
 
-    pub async fn execute(&self, api_key_id: Uuid, user_id: Uuid) -> Result<(), String> {
-        self.repository.deactivate(api_key_id, user_id).await
+    pub async fn execute(&self, api_key_id: Uuid, actor: Actor) -> Result<(), AppError> {
+        let api_key = find_api_key(self.repository.as_ref(), api_key_id, actor.user_id).await?;
+        let mut entry = AuditEntry::new(actor, AuditAction::ApiKeyRevoked).with_target(api_key_id);
+        if let Some(api_key) = api_key {
+            entry = entry.with_before(api_key_summary(&api_key));
+        }
+        self.repository.deactivate(api_key_id, actor.user_id, &[entry]).await?;
+
+        Ok(())
     }
-}
\ No newline at end of file
+}
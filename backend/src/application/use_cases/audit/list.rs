@@ -0,0 +1,31 @@
+use crate::application::{AuditRepository, AuditQuery};
+use crate::application::AppError;
+use crate::domain::audit::AuditEntry;
+use std::sync::Arc;
+
+pub const MAX_AUDIT_PAGE_SIZE: i64 = 1_000;
+
+pub struct ListAuditEntries {
+    repository: Arc<dyn AuditRepository>,
+}
+
+impl ListAuditEntries {
+    pub fn new(repository: Arc<dyn AuditRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Entries are scoped to `query.user_id`, so a prompt's history stays
+    /// readable by its owner after the prompt is deleted.
+    pub async fn execute(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, AppError> {
+        if !(1..=MAX_AUDIT_PAGE_SIZE).contains(&query.limit) {
+            return Err(AppError::validation("limit", format!("limit must be between 1 and {}", MAX_AUDIT_PAGE_SIZE)));
+        }
+        if let (Some(since), Some(until)) = (query.since, query.until)
+            && since >= until
+        {
+            return Err(AppError::validation("until", "until must be later than since"));
+        }
+
+        Ok(self.repository.find(query).await?)
+    }
+}
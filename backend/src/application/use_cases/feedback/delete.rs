@@ -1,4 +1,8 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use super::submit::{feedback_summary, find_feedback};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -14,17 +18,28 @@ impl DeleteFeedback {
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
+        actor: Actor,
         version_id: Uuid,
         feedback_id: Uuid,
-    ) -> Result<(), String> {
+        expected_revision: Option<u64>,
+    ) -> Result<u64, AppError> {
         let mut prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_for_update(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        prompt.check_revision(expected_revision)?;
 
+        let before = find_feedback(&prompt, version_id, feedback_id).map(feedback_summary);
         prompt.delete_feedback(version_id, feedback_id)?;
-        self.repository.save(&prompt).await?;
-        Ok(())
+
+        let mut entry = AuditEntry::new(actor, AuditAction::FeedbackDeleted)
+            .with_prompt(prompt_id)
+            .with_target(feedback_id);
+        if let Some(before) = before {
+            entry = entry.with_before(before);
+        }
+        let revision = self.repository.save(&prompt, &[entry], expected_revision).await?;
+
+        Ok(revision)
     }
 }
\ No newline at end of file
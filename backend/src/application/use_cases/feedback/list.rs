@@ -0,0 +1,69 @@
+use crate::application::PromptRepository;
+use crate::application::AppError;
+use crate::domain::actor::ActorSource;
+use crate::domain::prompt::Feedback;
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Narrows a prompt's feedback. Author filters never match feedback that
+/// predates authorship tracking.
+#[derive(Default)]
+pub struct FeedbackFilter {
+    pub version_id: Option<Uuid>,
+    pub author_id: Option<Uuid>,
+    pub api_key_id: Option<Uuid>,
+    pub source: Option<ActorSource>,
+}
+
+impl FeedbackFilter {
+    fn matches(&self, feedback: &Feedback) -> bool {
+        if self.version_id.is_some_and(|id| id != feedback.version_id()) {
+            return false;
+        }
+        if self.author_id.is_none() && self.api_key_id.is_none() && self.source.is_none() {
+            return true;
+        }
+        feedback.created_by().is_some_and(|author| {
+            self.author_id.is_none_or(|id| id == author.user_id)
+                && self.api_key_id.is_none_or(|id| Some(id) == author.api_key_id)
+                && self.source.is_none_or(|source| source == author.source)
+        })
+    }
+}
+
+pub struct ListFeedback {
+    repository: Arc<dyn PromptRepository>,
+}
+
+impl ListFeedback {
+    pub fn new(repository: Arc<dyn PromptRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Feedback across the prompt's versions, oldest version first.
+    pub async fn execute(
+        &self,
+        prompt_id: Uuid,
+        user_id: Uuid,
+        filter: &FeedbackFilter,
+    ) -> Result<Vec<Feedback>, AppError> {
+        let prompt = self.repository
+            .find_by_id_and_user(prompt_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+
+        if let Some(version_id) = filter.version_id {
+            prompt
+                .find_version_by_id(version_id)
+                .ok_or_else(|| AppError::not_found("Version not found"))?;
+        }
+
+        Ok(prompt
+            .versions()
+            .iter()
+            .flat_map(|version| version.feedbacks())
+            .filter(|feedback| filter.matches(feedback))
+            .cloned()
+            .collect())
+    }
+}
@@ -1,7 +1,9 @@
 pub mod submit;
 pub mod update;
 pub mod delete;
+pub mod list;
 
-pub use submit::SubmitFeedback;
-pub use update::UpdateFeedback;
-pub use delete::DeleteFeedback;
\ No newline at end of file
+pub use submit::{SubmitFeedback, FeedbackInput};
+pub use update::{UpdateFeedback, FeedbackUpdate};
+pub use delete::DeleteFeedback;
+pub use list::{ListFeedback, FeedbackFilter};
@@ -1,47 +1,135 @@
-use crate::application::PromptRepository;
-use crate::domain::prompt::TestScenario;
+use crate::application::{PromptRepository, RenderRepository};
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use crate::domain::prompt::{CallDetails, Feedback, FeedbackDraft, Prompt, TestScenario};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// A feedback submission as received from the caller.
+pub struct FeedbackInput {
+    /// May be omitted when `render_id` is given.
+    pub version_id: Option<Uuid>,
+    pub render_id: Option<Uuid>,
+    pub rating: u8,
+    pub comment: Option<String>,
+    pub test_input: Option<String>,
+    pub test_actual_output: Option<String>,
+    pub test_expected_output: Option<String>,
+    pub call_details: Option<CallDetails>,
+}
+
 pub struct SubmitFeedback {
     repository: Arc<dyn PromptRepository>,
+    render_repository: Arc<dyn RenderRepository>,
 }
 
 impl SubmitFeedback {
-    pub fn new(repository: Arc<dyn PromptRepository>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<dyn PromptRepository>, render_repository: Arc<dyn RenderRepository>) -> Self {
+        Self { repository, render_repository }
     }
 
+    /// A referenced render supplies the test scenario input when the caller
+    /// sends an output without one, and the call's render context.
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
-        version_id: Uuid,
-        rating: u8,
-        comment: Option<String>,
-        input: Option<String>,
-        actual_output: Option<String>,
-        expected_output: Option<String>,
-    ) -> Result<Uuid, String> {
+        actor: Actor,
+        input: FeedbackInput,
+        expected_revision: Option<u64>,
+    ) -> Result<(Uuid, u64), AppError> {
+        let FeedbackInput {
+            version_id,
+            render_id,
+            rating,
+            comment,
+            test_input: input,
+            test_actual_output: actual_output,
+            test_expected_output: expected_output,
+            call_details,
+        } = input;
+
         let mut prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_for_update(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        prompt.check_revision(expected_revision)?;
+
+        let render = match render_id {
+            Some(render_id) => Some(
+                self.render_repository
+                    .find_by_id_and_user(render_id, actor.user_id)
+                    .await?
+                    .ok_or_else(|| AppError::not_found("Render not found"))?,
+            ),
+            None => None,
+        };
+        if render.as_ref().is_some_and(|r| r.prompt_id() != prompt_id) {
+            return Err(AppError::validation("render_id", "Render belongs to a different prompt"));
+        }
 
-        let version = prompt.versions_mut()
-            .iter_mut()
-            .find(|v| v.id() == version_id)
-            .ok_or_else(|| "Version not found".to_string())?;
+        let version_id = match (version_id, render.as_ref().map(|r| r.version_id())) {
+            (Some(version_id), Some(rendered)) if version_id != rendered => {
+                return Err(AppError::validation("version_id", "version_id does not match the render's version"));
+            }
+            (Some(version_id), _) | (None, Some(version_id)) => version_id,
+            (None, None) => return Err(AppError::validation("version_id", "Either version_id or render_id is required")),
+        };
+
+        let rendered_context = render.as_ref().and_then(|r| r.context());
+        let input = match (input, &actual_output) {
+            (None, Some(_)) if render.is_some() => Some(
+                rendered_context
+                    .map(|context| context.to_string())
+                    .ok_or_else(|| AppError::validation("test_input", "test_input is required because the render's context was not stored"))?,
+            ),
+            (input, _) => input,
+        };
+        let call_details = match &render {
+            Some(render) => Some(call_details.unwrap_or_default().link_render(render.id(), rendered_context.cloned())),
+            None => call_details,
+        };
 
         let test_scenario = match (input, actual_output) {
             (Some(inp), Some(out)) => Some(TestScenario::new(inp, out, expected_output)?),
             (None, None) => None,
-            _ => return Err("Both input and actual_output are required for test scenario".to_string()),
+            _ => return Err(AppError::Validation {
+                field: None,
+                message: "Both input and actual_output are required for test scenario".to_string(),
+            }),
         };
 
         let feedback_id = Uuid::new_v4();
-        version.add_feedback(feedback_id, rating, comment, test_scenario)?;
-        self.repository.save(&prompt).await?;
-        Ok(feedback_id)
+        let draft = FeedbackDraft { rating, comment, test_scenario, call_details };
+        prompt.add_feedback(version_id, feedback_id, draft, actor)?;
+
+        let audit: Vec<AuditEntry> = find_feedback(&prompt, version_id, feedback_id)
+            .map(|feedback| {
+                AuditEntry::new(actor, AuditAction::FeedbackSubmitted)
+                    .with_prompt(prompt_id)
+                    .with_target(feedback_id)
+                    .with_after(feedback_summary(feedback))
+            })
+            .into_iter()
+            .collect();
+        let revision = self.repository.save(&prompt, &audit, expected_revision).await?;
+
+        Ok((feedback_id, revision))
     }
+}
+
+pub(super) fn find_feedback(prompt: &Prompt, version_id: Uuid, feedback_id: Uuid) -> Option<&Feedback> {
+    prompt
+        .find_version_by_id(version_id)
+        .and_then(|version| version.find_feedback(feedback_id))
+}
+
+/// The feedback fields that `UpdateFeedback` can change, for audit entries.
+pub(super) fn feedback_summary(feedback: &Feedback) -> serde_json::Value {
+    json!({
+        "version_id": feedback.version_id().to_string(),
+        "rating": feedback.rating(),
+        "comment": feedback.comment(),
+    })
 }
\ No newline at end of file
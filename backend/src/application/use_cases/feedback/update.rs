@@ -1,7 +1,18 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use super::submit::{feedback_summary, find_feedback};
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Fields to change on a feedback; `None` leaves a field as it is.
+pub struct FeedbackUpdate {
+    pub rating: Option<u8>,
+    /// `Some(None)` clears the comment.
+    pub comment: Option<Option<String>>,
+}
+
 pub struct UpdateFeedback {
     repository: Arc<dyn PromptRepository>,
 }
@@ -14,19 +25,32 @@ impl UpdateFeedback {
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
+        actor: Actor,
         version_id: Uuid,
         feedback_id: Uuid,
-        rating: Option<u8>,
-        comment: Option<Option<String>>,
-    ) -> Result<(), String> {
+        update: FeedbackUpdate,
+        expected_revision: Option<u64>,
+    ) -> Result<u64, AppError> {
         let mut prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_for_update(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        prompt.check_revision(expected_revision)?;
+
+        let before = find_feedback(&prompt, version_id, feedback_id).map(feedback_summary);
+        prompt.update_feedback(version_id, feedback_id, update.rating, update.comment)?;
+
+        let mut entry = AuditEntry::new(actor, AuditAction::FeedbackUpdated)
+            .with_prompt(prompt_id)
+            .with_target(feedback_id);
+        if let Some(before) = before {
+            entry = entry.with_before(before);
+        }
+        if let Some(after) = find_feedback(&prompt, version_id, feedback_id).map(feedback_summary) {
+            entry = entry.with_after(after);
+        }
+        let revision = self.repository.save(&prompt, &[entry], expected_revision).await?;
 
-        prompt.update_feedback(version_id, feedback_id, rating, comment)?;
-        self.repository.save(&prompt).await?;
-        Ok(())
+        Ok(revision)
     }
 }
\ No newline at end of file
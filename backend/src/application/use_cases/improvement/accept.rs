@@ -1,43 +1,70 @@
-use crate::application::PromptRepository;
+use crate::application::{PromptRepository, Tokenizer};
+use crate::application::AppError;
+use crate::application::use_cases::version::create::version_summary;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
 use crate::domain::prompt::Version;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Number and changelog of the version an accepted suggestion becomes.
+pub struct AcceptedVersion {
+    pub version: Version,
+    pub changelog: Option<String>,
+}
+
 pub struct AcceptImprovementSuggestion {
     repository: Arc<dyn PromptRepository>,
+    tokenizer: Arc<dyn Tokenizer>,
 }
 
 impl AcceptImprovementSuggestion {
-    pub fn new(repository: Arc<dyn PromptRepository>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<dyn PromptRepository>, tokenizer: Arc<dyn Tokenizer>) -> Self {
+        Self { repository, tokenizer }
     }
 
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
+        actor: Actor,
         source_version_id: Uuid,
         suggestion_id: Uuid,
-        new_version: String,
-        changelog: Option<String>,
-    ) -> Result<Uuid, String> {
+        accepted: AcceptedVersion,
+        expected_revision: Option<u64>,
+    ) -> Result<(Uuid, u64), AppError> {
         let mut prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_for_update(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        prompt.check_revision(expected_revision)?;
 
-        let version_number = Version::from_str(&new_version)?;
         let new_version_id = Uuid::new_v4();
 
-        prompt.accept_improvement(
+        let version = prompt.accept_improvement(
             source_version_id,
             suggestion_id,
             new_version_id,
-            version_number,
-            changelog,
+            accepted.version,
+            accepted.changelog,
+            actor,
         )?;
+        let token_count = self.tokenizer.count_tokens(version.content(), version.model_config());
+        prompt.record_token_count(new_version_id, token_count)?;
+
+        let audit: Vec<AuditEntry> = prompt
+            .find_version_by_id(new_version_id)
+            .map(|version| {
+                let mut after = version_summary(version);
+                after["version_id"] = new_version_id.to_string().into();
+                AuditEntry::new(actor, AuditAction::SuggestionAccepted)
+                    .with_prompt(prompt_id)
+                    .with_target(suggestion_id)
+                    .with_after(after)
+            })
+            .into_iter()
+            .collect();
+        let revision = self.repository.save(&prompt, &audit, expected_revision).await?;
 
-        self.repository.save(&prompt).await?;
-        Ok(new_version_id)
+        Ok((new_version_id, revision))
     }
 }
\ No newline at end of file
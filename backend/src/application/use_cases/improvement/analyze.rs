@@ -1,49 +1,68 @@
 use crate::application::{PromptRepository, AIService};
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct AnalyzeFeedbackAndSuggest {
     repository: Arc<dyn PromptRepository>,
-    ai_service: Arc<dyn AIService>,
+    /// `None` when analysis is switched off or no AI provider is configured.
+    ai_service: Option<Arc<dyn AIService>>,
 }
 
 impl AnalyzeFeedbackAndSuggest {
-    pub fn new(repository: Arc<dyn PromptRepository>, ai_service: Arc<dyn AIService>) -> Self {
+    pub fn new(repository: Arc<dyn PromptRepository>, ai_service: Option<Arc<dyn AIService>>) -> Self {
         Self { repository, ai_service }
     }
 
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
+        actor: Actor,
         version_id: Uuid,
-    ) -> Result<Uuid, String> {
+        expected_revision: Option<u64>,
+    ) -> Result<(Uuid, u64), AppError> {
+        let ai_service = self.ai_service
+            .as_ref()
+            .ok_or_else(|| AppError::Unavailable("Feedback analysis is not enabled on this server".to_string()))?;
+
         let mut prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_for_update(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        prompt.check_revision(expected_revision)?;
 
-        let version = prompt.versions_mut()
-            .iter_mut()
-            .find(|v| v.id() == version_id)
-            .ok_or_else(|| "Version not found".to_string())?;
+        let version = prompt
+            .find_version_by_id(version_id)
+            .ok_or_else(|| AppError::not_found("Version not found"))?;
 
         if version.feedbacks().is_empty() {
-            return Err("No feedback available to analyze".to_string());
+            return Err(AppError::Validation {
+                field: None,
+                message: "No feedback available to analyze".to_string(),
+            });
         }
 
-        let (suggested_content, ai_rationale) = self.ai_service
+        let (suggested_content, ai_rationale) = ai_service
             .analyze_feedback_and_suggest(
                 version.content(),
                 version.content_type(),
                 version.feedbacks(),
             )
-            .await?;
+            .await
+            .map_err(AppError::Upstream)?;
 
         let suggestion_id = Uuid::new_v4();
-        version.create_improvement_suggestion(suggestion_id, suggested_content, ai_rationale)?;
+        prompt.create_improvement_suggestion(version_id, suggestion_id, suggested_content, ai_rationale, actor)?;
+
+        let entry = AuditEntry::new(actor, AuditAction::SuggestionCreated)
+            .with_prompt(prompt_id)
+            .with_target(suggestion_id)
+            .with_after(json!({ "version_id": version_id.to_string(), "source": "ai" }));
+        let revision = self.repository.save(&prompt, &[entry], expected_revision).await?;
 
-        self.repository.save(&prompt).await?;
-        Ok(suggestion_id)
+        Ok((suggestion_id, revision))
     }
 }
\ No newline at end of file
@@ -1,4 +1,8 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -14,25 +18,27 @@ impl CreateImprovementSuggestion {
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
+        actor: Actor,
         version_id: Uuid,
         suggested_content: String,
         ai_rationale: String,
-    ) -> Result<Uuid, String> {
+        expected_revision: Option<u64>,
+    ) -> Result<(Uuid, u64), AppError> {
         let mut prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_for_update(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
-
-        let version = prompt.versions_mut()
-            .iter_mut()
-            .find(|v| v.id() == version_id)
-            .ok_or_else(|| "Version not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        prompt.check_revision(expected_revision)?;
 
         let suggestion_id = Uuid::new_v4();
-        version.create_improvement_suggestion(suggestion_id, suggested_content, ai_rationale)?;
+        prompt.create_improvement_suggestion(version_id, suggestion_id, suggested_content, ai_rationale, actor)?;
+
+        let entry = AuditEntry::new(actor, AuditAction::SuggestionCreated)
+            .with_prompt(prompt_id)
+            .with_target(suggestion_id)
+            .with_after(json!({ "version_id": version_id.to_string(), "source": "manual" }));
+        let revision = self.repository.save(&prompt, &[entry], expected_revision).await?;
 
-        self.repository.save(&prompt).await?;
-        Ok(suggestion_id)
+        Ok((suggestion_id, revision))
     }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
 use crate::domain::prompt::ImprovementSuggestion;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -17,15 +18,15 @@ impl GetSuggestionsForVersion {
         prompt_id: Uuid,
         user_id: Uuid,
         version_id: Uuid,
-    ) -> Result<Vec<ImprovementSuggestion>, String> {
+    ) -> Result<Vec<ImprovementSuggestion>, AppError> {
         let prompt = self.repository
             .find_by_id_and_user(prompt_id, user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
 
         let version = prompt
             .find_version_by_id(version_id)
-            .ok_or_else(|| "Version not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Version not found"))?;
 
         Ok(version.improvement_suggestions().to_vec())
     }
@@ -5,7 +5,7 @@ pub mod get_for_version;
 pub mod analyze;
 
 pub use create::CreateImprovementSuggestion;
-pub use accept::AcceptImprovementSuggestion;
+pub use accept::{AcceptImprovementSuggestion, AcceptedVersion};
 pub use decline::DeclineImprovementSuggestion;
 pub use get_for_version::GetSuggestionsForVersion;
 pub use analyze::AnalyzeFeedbackAndSuggest;
\ No newline at end of file
@@ -4,10 +4,14 @@ pub mod tag;
 pub mod feedback;
 pub mod api_key;
 pub mod improvement;
+pub mod webhook;
+pub mod audit;
 
 pub use prompt::*;
 pub use version::*;
 pub use tag::*;
 pub use feedback::*;
 pub use api_key::*;
-pub use improvement::*;
\ No newline at end of file
+pub use improvement::*;
+pub use webhook::*;
+pub use audit::*;
\ No newline at end of file
@@ -1,5 +1,9 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
 use crate::domain::prompt::{Prompt, PromptType};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -14,14 +18,32 @@ impl CreatePrompt {
 
     pub async fn execute(
         &self,
-        user_id: Uuid,
+        actor: Actor,
         name: String,
         description: Option<String>,
         prompt_type: PromptType,
-    ) -> Result<Uuid, String> {
+        max_render_tokens: Option<u32>,
+    ) -> Result<(Uuid, u64), AppError> {
+        if max_render_tokens == Some(0) {
+            return Err(AppError::validation("max_render_tokens", "max_render_tokens must be greater than 0"));
+        }
+
         let id = Uuid::new_v4();
-        let prompt = Prompt::new(id, user_id, name, description, prompt_type);
-        self.repository.save(&prompt).await?;
-        Ok(id)
+        let prompt = Prompt::new(id, actor.user_id, name, description, prompt_type, max_render_tokens);
+        let entry = AuditEntry::new(actor, AuditAction::PromptCreated)
+            .with_prompt(id)
+            .with_after(prompt_summary(&prompt));
+        let revision = self.repository.save(&prompt, &[entry], None).await?;
+
+        Ok((id, revision))
     }
-}
\ No newline at end of file
+}
+
+/// The prompt fields that `UpdatePrompt` can change, for audit entries.
+pub(super) fn prompt_summary(prompt: &Prompt) -> serde_json::Value {
+    json!({
+        "name": prompt.name(),
+        "description": prompt.description(),
+        "max_render_tokens": prompt.max_render_tokens(),
+    })
+}
@@ -1,4 +1,8 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use super::create::prompt_summary;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -11,12 +15,18 @@ impl DeletePrompt {
         Self { repository }
     }
 
-    pub async fn execute(&self, prompt_id: Uuid, user_id: Uuid) -> Result<(), String> {
-        self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+    pub async fn execute(&self, prompt_id: Uuid, actor: Actor, expected_revision: Option<u64>) -> Result<(), AppError> {
+        let prompt = self.repository
+            .find_for_update(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        prompt.check_revision(expected_revision)?;
 
-        self.repository.delete(prompt_id).await
+        let entry = AuditEntry::new(actor, AuditAction::PromptDeleted)
+            .with_prompt(prompt_id)
+            .with_before(prompt_summary(&prompt));
+        self.repository.delete(prompt_id, &[entry]).await?;
+
+        Ok(())
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,5 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
 use crate::domain::prompt::Prompt;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -12,10 +13,10 @@ impl GetPrompt {
         Self { repository }
     }
 
-    pub async fn execute(&self, prompt_id: Uuid, user_id: Uuid) -> Result<Prompt, String> {
+    pub async fn execute(&self, prompt_id: Uuid, user_id: Uuid) -> Result<Prompt, AppError> {
         self.repository
             .find_by_id_and_user(prompt_id, user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())
+            .ok_or_else(|| AppError::not_found("Prompt not found"))
     }
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
 use crate::domain::prompt::Prompt;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -12,7 +13,7 @@ impl ListPrompts {
         Self { repository }
     }
 
-    pub async fn execute(&self, user_id: Uuid) -> Result<Vec<Prompt>, String> {
-        self.repository.find_by_user(user_id).await
+    pub async fn execute(&self, user_id: Uuid) -> Result<Vec<Prompt>, AppError> {
+        Ok(self.repository.find_by_user(user_id).await?)
     }
 }
\ No newline at end of file
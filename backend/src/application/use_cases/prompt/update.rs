@@ -1,4 +1,8 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use super::create::prompt_summary;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -14,14 +18,18 @@ impl UpdatePrompt {
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
+        actor: Actor,
         name: Option<String>,
         description: Option<Option<String>>,
-    ) -> Result<(), String> {
+        max_render_tokens: Option<Option<u32>>,
+        expected_revision: Option<u64>,
+    ) -> Result<u64, AppError> {
         let mut prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_for_update(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        prompt.check_revision(expected_revision)?;
+        let before = prompt_summary(&prompt);
 
         if let Some(n) = name {
             prompt.update_name(n)?;
@@ -31,7 +39,16 @@ impl UpdatePrompt {
             prompt.update_description(d);
         }
 
-        self.repository.save(&prompt).await?;
-        Ok(())
+        if let Some(m) = max_render_tokens {
+            prompt.update_max_render_tokens(m)?;
+        }
+
+        let entry = AuditEntry::new(actor, AuditAction::PromptUpdated)
+            .with_prompt(prompt_id)
+            .with_before(before)
+            .with_after(prompt_summary(&prompt));
+        let revision = self.repository.save(&prompt, &[entry], expected_revision).await?;
+
+        Ok(revision)
     }
-}
\ No newline at end of file
+}
@@ -1,4 +1,8 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -14,18 +18,32 @@ impl CreateTag {
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
+        actor: Actor,
         tag_name: String,
         version_id: Uuid,
-    ) -> Result<(), String> {
+        expected_revision: Option<u64>,
+    ) -> Result<u64, AppError> {
         let mut prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_for_update(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        prompt.check_revision(expected_revision)?;
 
-        let tag_id = Uuid::new_v4();
-        prompt.tag_version(tag_id, tag_name, version_id)?;
-        self.repository.save(&prompt).await?;
-        Ok(())
+        let previous = prompt.find_tag(&tag_name).map(|tag| (tag.id(), tag.version_id()));
+        let tag_id = previous.map_or_else(Uuid::new_v4, |(id, _)| id);
+        prompt.tag_version(tag_id, tag_name.clone(), version_id)?;
+
+        let entry = match previous {
+            Some((_, previous_version_id)) => AuditEntry::new(actor, AuditAction::TagMoved)
+                .with_before(json!({ "tag": tag_name, "version_id": previous_version_id.to_string() })),
+            None => AuditEntry::new(actor, AuditAction::TagCreated),
+        };
+        let entry = entry
+            .with_prompt(prompt_id)
+            .with_target(tag_id)
+            .with_after(json!({ "tag": tag_name, "version_id": version_id.to_string() }));
+        let revision = self.repository.save(&prompt, &[entry], expected_revision).await?;
+
+        Ok(revision)
     }
 }
\ No newline at end of file
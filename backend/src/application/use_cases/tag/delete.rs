@@ -1,4 +1,8 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -14,16 +18,30 @@ impl DeleteTag {
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
+        actor: Actor,
         tag_name: String,
-    ) -> Result<(), String> {
+        expected_revision: Option<u64>,
+    ) -> Result<u64, AppError> {
         let mut prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_for_update(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        prompt.check_revision(expected_revision)?;
 
+        let removed = prompt.find_tag(&tag_name).map(|tag| (tag.id(), tag.version_id()));
         prompt.remove_tag(&tag_name)?;
-        self.repository.save(&prompt).await?;
-        Ok(())
+
+        let audit: Vec<AuditEntry> = removed
+            .map(|(tag_id, version_id)| {
+                AuditEntry::new(actor, AuditAction::TagDeleted)
+                    .with_prompt(prompt_id)
+                    .with_target(tag_id)
+                    .with_before(json!({ "tag": tag_name, "version_id": version_id.to_string() }))
+            })
+            .into_iter()
+            .collect();
+        let revision = self.repository.save(&prompt, &audit, expected_revision).await?;
+
+        Ok(revision)
     }
 }
\ No newline at end of file
@@ -0,0 +1,68 @@
+use crate::application::PromptRepository;
+use crate::application::AppError;
+use crate::domain::prompt::{PromptType, PromptVersion};
+use sha2::{Sha256, Digest};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct TagBundleEntry {
+    pub prompt_id: Uuid,
+    pub prompt_name: String,
+    pub prompt_type: PromptType,
+    pub version: PromptVersion,
+}
+
+pub struct TagBundle {
+    pub tag_name: String,
+    pub etag: String,
+    pub entries: Vec<TagBundleEntry>,
+}
+
+pub struct GetTagBundle {
+    repository: Arc<dyn PromptRepository>,
+}
+
+impl GetTagBundle {
+    pub fn new(repository: Arc<dyn PromptRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Collects the version each of the user's prompts has under `tag_name`.
+    /// The ETag changes whenever the bundle body can: a prompt joins or
+    /// leaves the tag, is renamed, or its tag moves to another version,
+    /// even one with the same digest.
+    pub async fn execute(&self, user_id: Uuid, tag_name: String) -> Result<TagBundle, AppError> {
+        let prompts = self.repository.find_content_by_tag(user_id, &tag_name).await?;
+
+        let mut entries: Vec<TagBundleEntry> = prompts
+            .iter()
+            .filter_map(|prompt| {
+                let tag = prompt.find_tag(&tag_name)?;
+                let version = prompt.find_version_by_id(tag.version_id())?;
+                Some(TagBundleEntry {
+                    prompt_id: prompt.id(),
+                    prompt_name: prompt.name().to_string(),
+                    prompt_type: prompt.prompt_type(),
+                    version: version.clone(),
+                })
+            })
+            .collect();
+        entries.sort_by(|a, b| a.prompt_name.cmp(&b.prompt_name).then(a.prompt_id.cmp(&b.prompt_id)));
+
+        let mut hasher = Sha256::new();
+        hasher.update(tag_name.as_bytes());
+        for entry in &entries {
+            hasher.update(b"\n");
+            hasher.update(entry.prompt_id.as_bytes());
+            hasher.update(entry.prompt_name.as_bytes());
+            hasher.update(b"\0");
+            hasher.update(entry.version.id().as_bytes());
+            hasher.update(entry.version.version_string().as_bytes());
+            hasher.update(b"\0");
+            hasher.update(entry.version.digest().as_bytes());
+        }
+        let etag = hex::encode(hasher.finalize());
+
+        Ok(TagBundle { tag_name, etag, entries })
+    }
+}
@@ -1,4 +1,5 @@
 use crate::application::PromptRepository;
+use crate::application::AppError;
 use crate::domain::prompt::PromptVersion;
 use std::sync::Arc;
 use uuid::Uuid;
@@ -17,19 +18,19 @@ impl GetVersionByTag {
         prompt_id: Uuid,
         user_id: Uuid,
         tag_name: String,
-    ) -> Result<PromptVersion, String> {
+    ) -> Result<PromptVersion, AppError> {
         let prompt = self.repository
             .find_by_id_and_user(prompt_id, user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
 
         let tag = prompt
             .find_tag(&tag_name)
-            .ok_or_else(|| "Tag not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Tag not found"))?;
 
         prompt
             .find_version_by_id(tag.version_id())
             .cloned()
-            .ok_or_else(|| "Version not found".to_string())
+            .ok_or_else(|| AppError::not_found("Version not found"))
     }
 }
\ No newline at end of file
@@ -1,7 +1,9 @@
 pub mod create;
 pub mod delete;
+pub mod get_bundle;
 pub mod get_version_by_tag;
 
 pub use create::CreateTag;
 pub use delete::DeleteTag;
+pub use get_bundle::{GetTagBundle, TagBundle};
 pub use get_version_by_tag::GetVersionByTag;
\ No newline at end of file
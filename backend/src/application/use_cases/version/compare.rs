@@ -0,0 +1,71 @@
+use crate::application::{PromptRepository, PricingTable, CostEstimate};
+use crate::application::AppError;
+use crate::domain::prompt::PromptVersion;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub struct VersionComparison {
+    pub base: PromptVersion,
+    pub target: PromptVersion,
+    pub base_cost: Option<CostEstimate>,
+    pub target_cost: Option<CostEstimate>,
+    pub token_delta: Option<i64>,
+    pub cost_delta_usd: Option<f64>,
+}
+
+pub struct CompareVersions {
+    repository: Arc<dyn PromptRepository>,
+    pricing: Arc<PricingTable>,
+}
+
+impl CompareVersions {
+    pub fn new(repository: Arc<dyn PromptRepository>, pricing: Arc<PricingTable>) -> Self {
+        Self { repository, pricing }
+    }
+
+    pub async fn execute(
+        &self,
+        prompt_id: Uuid,
+        user_id: Uuid,
+        base_version_id: Uuid,
+        target_version_id: Uuid,
+    ) -> Result<VersionComparison, AppError> {
+        let prompt = self.repository
+            .find_content_by_id_and_user(prompt_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+
+        let base = prompt
+            .find_version_by_id(base_version_id)
+            .ok_or_else(|| AppError::not_found("Base version not found"))?;
+        let target = prompt
+            .find_version_by_id(target_version_id)
+            .ok_or_else(|| AppError::not_found("Target version not found"))?;
+
+        let base_cost = self.estimate(base);
+        let target_cost = self.estimate(target);
+
+        let token_delta = match (base.token_count(), target.token_count()) {
+            (Some(b), Some(t)) => Some(t.tokens() as i64 - b.tokens() as i64),
+            _ => None,
+        };
+        let cost_delta_usd = match (base_cost, target_cost) {
+            (Some(b), Some(t)) => Some(t.input_cost_usd - b.input_cost_usd),
+            _ => None,
+        };
+
+        Ok(VersionComparison {
+            base: base.clone(),
+            target: target.clone(),
+            base_cost,
+            target_cost,
+            token_delta,
+            cost_delta_usd,
+        })
+    }
+
+    fn estimate(&self, version: &PromptVersion) -> Option<CostEstimate> {
+        let token_count = version.token_count()?;
+        self.pricing.estimate_input_cost(version.model_config(), token_count.tokens())
+    }
+}
@@ -1,36 +1,61 @@
-use crate::application::PromptRepository;
-use crate::domain::prompt::{Version, ContentType};
+use crate::application::{PromptRepository, Tokenizer};
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use crate::domain::prompt::{PromptVersion, VersionDraft};
+use serde_json::json;
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct CreateVersion {
     repository: Arc<dyn PromptRepository>,
+    tokenizer: Arc<dyn Tokenizer>,
 }
 
 impl CreateVersion {
-    pub fn new(repository: Arc<dyn PromptRepository>) -> Self {
-        Self { repository }
+    pub fn new(repository: Arc<dyn PromptRepository>, tokenizer: Arc<dyn Tokenizer>) -> Self {
+        Self { repository, tokenizer }
     }
 
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
-        version: String,
-        content: String,
-        content_type: ContentType,
-        variables: Option<Vec<String>>,
-        changelog: Option<String>,
-    ) -> Result<Uuid, String> {
+        actor: Actor,
+        draft: VersionDraft,
+        expected_revision: Option<u64>,
+    ) -> Result<(Uuid, u64), AppError> {
         let mut prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_for_update(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        prompt.check_revision(expected_revision)?;
 
-        let version = Version::from_str(&version)?;
         let version_id = Uuid::new_v4();
-        prompt.add_version(version_id, version, content, content_type, variables, changelog)?;
-        self.repository.save(&prompt).await?;
-        Ok(version_id)
+        let token_count = self.tokenizer.count_tokens(&draft.content, draft.model_config.as_ref());
+        prompt.add_version(version_id, draft, actor)?;
+
+        prompt.record_token_count(version_id, token_count)?;
+
+        let audit: Vec<AuditEntry> = prompt
+            .find_version_by_id(version_id)
+            .map(|version| {
+                AuditEntry::new(actor, AuditAction::VersionCreated)
+                    .with_prompt(prompt_id)
+                    .with_target(version_id)
+                    .with_after(version_summary(version))
+            })
+            .into_iter()
+            .collect();
+        let revision = self.repository.save(&prompt, &audit, expected_revision).await?;
+
+        Ok((version_id, revision))
     }
+}
+
+/// Identifies a version in audit entries without copying its content.
+pub(crate) fn version_summary(version: &PromptVersion) -> serde_json::Value {
+    json!({
+        "version": version.version_string(),
+        "digest": version.digest(),
+    })
 }
\ No newline at end of file
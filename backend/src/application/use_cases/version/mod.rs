@@ -1,11 +1,15 @@
+pub mod compare;
 pub mod create;
 pub mod delete;
 pub mod get;
 pub mod render;
+pub mod render_batch;
 pub mod render_by_tag;
 
+pub use compare::{CompareVersions, VersionComparison};
 pub use create::CreateVersion;
 pub use delete::DeleteVersion;
 pub use get::GetVersion;
-pub use render::RenderVersion;
+pub use render::{RenderVersion, RenderedPrompt};
+pub use render_batch::{RenderBatch, BatchRenderer, BatchRenderItem, MAX_BATCH_RENDER_ITEMS};
 pub use render_by_tag::RenderVersionByTag;
\ No newline at end of file
@@ -1,32 +1,104 @@
-use crate::application::PromptRepository;
+use crate::application::{PromptRepository, Tokenizer, PricingTable, CostEstimate, RenderLog, build_provider_payload};
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::prompt::{Prompt, PromptVersion, ModelProvider, TokenCount};
+use crate::domain::render::RenderEvent;
 use std::sync::Arc;
 use uuid::Uuid;
 
+pub struct RenderedPrompt {
+    pub version: PromptVersion,
+    pub rendered_content: String,
+    pub token_count: TokenCount,
+    pub estimated_cost: Option<CostEstimate>,
+    pub payload: Option<serde_json::Value>,
+    /// Recorded in the render log; its id goes back to the caller.
+    pub render_event: RenderEvent,
+}
+
 pub struct RenderVersion {
     repository: Arc<dyn PromptRepository>,
+    tokenizer: Arc<dyn Tokenizer>,
+    pricing: Arc<PricingTable>,
+    render_log: Arc<RenderLog>,
 }
 
 impl RenderVersion {
-    pub fn new(repository: Arc<dyn PromptRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<dyn PromptRepository>,
+        tokenizer: Arc<dyn Tokenizer>,
+        pricing: Arc<PricingTable>,
+        render_log: Arc<RenderLog>,
+    ) -> Self {
+        Self { repository, tokenizer, pricing, render_log }
     }
 
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
+        actor: Actor,
         version_id: Uuid,
         context: Option<serde_json::Value>,
-    ) -> Result<String, String> {
+        format: Option<ModelProvider>,
+    ) -> Result<RenderedPrompt, AppError> {
         let prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_content_by_id_and_user(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
 
         let version = prompt
             .find_version_by_id(version_id)
-            .ok_or_else(|| "Version not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Version not found"))?;
 
-        version.render(context.as_ref())
+        let request = RenderRequest { tag: None, context: context.as_ref(), format, actor };
+        let rendered = render_prompt_version(&prompt, version, request, self.tokenizer.as_ref(), &self.pricing)?;
+        self.render_log.record(std::slice::from_ref(&rendered.render_event));
+        Ok(rendered)
     }
-}
\ No newline at end of file
+}
+
+/// Inputs of a single render besides the prompt and version themselves.
+pub(super) struct RenderRequest<'a> {
+    /// Tag the version was resolved through, if any.
+    pub tag: Option<&'a str>,
+    pub context: Option<&'a serde_json::Value>,
+    pub format: Option<ModelProvider>,
+    pub actor: Actor,
+}
+
+/// Renders a version of an already loaded prompt, enforcing the prompt's
+/// render budget and attaching token count, cost estimate and payload.
+/// The returned render event is not recorded yet.
+pub(super) fn render_prompt_version(
+    prompt: &Prompt,
+    version: &PromptVersion,
+    request: RenderRequest<'_>,
+    tokenizer: &dyn Tokenizer,
+    pricing: &PricingTable,
+) -> Result<RenderedPrompt, AppError> {
+    let RenderRequest { tag, context, format, actor } = request;
+    let rendered_content = tracing::info_span!("render.template", version_id = %version.id())
+        .in_scope(|| version.render(context))?;
+
+    let token_count = tracing::info_span!("render.count_tokens")
+        .in_scope(|| tokenizer.count_tokens(&rendered_content, version.model_config()));
+    let completion_tokens = version.model_config().and_then(|c| c.max_tokens());
+    let context_window = version.model_config().and_then(|c| tokenizer.context_window(c));
+    prompt.check_render_budget(token_count.tokens(), completion_tokens, context_window)?;
+    let estimated_cost = pricing.estimate_input_cost(version.model_config(), token_count.tokens());
+
+    let payload = format.map(|provider| {
+        build_provider_payload(provider, prompt.prompt_type(), &rendered_content, version)
+    });
+
+    let render_event = RenderEvent::new(actor, prompt.id(), version.id(), tag.map(|t| t.to_string()), context.cloned());
+
+    Ok(RenderedPrompt {
+        version: version.clone(),
+        rendered_content,
+        token_count,
+        estimated_cost,
+        payload,
+        render_event,
+    })
+}
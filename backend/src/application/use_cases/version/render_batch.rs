@@ -0,0 +1,102 @@
+use crate::application::{PromptRepository, Tokenizer, PricingTable, RenderLog};
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::render::RenderEvent;
+use crate::domain::prompt::{Prompt, ModelProvider};
+use super::render::{RenderRequest, RenderedPrompt, render_prompt_version};
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const MAX_BATCH_RENDER_ITEMS: usize = 10_000;
+
+pub struct BatchRenderItem {
+    pub prompt_id: Uuid,
+    pub tag_name: String,
+    pub context: Option<serde_json::Value>,
+    pub format: Option<ModelProvider>,
+}
+
+pub struct RenderBatch {
+    repository: Arc<dyn PromptRepository>,
+    tokenizer: Arc<dyn Tokenizer>,
+    pricing: Arc<PricingTable>,
+    render_log: Arc<RenderLog>,
+}
+
+impl RenderBatch {
+    pub fn new(
+        repository: Arc<dyn PromptRepository>,
+        tokenizer: Arc<dyn Tokenizer>,
+        pricing: Arc<PricingTable>,
+        render_log: Arc<RenderLog>,
+    ) -> Self {
+        Self { repository, tokenizer, pricing, render_log }
+    }
+
+    /// Loads every distinct prompt referenced by the batch in one go. Items are
+    /// then rendered one at a time with [`BatchRenderer::render`], so callers
+    /// can stream results instead of holding the whole batch in memory, and
+    /// recorded with [`BatchRenderer::record`].
+    pub async fn prepare(
+        &self,
+        actor: Actor,
+        prompt_ids: impl IntoIterator<Item = Uuid>,
+    ) -> Result<BatchRenderer, AppError> {
+        let mut prompt_ids: Vec<Uuid> = prompt_ids.into_iter().collect();
+        prompt_ids.sort_unstable();
+        prompt_ids.dedup();
+
+        let prompts = self.repository
+            .find_content_by_ids(&prompt_ids, actor.user_id)
+            .await?
+            .into_iter()
+            .map(|prompt| (prompt.id(), prompt))
+            .collect();
+
+        Ok(BatchRenderer {
+            prompts,
+            actor,
+            tokenizer: self.tokenizer.clone(),
+            pricing: self.pricing.clone(),
+            render_log: self.render_log.clone(),
+        })
+    }
+}
+
+pub struct BatchRenderer {
+    prompts: HashMap<Uuid, Prompt>,
+    actor: Actor,
+    tokenizer: Arc<dyn Tokenizer>,
+    pricing: Arc<PricingTable>,
+    render_log: Arc<RenderLog>,
+}
+
+impl BatchRenderer {
+    pub fn render(&self, item: &BatchRenderItem) -> Result<RenderedPrompt, AppError> {
+        let prompt = self.prompts
+            .get(&item.prompt_id)
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+
+        let tag = prompt
+            .find_tag(&item.tag_name)
+            .ok_or_else(|| AppError::not_found("Tag not found"))?;
+
+        let version = prompt
+            .find_version_by_id(tag.version_id())
+            .ok_or_else(|| AppError::not_found("Version not found"))?;
+
+        let request = RenderRequest {
+            tag: Some(&item.tag_name),
+            context: item.context.as_ref(),
+            format: item.format,
+            actor: self.actor,
+        };
+        render_prompt_version(prompt, version, request, self.tokenizer.as_ref(), &self.pricing)
+    }
+
+    /// Queues the render events of rendered items for the render log.
+    pub fn record(&self, events: &[RenderEvent]) {
+        self.render_log.record(events);
+    }
+}
@@ -1,36 +1,52 @@
-use crate::application::PromptRepository;
+use crate::application::{PromptRepository, Tokenizer, PricingTable, RenderLog};
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::prompt::ModelProvider;
+use super::render::{RenderRequest, RenderedPrompt, render_prompt_version};
 use std::sync::Arc;
 use uuid::Uuid;
 
 pub struct RenderVersionByTag {
     repository: Arc<dyn PromptRepository>,
+    tokenizer: Arc<dyn Tokenizer>,
+    pricing: Arc<PricingTable>,
+    render_log: Arc<RenderLog>,
 }
 
 impl RenderVersionByTag {
-    pub fn new(repository: Arc<dyn PromptRepository>) -> Self {
-        Self { repository }
+    pub fn new(
+        repository: Arc<dyn PromptRepository>,
+        tokenizer: Arc<dyn Tokenizer>,
+        pricing: Arc<PricingTable>,
+        render_log: Arc<RenderLog>,
+    ) -> Self {
+        Self { repository, tokenizer, pricing, render_log }
     }
 
     pub async fn execute(
         &self,
         prompt_id: Uuid,
-        user_id: Uuid,
+        actor: Actor,
         tag_name: String,
         context: Option<serde_json::Value>,
-    ) -> Result<String, String> {
+        format: Option<ModelProvider>,
+    ) -> Result<RenderedPrompt, AppError> {
         let prompt = self.repository
-            .find_by_id_and_user(prompt_id, user_id)
+            .find_content_by_id_and_user(prompt_id, actor.user_id)
             .await?
-            .ok_or_else(|| "Prompt not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Prompt not found"))?;
 
         let tag = prompt
             .find_tag(&tag_name)
-            .ok_or_else(|| "Tag not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Tag not found"))?;
 
         let version = prompt
             .find_version_by_id(tag.version_id())
-            .ok_or_else(|| "Version not found".to_string())?;
+            .ok_or_else(|| AppError::not_found("Version not found"))?;
 
-        version.render(context.as_ref())
+        let request = RenderRequest { tag: Some(&tag_name), context: context.as_ref(), format, actor };
+        let rendered = render_prompt_version(&prompt, version, request, self.tokenizer.as_ref(), &self.pricing)?;
+        self.render_log.record(std::slice::from_ref(&rendered.render_event));
+        Ok(rendered)
     }
 }
\ No newline at end of file
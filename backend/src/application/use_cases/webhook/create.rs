@@ -0,0 +1,59 @@
+use std::sync::Arc;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::application::{PromptRepository, WebhookRepository};
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use crate::domain::webhook::{Webhook, generate_webhook_secret};
+
+pub struct CreateWebhook {
+    repository: Arc<dyn WebhookRepository>,
+    prompt_repository: Arc<dyn PromptRepository>,
+    allow_private_targets: bool,
+}
+
+impl CreateWebhook {
+    /// `allow_private_targets` accepts loopback and private-network URLs,
+    /// for testing against a local receiver.
+    pub fn new(repository: Arc<dyn WebhookRepository>, prompt_repository: Arc<dyn PromptRepository>, allow_private_targets: bool) -> Self {
+        Self { repository, prompt_repository, allow_private_targets }
+    }
+
+    /// Returns the new webhook; its secret is only shown in this response.
+    pub async fn execute(
+        &self,
+        actor: Actor,
+        prompt_id: Option<Uuid>,
+        url: String,
+        event_types: Vec<String>,
+    ) -> Result<Webhook, AppError> {
+        if let Some(prompt_id) = prompt_id {
+            self.prompt_repository
+                .find_by_id_and_user(prompt_id, actor.user_id)
+                .await?
+                .ok_or_else(|| AppError::not_found("Prompt not found"))?;
+        }
+
+        let webhook = Webhook::new(actor.user_id, prompt_id, url, generate_webhook_secret(), event_types, self.allow_private_targets)?;
+
+        let mut entry = AuditEntry::new(actor, AuditAction::WebhookCreated)
+            .with_target(webhook.id())
+            .with_after(webhook_summary(&webhook));
+        if let Some(prompt_id) = prompt_id {
+            entry = entry.with_prompt(prompt_id);
+        }
+        self.repository.create(&webhook, &[entry]).await?;
+
+        Ok(webhook)
+    }
+}
+
+/// Identifies a webhook in audit entries; never includes the secret.
+pub(super) fn webhook_summary(webhook: &Webhook) -> serde_json::Value {
+    json!({
+        "url": webhook.url(),
+        "event_types": webhook.event_types(),
+    })
+}
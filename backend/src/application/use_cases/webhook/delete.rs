@@ -0,0 +1,33 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::WebhookRepository;
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use super::create::webhook_summary;
+
+pub struct DeleteWebhook {
+    repository: Arc<dyn WebhookRepository>,
+}
+
+impl DeleteWebhook {
+    pub fn new(repository: Arc<dyn WebhookRepository>) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, webhook_id: Uuid, actor: Actor) -> Result<(), AppError> {
+        let webhook = self.repository.find_by_id_and_user(webhook_id, actor.user_id).await?;
+
+        let mut entry = AuditEntry::new(actor, AuditAction::WebhookDeleted).with_target(webhook_id);
+        if let Some(webhook) = webhook {
+            if let Some(prompt_id) = webhook.prompt_id() {
+                entry = entry.with_prompt(prompt_id);
+            }
+            entry = entry.with_before(webhook_summary(&webhook));
+        }
+        self.repository.delete(webhook_id, actor.user_id, &[entry]).await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::WebhookRepository;
+use crate::application::AppError;
+use crate::domain::webhook::Webhook;
+
+pub struct ListWebhooks {
+    repository: Arc<dyn WebhookRepository>,
+}
+
+impl ListWebhooks {
+    pub fn new(repository: Arc<dyn WebhookRepository>) -> Self {
+        Self { repository }
+    }
+
+    pub async fn execute(&self, user_id: Uuid) -> Result<Vec<Webhook>, AppError> {
+        Ok(self.repository.find_by_user_id(user_id).await?)
+    }
+}
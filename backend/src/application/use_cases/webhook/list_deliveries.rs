@@ -0,0 +1,28 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::WebhookRepository;
+use crate::application::AppError;
+use crate::domain::webhook::WebhookDelivery;
+
+const MAX_DELIVERIES: i64 = 100;
+
+pub struct ListWebhookDeliveries {
+    repository: Arc<dyn WebhookRepository>,
+}
+
+impl ListWebhookDeliveries {
+    pub fn new(repository: Arc<dyn WebhookRepository>) -> Self {
+        Self { repository }
+    }
+
+    /// Most recent deliveries first.
+    pub async fn execute(&self, webhook_id: Uuid, user_id: Uuid) -> Result<Vec<WebhookDelivery>, AppError> {
+        self.repository
+            .find_by_id_and_user(webhook_id, user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Webhook not found"))?;
+
+        Ok(self.repository.find_deliveries(webhook_id, MAX_DELIVERIES).await?)
+    }
+}
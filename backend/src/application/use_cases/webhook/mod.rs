@@ -0,0 +1,11 @@
+pub mod create;
+pub mod list;
+pub mod delete;
+pub mod list_deliveries;
+pub mod redeliver;
+
+pub use create::CreateWebhook;
+pub use list::ListWebhooks;
+pub use delete::DeleteWebhook;
+pub use list_deliveries::ListWebhookDeliveries;
+pub use redeliver::RedeliverWebhookDelivery;
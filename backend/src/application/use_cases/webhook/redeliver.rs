@@ -0,0 +1,41 @@
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::{WebhookRepository, WebhookDispatcher};
+use crate::application::AppError;
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry};
+use serde_json::json;
+
+pub struct RedeliverWebhookDelivery {
+    repository: Arc<dyn WebhookRepository>,
+    dispatcher: Arc<WebhookDispatcher>,
+}
+
+impl RedeliverWebhookDelivery {
+    pub fn new(repository: Arc<dyn WebhookRepository>, dispatcher: Arc<WebhookDispatcher>) -> Self {
+        Self { repository, dispatcher }
+    }
+
+    /// Queues the delivery to be sent again with a fresh set of attempts.
+    pub async fn execute(&self, webhook_id: Uuid, delivery_id: Uuid, actor: Actor) -> Result<(), AppError> {
+        self.repository
+            .find_by_id_and_user(webhook_id, actor.user_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Webhook not found"))?;
+
+        let mut delivery = self.repository
+            .find_delivery(delivery_id, webhook_id)
+            .await?
+            .ok_or_else(|| AppError::not_found("Delivery not found"))?;
+
+        delivery.redeliver();
+        let entry = AuditEntry::new(actor, AuditAction::WebhookRedelivered)
+            .with_target(delivery_id)
+            .with_after(json!({ "webhook_id": webhook_id.to_string(), "event_type": delivery.event_type() }));
+        self.repository.save_delivery(&delivery, &[entry], None).await?;
+        self.dispatcher.wake();
+
+        Ok(())
+    }
+}
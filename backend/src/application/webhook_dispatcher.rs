@@ -0,0 +1,109 @@
+use crate::application::{AppError, WebhookRepository, WebhookSender, SEND_TIMEOUT};
+use crate::domain::prompt::PromptEvent;
+use crate::domain::webhook::{Webhook, WebhookDelivery, sign_payload};
+use chrono::Utc;
+use futures_util::{stream, StreamExt};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, Notify};
+
+const CLAIM_BATCH: i64 = 20;
+const CONCURRENT_SENDS: usize = 10;
+const CLAIM_LEASE: Duration = Duration::from_secs(60);
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+// A claimed batch goes out in `CLAIM_BATCH / CONCURRENT_SENDS` rounds of
+// sends; the lease has to outlast all of them or another instance takes
+// the deliveries over while they are still in flight.
+const _: () = assert!(
+    (CLAIM_BATCH as u64).div_ceil(CONCURRENT_SENDS as u64) * SEND_TIMEOUT.as_secs() < CLAIM_LEASE.as_secs()
+);
+
+/// Sends queued webhook deliveries in the background, so the request that
+/// caused an event never waits on a receiver. Deliveries are queued by the
+/// prompt repository in the transaction that saves the change, and this
+/// loop claims them from storage, so an event is delivered at least once
+/// even if notifications are dropped or an instance dies mid-attempt.
+pub struct WebhookDispatcher {
+    repository: Arc<dyn WebhookRepository>,
+    sender: Arc<dyn WebhookSender>,
+    wake: Notify,
+}
+
+impl WebhookDispatcher {
+    pub fn new(repository: Arc<dyn WebhookRepository>, sender: Arc<dyn WebhookSender>) -> Self {
+        Self {
+            repository,
+            sender,
+            wake: Notify::new(),
+        }
+    }
+
+    /// Runs the delivery loop until the process exits. `events` only wakes
+    /// it early so new deliveries go out before the next poll; a missed
+    /// event just waits for that poll.
+    pub fn start(self: Arc<Self>, mut events: broadcast::Receiver<PromptEvent>) {
+        let dispatcher = self.clone();
+        tokio::spawn(async move {
+            while let Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) = events.recv().await {
+                dispatcher.wake();
+            }
+        });
+
+        tokio::spawn(async move {
+            loop {
+                match self.deliver_due().await {
+                    // A full batch means more may be due; go again right away.
+                    Ok(claimed) if claimed as i64 == CLAIM_BATCH => continue,
+                    Ok(_) => {}
+                    Err(e) => tracing::error!(error = %e, "Webhook delivery failed"),
+                }
+                tokio::select! {
+                    _ = self.wake.notified() => {}
+                    _ = tokio::time::sleep(POLL_INTERVAL) => {}
+                }
+            }
+        });
+    }
+
+    /// Wakes the delivery loop, e.g. after a delivery was queued for redelivery.
+    pub fn wake(&self) {
+        self.wake.notify_one();
+    }
+
+    async fn deliver_due(&self) -> Result<usize, String> {
+        let claimed = self.repository.claim_due_deliveries(CLAIM_BATCH, CLAIM_LEASE).await?;
+        let count = claimed.len();
+
+        stream::iter(claimed)
+            .for_each_concurrent(CONCURRENT_SENDS, |(mut delivery, webhook)| async move {
+                let claimed_until = delivery.next_attempt_at();
+                self.attempt(&mut delivery, &webhook).await;
+                match self.repository.save_delivery(&delivery, &[], claimed_until).await {
+                    Ok(()) => {}
+                    Err(AppError::Conflict(e)) => tracing::warn!(delivery_id = %delivery.id(), "{}", e),
+                    Err(e) => tracing::error!(delivery_id = %delivery.id(), error = %e, "Failed to save webhook delivery"),
+                }
+            })
+            .await;
+
+        Ok(count)
+    }
+
+    async fn attempt(&self, delivery: &mut WebhookDelivery, webhook: &Webhook) {
+        let timestamp = Utc::now().timestamp();
+        let headers = [
+            ("Content-Type", "application/json".to_string()),
+            ("X-PromptKaizen-Event", delivery.event_type().to_string()),
+            ("X-PromptKaizen-Delivery", delivery.id().to_string()),
+            ("X-PromptKaizen-Timestamp", timestamp.to_string()),
+            ("X-PromptKaizen-Signature", sign_payload(webhook.secret(), timestamp, delivery.payload())),
+        ];
+
+        match self.sender.send(webhook.url(), &headers, delivery.payload()).await {
+            Ok(status) if (200..300).contains(&status) => delivery.record_success(status),
+            Ok(status) => delivery.record_failure(Some(status), format!("Receiver responded with {}", status)),
+            Err(e) => delivery.record_failure(None, e),
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use uuid::Uuid;
+use crate::application::AppError;
+use crate::domain::audit::AuditEntry;
+use crate::domain::webhook::{Webhook, WebhookDelivery};
+
+/// Message of the `Conflict` that `save_delivery` returns when the delivery
+/// no longer holds the claim the attempt was made under.
+pub const CLAIM_LOST: &str = "Delivery was claimed again before its attempt was saved";
+
+/// Mutations write their `audit` entries in the same transaction. Deliveries
+/// are queued by the prompt repository as part of each save.
+#[async_trait]
+pub trait WebhookRepository: Send + Sync {
+    async fn create(&self, webhook: &Webhook, audit: &[AuditEntry]) -> Result<(), String>;
+    async fn find_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Webhook>, String>;
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Webhook>, String>;
+    async fn delete(&self, id: Uuid, user_id: Uuid, audit: &[AuditEntry]) -> Result<(), AppError>;
+
+    /// With `claimed_until` (the `next_attempt_at` a claim set) the save
+    /// fails with `Conflict` unless the delivery still holds that claim, so
+    /// an attempt that outlived its lease cannot overwrite a newer one.
+    async fn save_delivery(&self, delivery: &WebhookDelivery, audit: &[AuditEntry], claimed_until: Option<DateTime<Utc>>) -> Result<(), AppError>;
+    async fn find_delivery(&self, id: Uuid, webhook_id: Uuid) -> Result<Option<WebhookDelivery>, String>;
+    async fn find_deliveries(&self, webhook_id: Uuid, limit: i64) -> Result<Vec<WebhookDelivery>, String>;
+
+    /// Takes up to `limit` pending deliveries that are due and pushes their
+    /// next attempt out by `lease`, so other instances skip them meanwhile.
+    async fn claim_due_deliveries(&self, limit: i64, lease: Duration) -> Result<Vec<(WebhookDelivery, Webhook)>, String>;
+}
@@ -0,0 +1,13 @@
+use async_trait::async_trait;
+use std::time::Duration;
+
+/// Longest a send may take before it is abandoned. The dispatcher sizes its
+/// claim lease from it, so implementations must not wait longer.
+pub const SEND_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[async_trait]
+pub trait WebhookSender: Send + Sync {
+    /// POSTs `body` to `url`, returning the response status, or an error if
+    /// no response was received.
+    async fn send(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<u16, String>;
+}
@@ -0,0 +1,354 @@
+use serde::Deserialize;
+use std::fmt::Display;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Server settings. Read from the TOML file named by `CONFIG_FILE` (or
+/// `config.toml` when it exists), then overridden by environment variables,
+/// so deployments can keep secrets and per-machine values in the environment.
+/// See `config.example.toml` for every key.
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub server: ServerConfig,
+    pub database: DatabaseConfig,
+    pub cors: CorsConfig,
+    pub ai: AiConfig,
+    pub cache: CacheConfig,
+    pub features: FeatureConfig,
+    pub telemetry: TelemetryConfig,
+    pub render_log: RenderLogConfig,
+    pub metrics: MetricsConfig,
+    pub webhooks: WebhooksConfig,
+    /// JSON file with model pricing overrides.
+    pub pricing_file: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    pub bind_address: SocketAddr,
+    /// How long in-flight requests get to finish after SIGTERM before the
+    /// remaining connections are dropped.
+    pub shutdown_timeout_secs: u64,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: SocketAddr::from(([0, 0, 0, 0], 3000)),
+            shutdown_timeout_secs: 25,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DatabaseConfig {
+    /// `sqlite:` URLs use a local file; anything else is PostgreSQL.
+    pub url: Option<String>,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout_secs: u64,
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            max_connections: 10,
+            min_connections: 0,
+            acquire_timeout_secs: 30,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CorsConfig {
+    /// Origins allowed to call the API from a browser; `*` allows any.
+    pub allowed_origins: Vec<String>,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self { allowed_origins: vec!["http://localhost:5173".to_string()] }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AiProvider {
+    OpenAI,
+    /// Canned suggestions, for development and tests.
+    Fake,
+}
+
+impl FromStr for AiProvider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "openai" => Ok(Self::OpenAI),
+            "fake" => Ok(Self::Fake),
+            _ => Err("must be 'openai' or 'fake'".to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AiConfig {
+    pub provider: AiProvider,
+    pub api_key: Option<String>,
+    pub model: String,
+    pub base_url: String,
+}
+
+impl Default for AiConfig {
+    fn default() -> Self {
+        Self {
+            provider: AiProvider::OpenAI,
+            api_key: None,
+            model: "gpt-4o-mini".to_string(),
+            base_url: "https://api.openai.com/v1".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CacheConfig {
+    /// 0 disables the prompt cache.
+    pub ttl_secs: u64,
+    pub max_entries: usize,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self { ttl_secs: 30, max_entries: 1000 }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FeatureConfig {
+    /// In-memory storage, fake AI, and requests without credentials act as
+    /// a fixed dev user.
+    pub dev_mode: bool,
+    /// Feedback analysis through the AI provider.
+    pub ai_analysis: bool,
+    /// Background delivery of webhooks. Deliveries are still recorded when
+    /// off, and another instance with it on sends them.
+    pub webhook_delivery: bool,
+}
+
+impl Default for FeatureConfig {
+    fn default() -> Self {
+        Self { dev_mode: false, ai_analysis: true, webhook_delivery: true }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines, for local development.
+    Pretty,
+    /// One JSON object per line with the enclosing span fields, for log
+    /// aggregation.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pretty" => Ok(Self::Pretty),
+            "json" => Ok(Self::Json),
+            _ => Err("must be 'pretty' or 'json'".to_string()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TelemetryConfig {
+    pub log_format: LogFormat,
+    /// `tracing` filter directives, e.g. `info,backend=debug`.
+    pub log_filter: String,
+    /// OTLP/HTTP collector base URL, e.g. `http://localhost:4318`. Traces
+    /// are only exported when set.
+    pub otlp_endpoint: Option<String>,
+    pub service_name: String,
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            log_format: LogFormat::Pretty,
+            log_filter: "info".to_string(),
+            otlp_endpoint: None,
+            service_name: "prompt-kaizen".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RenderLogConfig {
+    /// Keep each render's full context so feedback can fill in its test
+    /// input. When off only a SHA-256 of the context is stored.
+    pub store_context: bool,
+}
+
+impl Default for RenderLogConfig {
+    fn default() -> Self {
+        Self { store_context: true }
+    }
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetricsConfig {
+    /// Bearer token that `/metrics` and `/cache/stats` require. Both are
+    /// off when unset.
+    pub token: Option<String>,
+    /// Extra listener serving `/metrics` without the token, for scrapers
+    /// that cannot send one. Bind it to an address only the scraper can
+    /// reach.
+    pub bind_address: Option<SocketAddr>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebhooksConfig {
+    /// Accept webhook URLs on loopback and private networks, e.g. a local
+    /// receiver while testing. Always on in dev mode.
+    pub allow_private_targets: bool,
+}
+
+impl Config {
+    pub fn load() -> Result<Self, String> {
+        let mut config = match std::env::var("CONFIG_FILE") {
+            Ok(path) => Self::from_file(&path)?,
+            Err(_) if std::path::Path::new("config.toml").exists() => Self::from_file("config.toml")?,
+            Err(_) => Self::default(),
+        };
+        config.apply_env()?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn from_file(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read config file {}: {}", path, e))?;
+        toml::from_str(&text).map_err(|e| format!("Invalid config file {}: {}", path, e))
+    }
+
+    fn apply_env(&mut self) -> Result<(), String> {
+        override_with(&mut self.server.bind_address, "BIND_ADDRESS")?;
+        override_with(&mut self.server.shutdown_timeout_secs, "SHUTDOWN_TIMEOUT_SECS")?;
+
+        override_option(&mut self.database.url, "DATABASE_URL");
+        override_with(&mut self.database.max_connections, "DATABASE_MAX_CONNECTIONS")?;
+        override_with(&mut self.database.min_connections, "DATABASE_MIN_CONNECTIONS")?;
+        override_with(&mut self.database.acquire_timeout_secs, "DATABASE_ACQUIRE_TIMEOUT_SECS")?;
+
+        // ALLOWED_ORIGIN is the older single-origin form.
+        if let Some(origins) = env("CORS_ALLOWED_ORIGINS").or_else(|| env("ALLOWED_ORIGIN")) {
+            self.cors.allowed_origins = origins
+                .split(',')
+                .map(|origin| origin.trim().to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect();
+        }
+
+        override_with(&mut self.ai.provider, "AI_PROVIDER")?;
+        override_option(&mut self.ai.api_key, "OPENAI_API_KEY");
+        override_with(&mut self.ai.model, "OPENAI_MODEL")?;
+        override_with(&mut self.ai.base_url, "OPENAI_BASE_URL")?;
+
+        override_with(&mut self.cache.ttl_secs, "PROMPT_CACHE_TTL_SECS")?;
+        override_with(&mut self.cache.max_entries, "PROMPT_CACHE_MAX_ENTRIES")?;
+
+        override_flag(&mut self.features.dev_mode, "DEV_MODE")?;
+        override_flag(&mut self.features.ai_analysis, "FEATURE_AI_ANALYSIS")?;
+        override_flag(&mut self.features.webhook_delivery, "FEATURE_WEBHOOK_DELIVERY")?;
+
+        override_with(&mut self.telemetry.log_format, "LOG_FORMAT")?;
+        override_with(&mut self.telemetry.log_filter, "RUST_LOG")?;
+        override_option(&mut self.telemetry.otlp_endpoint, "OTEL_EXPORTER_OTLP_ENDPOINT");
+        override_with(&mut self.telemetry.service_name, "OTEL_SERVICE_NAME")?;
+
+        override_flag(&mut self.render_log.store_context, "RENDER_LOG_STORE_CONTEXT")?;
+
+        override_option(&mut self.metrics.token, "METRICS_TOKEN");
+        if let Some(address) = env("METRICS_BIND_ADDRESS") {
+            self.metrics.bind_address = Some(address.parse().map_err(|e| format!("Invalid METRICS_BIND_ADDRESS: {}", e))?);
+        }
+
+        override_flag(&mut self.webhooks.allow_private_targets, "WEBHOOKS_ALLOW_PRIVATE_TARGETS")?;
+
+        override_option(&mut self.pricing_file, "PRICING_FILE");
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.database.max_connections == 0 {
+            return Err("database.max_connections must be greater than 0".to_string());
+        }
+        if self.database.min_connections > self.database.max_connections {
+            return Err("database.min_connections cannot exceed database.max_connections".to_string());
+        }
+        if !self.features.dev_mode && self.database.url.is_none() {
+            return Err("database.url (DATABASE_URL) must be set unless dev mode is on".to_string());
+        }
+        for origin in &self.cors.allowed_origins {
+            if origin != "*" && origin.parse::<http::HeaderValue>().is_err() {
+                return Err(format!("Invalid CORS origin: {}", origin));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn shutdown_timeout(&self) -> Duration {
+        Duration::from_secs(self.server.shutdown_timeout_secs)
+    }
+
+    pub fn allow_private_webhook_targets(&self) -> bool {
+        self.webhooks.allow_private_targets || self.features.dev_mode
+    }
+}
+
+fn env(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+fn override_with<T: FromStr>(target: &mut T, name: &str) -> Result<(), String>
+where
+    T::Err: Display,
+{
+    if let Some(value) = env(name) {
+        *target = value.parse().map_err(|e| format!("Invalid {}: {}", name, e))?;
+    }
+    Ok(())
+}
+
+fn override_option(target: &mut Option<String>, name: &str) {
+    if let Some(value) = env(name) {
+        *target = Some(value);
+    }
+}
+
+fn override_flag(target: &mut bool, name: &str) -> Result<(), String> {
+    if let Some(value) = env(name) {
+        *target = match value.to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" | "on" => true,
+            "0" | "false" | "no" | "off" => false,
+            _ => return Err(format!("Invalid {}: expected true or false", name)),
+        };
+    }
+    Ok(())
+}
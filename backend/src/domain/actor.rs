@@ -0,0 +1,66 @@
+use uuid::Uuid;
+
+/// How a request reached the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActorSource {
+    /// A signed-in user session, i.e. the web app.
+    Ui,
+    /// An API key used directly.
+    ApiKey,
+    /// An API key used by an integration that identifies itself, such as
+    /// the n8n node.
+    Integration,
+}
+
+impl ActorSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ActorSource::Ui => "ui",
+            ActorSource::ApiKey => "api_key",
+            ActorSource::Integration => "integration",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        match s {
+            "ui" => Ok(ActorSource::Ui),
+            "api_key" => Ok(ActorSource::ApiKey),
+            "integration" => Ok(ActorSource::Integration),
+            _ => Err(format!("Invalid source: {}", s)),
+        }
+    }
+}
+
+/// Who performed a request: the account, the API key used if the request
+/// was not made with a user session, and how it was made.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Actor {
+    pub user_id: Uuid,
+    pub api_key_id: Option<Uuid>,
+    pub source: ActorSource,
+}
+
+impl Actor {
+    pub fn user(user_id: Uuid) -> Self {
+        Self { user_id, api_key_id: None, source: ActorSource::Ui }
+    }
+
+    pub fn api_key(user_id: Uuid, api_key_id: Uuid) -> Self {
+        Self { user_id, api_key_id: Some(api_key_id), source: ActorSource::ApiKey }
+    }
+
+    pub fn integration(user_id: Uuid, api_key_id: Uuid) -> Self {
+        Self { user_id, api_key_id: Some(api_key_id), source: ActorSource::Integration }
+    }
+
+    /// Rows written before the source was stored fall back to what the API
+    /// key column implies.
+    pub fn from_repository(user_id: Uuid, api_key_id: Option<Uuid>, source: Option<&str>) -> Result<Self, String> {
+        let source = match source {
+            Some(source) => ActorSource::from_str(source)?,
+            None if api_key_id.is_some() => ActorSource::ApiKey,
+            None => ActorSource::Ui,
+        };
+        Ok(Self { user_id, api_key_id, source })
+    }
+}
@@ -0,0 +1,82 @@
+/// What an audited mutation did, stored as `<target>.<verb>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    PromptCreated,
+    PromptUpdated,
+    PromptDeleted,
+    VersionCreated,
+    VersionDeleted,
+    TagCreated,
+    TagMoved,
+    TagDeleted,
+    FeedbackSubmitted,
+    FeedbackUpdated,
+    FeedbackDeleted,
+    SuggestionCreated,
+    SuggestionAccepted,
+    SuggestionDeclined,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+    ApiKeyDeleted,
+    WebhookCreated,
+    WebhookDeleted,
+    WebhookRedelivered,
+}
+
+impl AuditAction {
+    pub const ALL: &'static [AuditAction] = &[
+        AuditAction::PromptCreated,
+        AuditAction::PromptUpdated,
+        AuditAction::PromptDeleted,
+        AuditAction::VersionCreated,
+        AuditAction::VersionDeleted,
+        AuditAction::TagCreated,
+        AuditAction::TagMoved,
+        AuditAction::TagDeleted,
+        AuditAction::FeedbackSubmitted,
+        AuditAction::FeedbackUpdated,
+        AuditAction::FeedbackDeleted,
+        AuditAction::SuggestionCreated,
+        AuditAction::SuggestionAccepted,
+        AuditAction::SuggestionDeclined,
+        AuditAction::ApiKeyCreated,
+        AuditAction::ApiKeyRevoked,
+        AuditAction::ApiKeyDeleted,
+        AuditAction::WebhookCreated,
+        AuditAction::WebhookDeleted,
+        AuditAction::WebhookRedelivered,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::PromptCreated => "prompt.created",
+            AuditAction::PromptUpdated => "prompt.updated",
+            AuditAction::PromptDeleted => "prompt.deleted",
+            AuditAction::VersionCreated => "version.created",
+            AuditAction::VersionDeleted => "version.deleted",
+            AuditAction::TagCreated => "tag.created",
+            AuditAction::TagMoved => "tag.moved",
+            AuditAction::TagDeleted => "tag.deleted",
+            AuditAction::FeedbackSubmitted => "feedback.submitted",
+            AuditAction::FeedbackUpdated => "feedback.updated",
+            AuditAction::FeedbackDeleted => "feedback.deleted",
+            AuditAction::SuggestionCreated => "suggestion.created",
+            AuditAction::SuggestionAccepted => "suggestion.accepted",
+            AuditAction::SuggestionDeclined => "suggestion.declined",
+            AuditAction::ApiKeyCreated => "api_key.created",
+            AuditAction::ApiKeyRevoked => "api_key.revoked",
+            AuditAction::ApiKeyDeleted => "api_key.deleted",
+            AuditAction::WebhookCreated => "webhook.created",
+            AuditAction::WebhookDeleted => "webhook.deleted",
+            AuditAction::WebhookRedelivered => "webhook.redelivered",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        AuditAction::ALL
+            .iter()
+            .copied()
+            .find(|action| action.as_str() == s)
+            .ok_or_else(|| format!("Invalid audit action: {}", s))
+    }
+}
@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use uuid::Uuid;
+use super::AuditAction;
+use crate::domain::actor::Actor;
+
+/// Stored state of an audit entry, as read back by a repository.
+#[derive(Debug, Clone)]
+pub struct AuditEntryRecord {
+    pub id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    pub actor: Actor,
+    pub action: AuditAction,
+    pub prompt_id: Option<Uuid>,
+    pub target_id: Option<Uuid>,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+/// One audited mutation. Entries are never changed once written; `before`
+/// and `after` summarise the affected fields rather than whole objects.
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    id: Uuid,
+    occurred_at: DateTime<Utc>,
+    actor: Actor,
+    action: AuditAction,
+    prompt_id: Option<Uuid>,
+    target_id: Option<Uuid>,
+    before: Option<Value>,
+    after: Option<Value>,
+}
+
+impl AuditEntry {
+    pub fn new(actor: Actor, action: AuditAction) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            occurred_at: Utc::now(),
+            actor,
+            action,
+            prompt_id: None,
+            target_id: None,
+            before: None,
+            after: None,
+        }
+    }
+
+    pub fn from_repository(record: AuditEntryRecord) -> Self {
+        Self {
+            id: record.id,
+            occurred_at: record.occurred_at,
+            actor: record.actor,
+            action: record.action,
+            prompt_id: record.prompt_id,
+            target_id: record.target_id,
+            before: record.before,
+            after: record.after,
+        }
+    }
+
+    pub fn with_prompt(mut self, prompt_id: Uuid) -> Self {
+        self.prompt_id = Some(prompt_id);
+        self
+    }
+
+    /// The version, tag, feedback, suggestion, API key, webhook or delivery
+    /// the action applies to.
+    pub fn with_target(mut self, target_id: Uuid) -> Self {
+        self.target_id = Some(target_id);
+        self
+    }
+
+    pub fn with_before(mut self, before: Value) -> Self {
+        self.before = Some(before);
+        self
+    }
+
+    pub fn with_after(mut self, after: Value) -> Self {
+        self.after = Some(after);
+        self
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
+    pub fn actor(&self) -> Actor {
+        self.actor
+    }
+
+    pub fn action(&self) -> AuditAction {
+        self.action
+    }
+
+    pub fn prompt_id(&self) -> Option<Uuid> {
+        self.prompt_id
+    }
+
+    pub fn target_id(&self) -> Option<Uuid> {
+        self.target_id
+    }
+
+    pub fn before(&self) -> Option<&Value> {
+        self.before.as_ref()
+    }
+
+    pub fn after(&self) -> Option<&Value> {
+        self.after.as_ref()
+    }
+}
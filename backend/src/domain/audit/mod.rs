@@ -0,0 +1,5 @@
+mod audit_action;
+mod audit_entry;
+
+pub use audit_action::AuditAction;
+pub use audit_entry::{AuditEntry, AuditEntryRecord};
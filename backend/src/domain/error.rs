@@ -0,0 +1,59 @@
+use std::fmt;
+
+/// Why the domain rejected an operation. Messages are meant for API
+/// clients, so they never carry infrastructure details.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DomainError {
+    NotFound(String),
+    /// The operation clashes with the current state, e.g. a duplicate
+    /// version number or a suggestion that was already resolved.
+    Conflict(String),
+    /// A mutation was conditional on a revision the prompt is no longer at.
+    PreconditionFailed(String),
+    /// Invalid input; `field` names the offending request field when known.
+    Validation { field: Option<String>, message: String },
+}
+
+impl DomainError {
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::NotFound(message.into())
+    }
+
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::Conflict(message.into())
+    }
+
+    pub fn validation(field: &str, message: impl Into<String>) -> Self {
+        Self::Validation { field: Some(field.to_string()), message: message.into() }
+    }
+
+    /// Invalid input that isn't down to a single field.
+    pub fn invalid(message: impl Into<String>) -> Self {
+        Self::Validation { field: None, message: message.into() }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            Self::NotFound(message)
+            | Self::Conflict(message)
+            | Self::PreconditionFailed(message)
+            | Self::Validation { message, .. } => message,
+        }
+    }
+}
+
+impl fmt::Display for DomainError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for DomainError {}
+
+// Repositories report failures as strings; a stored value the domain
+// rejects is one of them.
+impl From<DomainError> for String {
+    fn from(error: DomainError) -> Self {
+        error.to_string()
+    }
+}
@@ -1,2 +1,7 @@
+pub mod error;
 pub mod prompt;
-pub mod api_key;
\ No newline at end of file
+pub mod api_key;
+pub mod webhook;
+pub mod audit;
+pub mod actor;
+pub mod render;
@@ -0,0 +1,123 @@
+use serde_json::Value;
+use uuid::Uuid;
+use crate::domain::error::DomainError;
+
+const MAX_IDENTIFIER_LENGTH: usize = 255;
+
+/// What happened on the production call a feedback is about, as reported by
+/// the caller. Every field is optional since callers know different things.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CallDetails {
+    model: Option<String>,
+    latency_ms: Option<u64>,
+    input_tokens: Option<u32>,
+    output_tokens: Option<u32>,
+    cost_usd: Option<f64>,
+    render_context: Option<Value>,
+    trace_id: Option<String>,
+    metadata: Option<Value>,
+    render_id: Option<Uuid>,
+}
+
+/// Call details as reported, before validation.
+#[derive(Debug, Clone, Default)]
+pub struct CallDetailsDraft {
+    pub model: Option<String>,
+    pub latency_ms: Option<u64>,
+    pub input_tokens: Option<u32>,
+    pub output_tokens: Option<u32>,
+    pub cost_usd: Option<f64>,
+    pub render_context: Option<Value>,
+    pub trace_id: Option<String>,
+    pub metadata: Option<Value>,
+}
+
+impl CallDetails {
+    pub fn new(draft: CallDetailsDraft) -> Result<Self, DomainError> {
+        let CallDetailsDraft { model, latency_ms, input_tokens, output_tokens, cost_usd, render_context, trace_id, metadata } = draft;
+        if model.as_deref().is_some_and(|m| m.trim().is_empty() || m.len() > MAX_IDENTIFIER_LENGTH) {
+            return Err(DomainError::validation("model", "Model must be between 1 and 255 characters"));
+        }
+        if latency_ms.is_some_and(|l| l > i64::MAX as u64) {
+            return Err(DomainError::validation("latency_ms", "latency_ms is out of range"));
+        }
+        if cost_usd.is_some_and(|c| !c.is_finite() || c < 0.0) {
+            return Err(DomainError::validation("cost_usd", "cost_usd must be a non-negative number"));
+        }
+        if render_context.as_ref().is_some_and(|c| !c.is_object()) {
+            return Err(DomainError::validation("render_context", "render_context must be a JSON object"));
+        }
+        if trace_id.as_deref().is_some_and(|t| t.trim().is_empty() || t.len() > MAX_IDENTIFIER_LENGTH) {
+            return Err(DomainError::validation("trace_id", "trace_id must be between 1 and 255 characters"));
+        }
+        if metadata.as_ref().is_some_and(|m| !m.is_object()) {
+            return Err(DomainError::validation("metadata", "metadata must be a JSON object"));
+        }
+
+        Ok(Self {
+            model,
+            latency_ms,
+            input_tokens,
+            output_tokens,
+            cost_usd,
+            render_context,
+            trace_id,
+            metadata,
+            render_id: None,
+        })
+    }
+
+    /// Ties the call to the render it used, taking the render's context when
+    /// the caller did not report one.
+    pub fn link_render(mut self, render_id: Uuid, context: Option<Value>) -> Self {
+        self.render_id = Some(render_id);
+        if self.render_context.is_none() {
+            self.render_context = context;
+        }
+        self
+    }
+
+    pub fn model(&self) -> Option<&str> {
+        self.model.as_deref()
+    }
+
+    pub fn latency_ms(&self) -> Option<u64> {
+        self.latency_ms
+    }
+
+    pub fn input_tokens(&self) -> Option<u32> {
+        self.input_tokens
+    }
+
+    pub fn output_tokens(&self) -> Option<u32> {
+        self.output_tokens
+    }
+
+    pub fn cost_usd(&self) -> Option<f64> {
+        self.cost_usd
+    }
+
+    /// The variables the version was rendered with for this call.
+    pub fn render_context(&self) -> Option<&Value> {
+        self.render_context.as_ref()
+    }
+
+    /// Identifier of the call in the caller's tracing or logging system.
+    pub fn trace_id(&self) -> Option<&str> {
+        self.trace_id.as_deref()
+    }
+
+    pub fn metadata(&self) -> Option<&Value> {
+        self.metadata.as_ref()
+    }
+
+    /// The render event the call used, when the feedback referenced one.
+    pub fn render_id(&self) -> Option<Uuid> {
+        self.render_id
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
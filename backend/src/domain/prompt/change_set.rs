@@ -0,0 +1,320 @@
+use uuid::Uuid;
+
+/// Rows of a prompt aggregate touched by mutations since it was loaded or
+/// created, so a repository can write only those instead of the whole tree.
+#[derive(Debug, Clone, Default)]
+pub struct PromptChangeSet {
+    prompt: bool,
+    touched: bool,
+    rewritten: bool,
+    versions: Vec<Uuid>,
+    deleted_versions: Vec<Uuid>,
+    tags: Vec<Uuid>,
+    deleted_tags: Vec<Uuid>,
+    feedbacks: Vec<(Uuid, Uuid)>,
+    deleted_feedbacks: Vec<Uuid>,
+    suggestions: Vec<(Uuid, Uuid)>,
+}
+
+impl PromptChangeSet {
+    pub fn is_empty(&self) -> bool {
+        !self.prompt
+            && !self.touched
+            && self.versions.is_empty()
+            && self.deleted_versions.is_empty()
+            && self.tags.is_empty()
+            && self.deleted_tags.is_empty()
+            && self.feedbacks.is_empty()
+            && self.deleted_feedbacks.is_empty()
+            && self.suggestions.is_empty()
+    }
+
+    /// Whether the prompt's own fields changed, as opposed to only its
+    /// versions, tags, feedback or suggestions.
+    pub fn prompt(&self) -> bool {
+        self.prompt
+    }
+
+    /// Whether `updated_at` moved because the prompt's fields, versions or
+    /// tags changed. Feedback and suggestions leave it alone.
+    pub fn touched(&self) -> bool {
+        self.touched
+    }
+
+    /// Whether the changes only add feedback or suggestions. Such rows
+    /// cannot contradict a concurrent save; anything else was decided
+    /// against the state as loaded.
+    pub fn is_append_only(&self) -> bool {
+        !self.prompt && !self.touched && !self.rewritten && self.deleted_feedbacks.is_empty()
+    }
+
+    /// Ids of versions added since load.
+    pub fn versions(&self) -> &[Uuid] {
+        &self.versions
+    }
+
+    pub fn deleted_versions(&self) -> &[Uuid] {
+        &self.deleted_versions
+    }
+
+    /// Ids of tags created or moved since load.
+    pub fn tags(&self) -> &[Uuid] {
+        &self.tags
+    }
+
+    pub fn deleted_tags(&self) -> &[Uuid] {
+        &self.deleted_tags
+    }
+
+    /// `(version_id, feedback_id)` pairs of feedback added or edited since load.
+    pub fn feedbacks(&self) -> &[(Uuid, Uuid)] {
+        &self.feedbacks
+    }
+
+    pub fn deleted_feedbacks(&self) -> &[Uuid] {
+        &self.deleted_feedbacks
+    }
+
+    /// `(version_id, suggestion_id)` pairs of suggestions created or resolved since load.
+    pub fn suggestions(&self) -> &[(Uuid, Uuid)] {
+        &self.suggestions
+    }
+
+    pub(super) fn mark_prompt(&mut self) {
+        self.prompt = true;
+        self.touched = true;
+    }
+
+    pub(super) fn mark_touched(&mut self) {
+        self.touched = true;
+    }
+
+    pub(super) fn mark_version(&mut self, version_id: Uuid) {
+        push_unique(&mut self.versions, version_id);
+    }
+
+    pub(super) fn mark_version_deleted(&mut self, version_id: Uuid) {
+        self.versions.retain(|id| *id != version_id);
+        push_unique(&mut self.deleted_versions, version_id);
+    }
+
+    pub(super) fn mark_tag(&mut self, tag_id: Uuid) {
+        push_unique(&mut self.tags, tag_id);
+    }
+
+    pub(super) fn mark_tag_deleted(&mut self, tag_id: Uuid) {
+        self.tags.retain(|id| *id != tag_id);
+        push_unique(&mut self.deleted_tags, tag_id);
+    }
+
+    pub(super) fn mark_feedback(&mut self, version_id: Uuid, feedback_id: Uuid) {
+        push_unique(&mut self.feedbacks, (version_id, feedback_id));
+    }
+
+    pub(super) fn mark_feedback_updated(&mut self, version_id: Uuid, feedback_id: Uuid) {
+        self.rewritten = true;
+        self.mark_feedback(version_id, feedback_id);
+    }
+
+    pub(super) fn mark_feedback_deleted(&mut self, feedback_id: Uuid) {
+        self.feedbacks.retain(|(_, id)| *id != feedback_id);
+        push_unique(&mut self.deleted_feedbacks, feedback_id);
+    }
+
+    pub(super) fn mark_suggestion(&mut self, version_id: Uuid, suggestion_id: Uuid) {
+        push_unique(&mut self.suggestions, (version_id, suggestion_id));
+    }
+
+    pub(super) fn mark_suggestion_resolved(&mut self, version_id: Uuid, suggestion_id: Uuid) {
+        self.rewritten = true;
+        self.mark_suggestion(version_id, suggestion_id);
+    }
+}
+
+fn push_unique<T: PartialEq>(items: &mut Vec<T>, item: T) {
+    if !items.contains(&item) {
+        items.push(item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::domain::actor::Actor;
+    use crate::domain::prompt::{ContentType, FeedbackDraft, Prompt, PromptRecord, PromptType, Version, VersionDraft};
+    use uuid::Uuid;
+
+    fn add_version(prompt: &mut Prompt, version: Version) -> Uuid {
+        let version_id = Uuid::new_v4();
+        let actor = Actor::user(prompt.user_id());
+        let draft = VersionDraft {
+            version,
+            content: "Hello".to_string(),
+            content_type: ContentType::Static,
+            variables: None,
+            changelog: None,
+            model_config: None,
+            tools: Vec::new(),
+            output_schema: None,
+        };
+        prompt.add_version(version_id, draft, actor).unwrap();
+        version_id
+    }
+
+    fn add_feedback(prompt: &mut Prompt, version_id: Uuid) -> Uuid {
+        let feedback_id = Uuid::new_v4();
+        let draft = FeedbackDraft { rating: 4, comment: None, test_scenario: None, call_details: None };
+        prompt.add_feedback(version_id, feedback_id, draft, Actor::user(prompt.user_id())).unwrap();
+        feedback_id
+    }
+
+    /// The prompt as a repository would return it after saving, with no
+    /// pending changes.
+    fn reload(prompt: &Prompt) -> Prompt {
+        Prompt::from_repository(PromptRecord {
+            id: prompt.id(),
+            user_id: prompt.user_id(),
+            name: prompt.name().to_string(),
+            description: prompt.description().map(str::to_string),
+            prompt_type: prompt.prompt_type(),
+            max_render_tokens: prompt.max_render_tokens(),
+            created_at: prompt.created_at(),
+            updated_at: prompt.updated_at(),
+            revision: prompt.revision() + 1,
+            versions: prompt.versions().to_vec(),
+            tags: prompt.tags().to_vec(),
+        })
+    }
+
+    fn saved_prompt() -> (Prompt, Uuid, Uuid) {
+        let mut prompt = Prompt::new(Uuid::new_v4(), Uuid::new_v4(), "greeting".to_string(), None, PromptType::System, None);
+        let v1 = add_version(&mut prompt, Version::new(1, 0, 0));
+        let v2 = add_version(&mut prompt, Version::new(1, 1, 0));
+        prompt.tag_version(Uuid::new_v4(), "production".to_string(), v1).unwrap();
+        (reload(&prompt), v1, v2)
+    }
+
+    #[test]
+    fn loaded_prompt_has_no_changes() {
+        let (prompt, _, _) = saved_prompt();
+
+        assert!(prompt.pending_changes().is_empty());
+    }
+
+    #[test]
+    fn tracks_added_versions_and_tags() {
+        let (mut prompt, _, _) = saved_prompt();
+
+        let v3 = add_version(&mut prompt, Version::new(2, 0, 0));
+        let tag_id = Uuid::new_v4();
+        prompt.tag_version(tag_id, "staging".to_string(), v3).unwrap();
+
+        let changes = prompt.pending_changes();
+        assert_eq!(changes.versions(), [v3]);
+        assert_eq!(changes.tags(), [tag_id]);
+        assert!(changes.deleted_versions().is_empty());
+        assert!(changes.deleted_tags().is_empty());
+    }
+
+    #[test]
+    fn moving_a_tag_marks_the_existing_tag() {
+        let (mut prompt, _, v2) = saved_prompt();
+        let tag_id = prompt.find_tag("production").unwrap().id();
+
+        prompt.tag_version(Uuid::new_v4(), "production".to_string(), v2).unwrap();
+
+        let changes = prompt.pending_changes();
+        assert_eq!(changes.tags(), [tag_id]);
+        assert!(changes.versions().is_empty());
+    }
+
+    #[test]
+    fn removing_a_tag_moved_since_load_only_deletes_it() {
+        let (mut prompt, _, v2) = saved_prompt();
+        let tag_id = prompt.find_tag("production").unwrap().id();
+
+        prompt.tag_version(Uuid::new_v4(), "production".to_string(), v2).unwrap();
+        prompt.remove_tag("production").unwrap();
+
+        let changes = prompt.pending_changes();
+        assert!(changes.tags().is_empty());
+        assert_eq!(changes.deleted_tags(), [tag_id]);
+    }
+
+    #[test]
+    fn deleting_a_version_added_since_load_drops_its_insert() {
+        let (mut prompt, _, _) = saved_prompt();
+
+        let v3 = add_version(&mut prompt, Version::new(2, 0, 0));
+        prompt.delete_version(v3).unwrap();
+
+        let changes = prompt.pending_changes();
+        assert!(changes.versions().is_empty());
+        assert_eq!(changes.deleted_versions(), [v3]);
+    }
+
+    #[test]
+    fn tracks_feedback_once_per_row() {
+        let (mut prompt, v1, _) = saved_prompt();
+
+        let feedback_id = add_feedback(&mut prompt, v1);
+        prompt.update_feedback(v1, feedback_id, Some(2), None).unwrap();
+
+        let changes = prompt.pending_changes();
+        assert_eq!(changes.feedbacks(), [(v1, feedback_id)]);
+        assert!(changes.deleted_feedbacks().is_empty());
+        assert!(changes.versions().is_empty());
+    }
+
+    #[test]
+    fn deleting_feedback_replaces_its_upsert() {
+        let (mut prompt, v1, _) = saved_prompt();
+        let kept = add_feedback(&mut prompt, v1);
+        let deleted = add_feedback(&mut prompt, v1);
+        let mut prompt = reload(&prompt);
+
+        prompt.update_feedback(v1, deleted, Some(1), None).unwrap();
+        prompt.delete_feedback(v1, deleted).unwrap();
+
+        let changes = prompt.pending_changes();
+        assert!(changes.feedbacks().is_empty());
+        assert_eq!(changes.deleted_feedbacks(), [deleted]);
+        assert!(prompt.find_version_by_id(v1).unwrap().find_feedback(kept).is_some());
+    }
+
+    #[test]
+    fn version_and_tag_changes_touch_but_leave_prompt_fields_alone() {
+        let (mut prompt, _, v2) = saved_prompt();
+
+        prompt.tag_version(Uuid::new_v4(), "production".to_string(), v2).unwrap();
+
+        let changes = prompt.pending_changes();
+        assert!(changes.touched());
+        assert!(!changes.prompt());
+        assert!(!changes.is_append_only());
+    }
+
+    #[test]
+    fn renaming_marks_prompt_fields() {
+        let (mut prompt, _, _) = saved_prompt();
+
+        prompt.update_name("welcome".to_string()).unwrap();
+
+        let changes = prompt.pending_changes();
+        assert!(changes.prompt());
+        assert!(changes.touched());
+    }
+
+    #[test]
+    fn new_feedback_is_append_only_but_edits_are_not() {
+        let (mut prompt, v1, _) = saved_prompt();
+        let feedback_id = add_feedback(&mut prompt, v1);
+
+        assert!(prompt.pending_changes().is_append_only());
+        assert!(!prompt.pending_changes().touched());
+
+        let mut prompt = reload(&prompt);
+        prompt.update_feedback(v1, feedback_id, Some(2), None).unwrap();
+
+        assert!(!prompt.pending_changes().is_append_only());
+    }
+}
@@ -1,6 +1,30 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
-use super::TestScenario;
+use super::{CallDetails, TestScenario};
+use crate::domain::actor::Actor;
+use crate::domain::error::DomainError;
+
+/// What a caller submits about a version, before it gets an id and author.
+#[derive(Debug, Clone)]
+pub struct FeedbackDraft {
+    pub rating: u8,
+    pub comment: Option<String>,
+    pub test_scenario: Option<TestScenario>,
+    pub call_details: Option<CallDetails>,
+}
+
+/// Stored state of a feedback, as read back by a repository.
+#[derive(Debug, Clone)]
+pub struct FeedbackRecord {
+    pub id: Uuid,
+    pub version_id: Uuid,
+    pub rating: u8,
+    pub comment: Option<String>,
+    pub test_scenario: Option<TestScenario>,
+    pub call_details: Option<CallDetails>,
+    pub created_by: Option<Actor>,
+    pub created_at: DateTime<Utc>,
+}
 
 #[derive(Debug, Clone)]
 pub struct Feedback {
@@ -9,6 +33,9 @@ pub struct Feedback {
     rating: u8,
     comment: Option<String>,
     test_scenario: Option<TestScenario>,
+    call_details: Option<CallDetails>,
+    /// `None` for feedback recorded before authorship was tracked.
+    created_by: Option<Actor>,
     created_at: DateTime<Utc>,
 }
 
@@ -19,9 +46,11 @@ impl Feedback {
         rating: u8,
         comment: Option<String>,
         test_scenario: Option<TestScenario>,
-    ) -> Result<Self, String> {
+        call_details: Option<CallDetails>,
+        created_by: Option<Actor>,
+    ) -> Result<Self, DomainError> {
         if !(1..=5).contains(&rating) {
-            return Err("Rating must be between 1 and 5".to_string());
+            return Err(DomainError::validation("rating", "Rating must be between 1 and 5"));
         }
 
         Ok(Self {
@@ -30,10 +59,26 @@ impl Feedback {
             rating,
             comment,
             test_scenario,
+            call_details,
+            created_by,
             created_at: Utc::now(),
         })
     }
 
+    /// Rebuilds stored feedback as-is; it was validated when it was submitted.
+    pub fn from_repository(record: FeedbackRecord) -> Self {
+        Self {
+            id: record.id,
+            version_id: record.version_id,
+            rating: record.rating,
+            comment: record.comment,
+            test_scenario: record.test_scenario,
+            call_details: record.call_details,
+            created_by: record.created_by,
+            created_at: record.created_at,
+        }
+    }
+
     pub fn id(&self) -> Uuid {
         self.id
     }
@@ -54,13 +99,21 @@ impl Feedback {
         self.test_scenario.as_ref()
     }
 
+    pub fn call_details(&self) -> Option<&CallDetails> {
+        self.call_details.as_ref()
+    }
+
+    pub fn created_by(&self) -> Option<Actor> {
+        self.created_by
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
 
-    pub fn update_rating(&mut self, rating: u8) -> Result<(), String> {
+    pub fn update_rating(&mut self, rating: u8) -> Result<(), DomainError> {
         if !(1..=5).contains(&rating) {
-            return Err("Rating must be between 1 and 5".to_string());
+            return Err(DomainError::validation("rating", "Rating must be between 1 and 5"));
         }
         self.rating = rating;
         Ok(())
@@ -1,6 +1,8 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 use super::SuggestionStatus;
+use crate::domain::actor::Actor;
+use crate::domain::error::DomainError;
 
 #[derive(Debug, Clone)]
 pub struct ImprovementSuggestion {
@@ -13,6 +15,8 @@ pub struct ImprovementSuggestion {
     created_at: DateTime<Utc>,
     resolved_at: Option<DateTime<Utc>>,
     resulting_version_id: Option<Uuid>,
+    /// Who asked for the suggestion, whether written by hand or by the AI.
+    created_by: Option<Actor>,
 }
 
 impl ImprovementSuggestion {
@@ -21,6 +25,7 @@ impl ImprovementSuggestion {
         source_version_id: Uuid,
         suggested_content: String,
         ai_rationale: String,
+        created_by: Option<Actor>,
     ) -> Self {
         Self {
             id,
@@ -32,6 +37,7 @@ impl ImprovementSuggestion {
             created_at: Utc::now(),
             resolved_at: None,
             resulting_version_id: None,
+            created_by,
         }
     }
 
@@ -71,9 +77,13 @@ impl ImprovementSuggestion {
         self.resulting_version_id
     }
 
-    pub fn accept(&mut self, resulting_version_id: Uuid) -> Result<(), String> {
+    pub fn created_by(&self) -> Option<Actor> {
+        self.created_by
+    }
+
+    pub fn accept(&mut self, resulting_version_id: Uuid) -> Result<(), DomainError> {
         if self.status != SuggestionStatus::Pending {
-            return Err("Can only accept pending suggestions".to_string());
+            return Err(DomainError::conflict("Can only accept pending suggestions"));
         }
         self.status = SuggestionStatus::Accepted;
         self.resulting_version_id = Some(resulting_version_id);
@@ -81,9 +91,9 @@ impl ImprovementSuggestion {
         Ok(())
     }
 
-    pub fn decline(&mut self, reason: String) -> Result<(), String> {
+    pub fn decline(&mut self, reason: String) -> Result<(), DomainError> {
         if self.status != SuggestionStatus::Pending {
-            return Err("Can only decline pending suggestions".to_string());
+            return Err(DomainError::conflict("Can only decline pending suggestions"));
         }
         self.status = SuggestionStatus::Declined;
         self.decline_reason = Some(reason);
@@ -1,21 +1,37 @@
 pub mod prompt;
 pub mod version;
 pub mod version_number;
+pub mod version_draft;
 pub mod tag;
 pub mod feedback;
 pub mod test_scenario;
+pub mod call_details;
 pub mod prompt_type;
 pub mod content_type;
 pub mod improvement_suggestion;
 pub mod suggestion_status;
+pub mod model_config;
+pub mod tool_definition;
+pub mod output_schema;
+pub mod token_count;
+pub mod prompt_event;
+pub mod change_set;
 
-pub use prompt::Prompt;
+pub use prompt::{Prompt, PromptRecord};
 pub use version::PromptVersion;
 pub use version_number::Version;
+pub use version_draft::VersionDraft;
 pub use tag::Tag;
-pub use feedback::Feedback;
+pub use feedback::{Feedback, FeedbackDraft, FeedbackRecord};
 pub use test_scenario::TestScenario;
+pub use call_details::{CallDetails, CallDetailsDraft};
 pub use prompt_type::PromptType;
 pub use content_type::ContentType;
 pub use improvement_suggestion::ImprovementSuggestion;
-pub use suggestion_status::SuggestionStatus;
\ No newline at end of file
+pub use suggestion_status::SuggestionStatus;
+pub use model_config::{ModelConfig, ModelProvider};
+pub use tool_definition::ToolDefinition;
+pub use output_schema::OutputSchema;
+pub use token_count::TokenCount;
+pub use prompt_event::{PromptEvent, PromptEventKind};
+pub use change_set::PromptChangeSet;
\ No newline at end of file
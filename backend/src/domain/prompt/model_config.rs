@@ -0,0 +1,122 @@
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModelProvider {
+    OpenAI,
+    Anthropic,
+    Gemini,
+}
+
+impl ModelProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModelProvider::OpenAI => "openai",
+            ModelProvider::Anthropic => "anthropic",
+            ModelProvider::Gemini => "gemini",
+        }
+    }
+
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
+        match s {
+            "openai" => Ok(ModelProvider::OpenAI),
+            "anthropic" => Ok(ModelProvider::Anthropic),
+            "gemini" => Ok(ModelProvider::Gemini),
+            _ => Err(DomainError::validation("provider", "Invalid provider. Must be 'openai', 'anthropic' or 'gemini'")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelConfig {
+    provider: ModelProvider,
+    model: String,
+    temperature: Option<f64>,
+    top_p: Option<f64>,
+    max_tokens: Option<u32>,
+    stop_sequences: Vec<String>,
+    seed: Option<i64>,
+}
+
+impl ModelConfig {
+    pub fn new(
+        provider: ModelProvider,
+        model: String,
+        temperature: Option<f64>,
+        top_p: Option<f64>,
+        max_tokens: Option<u32>,
+        stop_sequences: Vec<String>,
+        seed: Option<i64>,
+    ) -> Result<Self, DomainError> {
+        if model.trim().is_empty() {
+            return Err(DomainError::validation("model", "Model name cannot be empty"));
+        }
+        if temperature.is_some_and(|t| !(0.0..=2.0).contains(&t)) {
+            return Err(DomainError::validation("temperature", "Temperature must be between 0 and 2"));
+        }
+        if top_p.is_some_and(|p| !(0.0..=1.0).contains(&p)) {
+            return Err(DomainError::validation("top_p", "top_p must be between 0 and 1"));
+        }
+        if max_tokens == Some(0) {
+            return Err(DomainError::validation("max_tokens", "max_tokens must be greater than 0"));
+        }
+        if stop_sequences.iter().any(|s| s.is_empty()) {
+            return Err(DomainError::validation("stop_sequences", "Stop sequences cannot be empty"));
+        }
+
+        Ok(Self {
+            provider,
+            model,
+            temperature,
+            top_p,
+            max_tokens,
+            stop_sequences,
+            seed,
+        })
+    }
+
+    pub fn provider(&self) -> ModelProvider {
+        self.provider
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    pub fn temperature(&self) -> Option<f64> {
+        self.temperature
+    }
+
+    pub fn top_p(&self) -> Option<f64> {
+        self.top_p
+    }
+
+    pub fn max_tokens(&self) -> Option<u32> {
+        self.max_tokens
+    }
+
+    pub fn stop_sequences(&self) -> &[String] {
+        &self.stop_sequences
+    }
+
+    pub fn seed(&self) -> Option<i64> {
+        self.seed
+    }
+
+    /// Stable textual form used when hashing a version's digest.
+    pub(crate) fn canonical_string(&self) -> String {
+        fn opt<T: ToString>(value: Option<T>) -> String {
+            value.map(|v| v.to_string()).unwrap_or_default()
+        }
+
+        format!(
+            "provider={};model={};temperature={};top_p={};max_tokens={};stop={};seed={}",
+            self.provider.as_str(),
+            self.model,
+            opt(self.temperature),
+            opt(self.top_p),
+            opt(self.max_tokens),
+            serde_json::to_string(&self.stop_sequences).unwrap_or_default(),
+            opt(self.seed),
+        )
+    }
+}
@@ -0,0 +1,173 @@
+use jsonschema::Validator;
+use serde_json::Value;
+use std::sync::{Arc, OnceLock};
+use super::tool_definition::validate_identifier;
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone)]
+pub struct OutputSchema {
+    name: String,
+    schema: Value,
+    /// Compiled on the first `violations` call and shared by clones, so
+    /// cached prompts compile each schema once.
+    validator: Arc<OnceLock<Result<Validator, String>>>,
+}
+
+impl PartialEq for OutputSchema {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.schema == other.schema
+    }
+}
+
+impl OutputSchema {
+    pub fn new(name: String, schema: Value) -> Result<Self, DomainError> {
+        validate_identifier(&name, "output_schema", "Output schema name")?;
+
+        if !schema.is_object() {
+            return Err(DomainError::validation("output_schema", "Output schema must be a JSON Schema object"));
+        }
+        jsonschema::meta::validate(&schema)
+            .map_err(|e| DomainError::validation("output_schema", format!("Output schema is not a valid JSON Schema: {}", e)))?;
+
+        Ok(Self { name, schema, validator: Arc::default() })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn schema(&self) -> &Value {
+        &self.schema
+    }
+
+    /// Whether the schema fits OpenAI's strict structured outputs: every
+    /// object closes `additionalProperties` and requires all its properties.
+    pub fn is_strict(&self) -> bool {
+        is_strict_schema(&self.schema)
+    }
+
+    /// Checks a model output against the schema and returns every violation
+    /// found. An empty list means the output conforms.
+    pub fn violations(&self, output: &str) -> Vec<String> {
+        let instance: Value = match serde_json::from_str(output) {
+            Ok(value) => value,
+            Err(e) => return vec![format!("Output is not valid JSON: {}", e)],
+        };
+
+        let validator = match self.validator.get_or_init(|| jsonschema::validator_for(&self.schema).map_err(|e| e.to_string())) {
+            Ok(validator) => validator,
+            Err(e) => return vec![format!("Output schema could not be compiled: {}", e)],
+        };
+
+        validator
+            .iter_errors(&instance)
+            .map(|e| {
+                let path = e.instance_path().to_string();
+                if path.is_empty() {
+                    e.to_string()
+                } else {
+                    format!("{}: {}", path, e)
+                }
+            })
+            .collect()
+    }
+}
+
+fn is_strict_schema(schema: &Value) -> bool {
+    let Some(schema) = schema.as_object() else {
+        return true;
+    };
+
+    let properties = schema.get("properties").and_then(Value::as_object);
+    let is_object = properties.is_some() || schema.get("type").and_then(Value::as_str) == Some("object");
+    if is_object && schema.get("additionalProperties") != Some(&Value::Bool(false)) {
+        return false;
+    }
+    if let Some(properties) = properties {
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        if !properties.iter().all(|(key, value)| required.contains(&key.as_str()) && is_strict_schema(value)) {
+            return false;
+        }
+    }
+
+    let items_strict = schema.get("items").is_none_or(is_strict_schema);
+    let any_of_strict = schema
+        .get("anyOf")
+        .and_then(Value::as_array)
+        .is_none_or(|schemas| schemas.iter().all(is_strict_schema));
+    let defs_strict = ["$defs", "definitions"].iter().all(|key| {
+        schema
+            .get(*key)
+            .and_then(Value::as_object)
+            .is_none_or(|defs| defs.values().all(is_strict_schema))
+    });
+    items_strict && any_of_strict && defs_strict
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema(value: Value) -> OutputSchema {
+        OutputSchema::new("answer".to_string(), value).unwrap()
+    }
+
+    #[test]
+    fn strict_when_objects_are_closed_and_fully_required() {
+        let strict = schema(json!({
+            "type": "object",
+            "properties": {
+                "label": { "type": "string" },
+                "spans": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": { "start": { "type": "integer" } },
+                        "required": ["start"],
+                        "additionalProperties": false,
+                    },
+                },
+            },
+            "required": ["label", "spans"],
+            "additionalProperties": false,
+        }));
+        assert!(strict.is_strict());
+    }
+
+    #[test]
+    fn reports_violations_with_their_path() {
+        let schema = schema(json!({
+            "type": "object",
+            "properties": { "score": { "type": "number" } },
+            "required": ["score"],
+        }));
+        assert!(schema.violations(r#"{"score": 0.5}"#).is_empty());
+        assert_eq!(schema.violations(r#"{"score": "high"}"#).len(), 1);
+        assert!(schema.violations(r#"{"score": "high"}"#)[0].starts_with("/score: "));
+        assert!(schema.violations("not json")[0].starts_with("Output is not valid JSON"));
+    }
+
+    #[test]
+    fn not_strict_with_optional_or_open_properties() {
+        let optional = schema(json!({
+            "type": "object",
+            "properties": { "label": { "type": "string" }, "score": { "type": "number" } },
+            "required": ["label"],
+            "additionalProperties": false,
+        }));
+        assert!(!optional.is_strict());
+
+        let open_nested = schema(json!({
+            "type": "object",
+            "properties": { "meta": { "type": "object", "properties": {}, "required": [] } },
+            "required": ["meta"],
+            "additionalProperties": false,
+        }));
+        assert!(!open_nested.is_strict());
+    }
+}
@@ -1,6 +1,24 @@
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
-use super::{PromptVersion, Tag, Version, PromptType, ContentType};
+use crate::domain::actor::Actor;
+use crate::domain::error::DomainError;
+use super::{PromptVersion, Tag, Version, VersionDraft, PromptType, Feedback, FeedbackDraft, ImprovementSuggestion, PromptEvent, PromptEventKind, PromptChangeSet, TokenCount};
+
+/// Stored state of a prompt, as read back by a repository.
+#[derive(Debug, Clone)]
+pub struct PromptRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub prompt_type: PromptType,
+    pub max_render_tokens: Option<u32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub revision: u64,
+    pub versions: Vec<PromptVersion>,
+    pub tags: Vec<Tag>,
+}
 
 #[derive(Debug, Clone)]
 pub struct Prompt {
@@ -9,10 +27,14 @@ pub struct Prompt {
     name: String,
     description: Option<String>,
     prompt_type: PromptType,
+    max_render_tokens: Option<u32>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    revision: u64,
     versions: Vec<PromptVersion>,
     tags: Vec<Tag>,
+    events: Vec<PromptEvent>,
+    changes: PromptChangeSet,
 }
 
 impl Prompt {
@@ -22,18 +44,43 @@ impl Prompt {
         name: String,
         description: Option<String>,
         prompt_type: PromptType,
+        max_render_tokens: Option<u32>,
     ) -> Self {
         let now = Utc::now();
+        let mut changes = PromptChangeSet::default();
+        changes.mark_prompt();
         Self {
             id,
             user_id,
             name,
             description,
             prompt_type,
+            max_render_tokens,
             created_at: now,
             updated_at: now,
+            revision: 0,
             versions: Vec::new(),
             tags: Vec::new(),
+            events: Vec::new(),
+            changes,
+        }
+    }
+
+    pub fn from_repository(record: PromptRecord) -> Self {
+        Self {
+            id: record.id,
+            user_id: record.user_id,
+            name: record.name,
+            description: record.description,
+            prompt_type: record.prompt_type,
+            max_render_tokens: record.max_render_tokens,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+            revision: record.revision,
+            versions: record.versions,
+            tags: record.tags,
+            events: Vec::new(),
+            changes: PromptChangeSet::default(),
         }
     }
 
@@ -57,6 +104,11 @@ impl Prompt {
         self.prompt_type
     }
 
+    /// Upper bound on the token count of a rendered version; larger renders are rejected.
+    pub fn max_render_tokens(&self) -> Option<u32> {
+        self.max_render_tokens
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
@@ -65,51 +117,79 @@ impl Prompt {
         self.updated_at
     }
 
-    pub fn versions(&self) -> &[PromptVersion] {
-        &self.versions
+    /// Revision the prompt was loaded at; `0` until it is first saved.
+    pub fn revision(&self) -> u64 {
+        self.revision
     }
 
-    pub fn versions_mut(&mut self) -> &mut Vec<PromptVersion> {
-        &mut self.versions
+    /// Rejects a mutation made against a revision the caller saw earlier,
+    /// e.g. from an `If-Match` header. `None` means unconditional.
+    pub fn check_revision(&self, expected: Option<u64>) -> Result<(), DomainError> {
+        match expected {
+            Some(expected) if expected != self.revision => Err(DomainError::PreconditionFailed(
+                "Prompt changed since the supplied ETag".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn versions(&self) -> &[PromptVersion] {
+        &self.versions
     }
 
     pub fn tags(&self) -> &[Tag] {
         &self.tags
     }
 
-    pub fn tags_mut(&mut self) -> &mut Vec<Tag> {
-        &mut self.tags
+    /// Events recorded by mutations since the prompt was loaded or created.
+    pub fn pending_events(&self) -> &[PromptEvent] {
+        &self.events
+    }
+
+    /// Rows touched by mutations since the prompt was loaded or created.
+    pub fn pending_changes(&self) -> &PromptChangeSet {
+        &self.changes
+    }
+
+    fn record(&mut self, kind: PromptEventKind) {
+        self.events.push(PromptEvent::new(Uuid::new_v4(), self.id, self.user_id, Utc::now(), kind));
     }
 
     pub fn add_version(
         &mut self,
         version_id: Uuid,
-        version: Version,
-        content: String,
-        content_type: ContentType,
-        variables: Option<Vec<String>>,
-        changelog: Option<String>,
-    ) -> Result<&PromptVersion, String> {
-        if self.versions.iter().any(|v| v.version() == version) {
-            return Err(format!("Version {} already exists", version));
+        draft: VersionDraft,
+        created_by: Actor,
+    ) -> Result<&PromptVersion, DomainError> {
+        if self.versions.iter().any(|v| v.version() == draft.version) {
+            return Err(DomainError::conflict(format!("Version {} already exists", draft.version)));
         }
 
-        let prompt_version = PromptVersion::new(
-            version_id,
-            self.id,
-            version,
-            content,
-            content_type,
-            variables,
-            changelog,
-        );
+        let mut tool_names = std::collections::HashSet::new();
+        if let Some(duplicate) = draft.tools.iter().find(|t| !tool_names.insert(t.name())) {
+            return Err(DomainError::validation("tools", format!("Tool '{}' is defined more than once", duplicate.name())));
+        }
 
+        let prompt_version = PromptVersion::new(version_id, self.id, draft, Some(created_by));
+
+        self.record(PromptEventKind::VersionCreated {
+            version_id,
+            version: prompt_version.version_string(),
+        });
         self.versions.push(prompt_version);
-        self.updated_at = Utc::now();
+        self.changes.mark_version(version_id);
+        self.touch();
 
         Ok(self.versions.last().unwrap())
     }
 
+    pub fn record_token_count(&mut self, version_id: Uuid, token_count: TokenCount) -> Result<(), DomainError> {
+        let index = self.version_index(version_id)?;
+        self.versions[index].record_token_count(token_count);
+        self.changes.mark_version(version_id);
+        Ok(())
+    }
+
     pub fn find_version(&self, version: &Version) -> Option<&PromptVersion> {
         self.versions.iter().find(|v| v.version() == *version)
     }
@@ -123,19 +203,29 @@ impl Prompt {
         tag_id: Uuid,
         tag_name: String,
         version_id: Uuid,
-    ) -> Result<(), String> {
+    ) -> Result<(), DomainError> {
         if !self.versions.iter().any(|v| v.id() == version_id) {
-            return Err("Version not found in this prompt".to_string());
+            return Err(DomainError::not_found("Version not found in this prompt"));
         }
 
-        if let Some(existing_tag) = self.tags.iter_mut().find(|t| t.name() == tag_name) {
+        let previous_version_id = if let Some(existing_tag) = self.tags.iter_mut().find(|t| t.name() == tag_name) {
+            let previous = existing_tag.version_id();
             existing_tag.move_to_version(version_id);
+            self.changes.mark_tag(existing_tag.id());
+            Some(previous)
         } else {
-            let tag = Tag::new(tag_id, self.id, version_id, tag_name);
+            let tag = Tag::new(tag_id, self.id, version_id, tag_name.clone());
             self.tags.push(tag);
-        }
+            self.changes.mark_tag(tag_id);
+            None
+        };
 
-        self.updated_at = Utc::now();
+        self.record(PromptEventKind::TagMoved {
+            tag_name,
+            version_id,
+            previous_version_id,
+        });
+        self.touch();
         Ok(())
     }
 
@@ -143,65 +233,172 @@ impl Prompt {
         self.tags.iter().find(|t| t.name() == tag_name)
     }
 
-    pub fn update_name(&mut self, name: String) -> Result<(), String> {
+    pub fn update_name(&mut self, name: String) -> Result<(), DomainError> {
         if name.trim().is_empty() {
-            return Err("Name cannot be empty".to_string());
+            return Err(DomainError::validation("name", "Name cannot be empty"));
         }
         self.name = name;
-        self.updated_at = Utc::now();
+        self.touch_fields();
         Ok(())
     }
 
     pub fn update_description(&mut self, description: Option<String>) {
         self.description = description;
-        self.updated_at = Utc::now();
+        self.touch_fields();
     }
 
-    pub fn delete_version(&mut self, version_id: Uuid) -> Result<(), String> {
+    pub fn update_max_render_tokens(&mut self, max_render_tokens: Option<u32>) -> Result<(), DomainError> {
+        if max_render_tokens == Some(0) {
+            return Err(DomainError::validation("max_render_tokens", "max_render_tokens must be greater than 0"));
+        }
+        self.max_render_tokens = max_render_tokens;
+        self.touch_fields();
+        Ok(())
+    }
+
+    /// Rejects a render whose token count exceeds the prompt's budget or,
+    /// together with the completion budget, the model's context window.
+    pub fn check_render_budget(
+        &self,
+        rendered_tokens: u32,
+        completion_tokens: Option<u32>,
+        context_window: Option<u32>,
+    ) -> Result<(), DomainError> {
+        if let Some(max) = self.max_render_tokens
+            && rendered_tokens > max
+        {
+            return Err(DomainError::invalid(format!(
+                "Rendered prompt is {} tokens, exceeding the prompt's budget of {}",
+                rendered_tokens, max
+            )));
+        }
+
+        let completion_tokens = completion_tokens.unwrap_or(0);
+        if let Some(window) = context_window
+            && rendered_tokens.saturating_add(completion_tokens) > window
+        {
+            return Err(DomainError::invalid(format!(
+                "Rendered prompt ({} tokens) plus max_tokens ({}) exceeds the model's context window of {}",
+                rendered_tokens, completion_tokens, window
+            )));
+        }
+
+        Ok(())
+    }
+
+    pub fn delete_version(&mut self, version_id: Uuid) -> Result<(), DomainError> {
         let initial_len = self.versions.len();
         self.versions.retain(|v| v.id() != version_id);
 
         if self.versions.len() == initial_len {
-            return Err("Version not found".to_string());
+            return Err(DomainError::not_found("Version not found"));
         }
 
+        // Tags, feedback and suggestions of the version go with it.
         self.tags.retain(|t| t.version_id() != version_id);
-        self.updated_at = Utc::now();
+        self.changes.mark_version_deleted(version_id);
+        self.touch();
         Ok(())
     }
 
-    pub fn remove_tag(&mut self, tag_name: &str) -> Result<(), String> {
-        let initial_len = self.tags.len();
-        self.tags.retain(|t| t.name() != tag_name);
+    pub fn remove_tag(&mut self, tag_name: &str) -> Result<(), DomainError> {
+        let tag_id = self.find_tag(tag_name)
+            .map(|t| t.id())
+            .ok_or_else(|| DomainError::not_found("Tag not found"))?;
 
-        if self.tags.len() == initial_len {
-            return Err("Tag not found".to_string());
-        }
+        self.tags.retain(|t| t.id() != tag_id);
+        self.changes.mark_tag_deleted(tag_id);
+        self.touch();
+        Ok(())
+    }
 
-        self.updated_at = Utc::now();
+    pub fn add_feedback(
+        &mut self,
+        version_id: Uuid,
+        feedback_id: Uuid,
+        draft: FeedbackDraft,
+        created_by: Actor,
+    ) -> Result<&Feedback, DomainError> {
+        let index = self.version_index(version_id)?;
+        let rating = draft.rating;
+        self.versions[index].add_feedback(feedback_id, draft, Some(created_by))?;
+        self.record(PromptEventKind::FeedbackSubmitted { version_id, feedback_id, rating });
+        self.changes.mark_feedback(version_id, feedback_id);
+
+        Ok(self.versions[index].feedbacks().last().unwrap())
+    }
+
+    pub fn create_improvement_suggestion(
+        &mut self,
+        version_id: Uuid,
+        suggestion_id: Uuid,
+        suggested_content: String,
+        ai_rationale: String,
+        created_by: Actor,
+    ) -> Result<&ImprovementSuggestion, DomainError> {
+        let index = self.version_index(version_id)?;
+        self.versions[index].create_improvement_suggestion(suggestion_id, suggested_content, ai_rationale, Some(created_by))?;
+        self.record(PromptEventKind::SuggestionCreated { version_id, suggestion_id });
+        self.changes.mark_suggestion(version_id, suggestion_id);
+
+        Ok(self.versions[index].improvement_suggestions().last().unwrap())
+    }
+
+    pub fn decline_suggestion(
+        &mut self,
+        version_id: Uuid,
+        suggestion_id: Uuid,
+        reason: String,
+    ) -> Result<(), DomainError> {
+        let index = self.version_index(version_id)?;
+        self.versions[index].decline_suggestion(suggestion_id, reason)?;
+        self.record(PromptEventKind::SuggestionDeclined { version_id, suggestion_id });
+        self.changes.mark_suggestion_resolved(version_id, suggestion_id);
         Ok(())
     }
 
+    /// Bumps `updated_at` after a change to versions or tags.
+    fn touch(&mut self) {
+        self.updated_at = Utc::now();
+        self.changes.mark_touched();
+    }
+
+    /// Bumps `updated_at` after a change to the prompt's own fields.
+    fn touch_fields(&mut self) {
+        self.updated_at = Utc::now();
+        self.changes.mark_prompt();
+    }
+
+    fn version_index(&self, version_id: Uuid) -> Result<usize, DomainError> {
+        self.versions.iter()
+            .position(|v| v.id() == version_id)
+            .ok_or_else(|| DomainError::not_found("Version not found"))
+    }
+
     pub fn update_feedback(
         &mut self,
         version_id: Uuid,
         feedback_id: Uuid,
         rating: Option<u8>,
         comment: Option<Option<String>>,
-    ) -> Result<(), String> {
+    ) -> Result<(), DomainError> {
         let version = self.versions.iter_mut()
             .find(|v| v.id() == version_id)
-            .ok_or("Version not found")?;
+            .ok_or_else(|| DomainError::not_found("Version not found"))?;
 
-        version.update_feedback(feedback_id, rating, comment)
+        version.update_feedback(feedback_id, rating, comment)?;
+        self.changes.mark_feedback_updated(version_id, feedback_id);
+        Ok(())
     }
 
-    pub fn delete_feedback(&mut self, version_id: Uuid, feedback_id: Uuid) -> Result<(), String> {
+    pub fn delete_feedback(&mut self, version_id: Uuid, feedback_id: Uuid) -> Result<(), DomainError> {
         let version = self.versions.iter_mut()
             .find(|v| v.id() == version_id)
-            .ok_or("Version not found")?;
+            .ok_or_else(|| DomainError::not_found("Version not found"))?;
 
-        version.delete_feedback(feedback_id)
+        version.delete_feedback(feedback_id)?;
+        self.changes.mark_feedback_deleted(feedback_id);
+        Ok(())
     }
 
     pub fn accept_improvement(
@@ -211,36 +408,49 @@ impl Prompt {
         new_version_id: Uuid,
         new_version_number: Version,
         changelog: Option<String>,
-    ) -> Result<&PromptVersion, String> {
+        created_by: Actor,
+    ) -> Result<&PromptVersion, DomainError> {
         let source_version = self.versions.iter()
             .find(|v| v.id() == source_version_id)
-            .ok_or("Source version not found")?;
+            .ok_or_else(|| DomainError::not_found("Source version not found"))?;
 
         let suggestion = source_version.find_suggestion(suggestion_id)
-            .ok_or("Suggestion not found")?;
+            .ok_or_else(|| DomainError::not_found("Suggestion not found"))?;
 
         if suggestion.status() != super::SuggestionStatus::Pending {
-            return Err("Can only accept pending suggestions".to_string());
+            return Err(DomainError::conflict("Can only accept pending suggestions"));
         }
 
-        let new_version = PromptVersion::new(
-            new_version_id,
-            self.id,
-            new_version_number,
-            suggestion.suggested_content().to_string(),
-            source_version.content_type(),
-            source_version.variables().map(|v| v.to_vec()),
+        let draft = VersionDraft {
+            version: new_version_number,
+            content: suggestion.suggested_content().to_string(),
+            content_type: source_version.content_type(),
+            variables: source_version.variables().map(|v| v.to_vec()),
             changelog,
-        );
+            model_config: source_version.model_config().cloned(),
+            tools: source_version.tools().to_vec(),
+            output_schema: source_version.output_schema().cloned(),
+        };
+        let new_version = PromptVersion::new(new_version_id, self.id, draft, Some(created_by));
 
         self.versions.push(new_version);
-        self.updated_at = Utc::now();
+        self.changes.mark_version(new_version_id);
+        self.touch();
 
         let source_version_mut = self.versions.iter_mut()
             .find(|v| v.id() == source_version_id)
             .unwrap();
 
         source_version_mut.accept_suggestion(suggestion_id, new_version_id)?;
+        self.changes.mark_suggestion_resolved(source_version_id, suggestion_id);
+
+        let version = self.versions.last().unwrap().version_string();
+        self.record(PromptEventKind::VersionCreated { version_id: new_version_id, version });
+        self.record(PromptEventKind::SuggestionAccepted {
+            version_id: source_version_id,
+            suggestion_id,
+            resulting_version_id: new_version_id,
+        });
 
         Ok(self.versions.last().unwrap())
     }
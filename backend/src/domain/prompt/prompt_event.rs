@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromptEventKind {
+    VersionCreated {
+        version_id: Uuid,
+        version: String,
+    },
+    TagMoved {
+        tag_name: String,
+        version_id: Uuid,
+        previous_version_id: Option<Uuid>,
+    },
+    SuggestionCreated {
+        version_id: Uuid,
+        suggestion_id: Uuid,
+    },
+    SuggestionAccepted {
+        version_id: Uuid,
+        suggestion_id: Uuid,
+        resulting_version_id: Uuid,
+    },
+    SuggestionDeclined {
+        version_id: Uuid,
+        suggestion_id: Uuid,
+    },
+    FeedbackSubmitted {
+        version_id: Uuid,
+        feedback_id: Uuid,
+        rating: u8,
+    },
+}
+
+impl PromptEventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PromptEventKind::VersionCreated { .. } => "version_created",
+            PromptEventKind::TagMoved { .. } => "tag_moved",
+            PromptEventKind::SuggestionCreated { .. } => "suggestion_created",
+            PromptEventKind::SuggestionAccepted { .. } => "suggestion_accepted",
+            PromptEventKind::SuggestionDeclined { .. } => "suggestion_declined",
+            PromptEventKind::FeedbackSubmitted { .. } => "feedback_submitted",
+        }
+    }
+}
+
+/// Something that happened to a prompt, recorded by the aggregate when it is
+/// mutated and published once the prompt has been saved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PromptEvent {
+    id: Uuid,
+    prompt_id: Uuid,
+    user_id: Uuid,
+    occurred_at: DateTime<Utc>,
+    kind: PromptEventKind,
+}
+
+impl PromptEvent {
+    pub fn new(id: Uuid, prompt_id: Uuid, user_id: Uuid, occurred_at: DateTime<Utc>, kind: PromptEventKind) -> Self {
+        Self {
+            id,
+            prompt_id,
+            user_id,
+            occurred_at,
+            kind,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn prompt_id(&self) -> Uuid {
+        self.prompt_id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn occurred_at(&self) -> DateTime<Utc> {
+        self.occurred_at
+    }
+
+    pub fn kind(&self) -> &PromptEventKind {
+        &self.kind
+    }
+}
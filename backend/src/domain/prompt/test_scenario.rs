@@ -1,8 +1,11 @@
+use crate::domain::error::DomainError;
+
 #[derive(Debug, Clone)]
 pub struct TestScenario {
     input: String,
     actual_output: String,
     expected_output: Option<String>,
+    schema_violations: Option<Vec<String>>,
 }
 
 impl TestScenario {
@@ -10,21 +13,38 @@ impl TestScenario {
         input: String,
         actual_output: String,
         expected_output: Option<String>,
-    ) -> Result<Self, String> {
+    ) -> Result<Self, DomainError> {
         if input.trim().is_empty() {
-            return Err("Input cannot be empty".to_string());
+            return Err(DomainError::validation("test_input", "Input cannot be empty"));
         }
         if actual_output.trim().is_empty() {
-            return Err("Actual output cannot be empty".to_string());
+            return Err(DomainError::validation("test_actual_output", "Actual output cannot be empty"));
         }
 
         Ok(Self {
             input,
             actual_output,
             expected_output,
+            schema_violations: None,
         })
     }
 
+    /// Rebuilds a stored scenario, keeping the schema violations found when
+    /// its feedback was submitted instead of checking again.
+    pub fn from_repository(
+        input: String,
+        actual_output: String,
+        expected_output: Option<String>,
+        schema_violations: Option<Vec<String>>,
+    ) -> Self {
+        Self {
+            input,
+            actual_output,
+            expected_output,
+            schema_violations,
+        }
+    }
+
     pub fn input(&self) -> &str {
         &self.input
     }
@@ -36,4 +56,14 @@ impl TestScenario {
     pub fn expected_output(&self) -> Option<&str> {
         self.expected_output.as_deref()
     }
+
+    /// Violations of the version's output schema found in `actual_output`.
+    /// `None` when the version declares no output schema.
+    pub fn schema_violations(&self) -> Option<&[String]> {
+        self.schema_violations.as_deref()
+    }
+
+    pub(crate) fn set_schema_violations(&mut self, violations: Option<Vec<String>>) {
+        self.schema_violations = violations;
+    }
 }
\ No newline at end of file
@@ -0,0 +1,19 @@
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenCount {
+    tokens: u32,
+    encoding: String,
+}
+
+impl TokenCount {
+    pub fn new(tokens: u32, encoding: String) -> Self {
+        Self { tokens, encoding }
+    }
+
+    pub fn tokens(&self) -> u32 {
+        self.tokens
+    }
+
+    pub fn encoding(&self) -> &str {
+        &self.encoding
+    }
+}
@@ -0,0 +1,56 @@
+use serde_json::Value;
+use crate::domain::error::DomainError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolDefinition {
+    name: String,
+    description: Option<String>,
+    parameters: Value,
+}
+
+impl ToolDefinition {
+    pub fn new(
+        name: String,
+        description: Option<String>,
+        parameters: Value,
+    ) -> Result<Self, DomainError> {
+        validate_identifier(&name, "tools", "Tool name")?;
+
+        if !parameters.is_object() {
+            return Err(DomainError::validation("tools", format!("Parameters of tool '{}' must be a JSON Schema object", name)));
+        }
+        jsonschema::meta::validate(&parameters)
+            .map_err(|e| DomainError::validation("tools", format!("Parameters of tool '{}' are not a valid JSON Schema: {}", name, e)))?;
+
+        Ok(Self {
+            name,
+            description,
+            parameters,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    pub fn parameters(&self) -> &Value {
+        &self.parameters
+    }
+}
+
+/// Tool and schema names end up in provider payloads, which only accept
+/// `[a-zA-Z0-9_-]{1,64}`.
+pub(crate) fn validate_identifier(name: &str, field: &str, label: &str) -> Result<(), DomainError> {
+    let valid_chars = name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if name.is_empty() || name.len() > 64 || !valid_chars {
+        return Err(DomainError::validation(field, format!(
+            "{} must be 1-64 characters of letters, digits, '_' or '-'",
+            label
+        )));
+    }
+    Ok(())
+}
@@ -1,7 +1,9 @@
 use chrono::{DateTime, Utc};
 use sha2::{Sha256, Digest as Sha2Digest};
 use uuid::Uuid;
-use super::{Feedback, TestScenario, Version, ContentType, ImprovementSuggestion};
+use super::{Feedback, FeedbackDraft, Version, VersionDraft, ContentType, ImprovementSuggestion, ModelConfig, ToolDefinition, OutputSchema, TokenCount};
+use crate::domain::actor::Actor;
+use crate::domain::error::DomainError;
 
 #[derive(Debug, Clone)]
 pub struct PromptVersion {
@@ -13,22 +15,26 @@ pub struct PromptVersion {
     content_type: ContentType,
     variables: Option<Vec<String>>,
     changelog: Option<String>,
+    model_config: Option<ModelConfig>,
+    tools: Vec<ToolDefinition>,
+    output_schema: Option<OutputSchema>,
+    token_count: Option<TokenCount>,
+    created_by: Option<Actor>,
     created_at: DateTime<Utc>,
     feedbacks: Vec<Feedback>,
     improvement_suggestions: Vec<ImprovementSuggestion>,
 }
 
 impl PromptVersion {
-    pub fn new(
-        id: Uuid,
-        prompt_id: Uuid,
-        version: Version,
-        content: String,
-        content_type: ContentType,
-        variables: Option<Vec<String>>,
-        changelog: Option<String>,
-    ) -> Self {
-        let digest = Self::generate_digest(&content, content_type);
+    pub fn new(id: Uuid, prompt_id: Uuid, draft: VersionDraft, created_by: Option<Actor>) -> Self {
+        let VersionDraft { version, content, content_type, variables, changelog, model_config, tools, output_schema } = draft;
+        let digest = Self::generate_digest(
+            &content,
+            content_type,
+            model_config.as_ref(),
+            &tools,
+            output_schema.as_ref(),
+        );
         Self {
             id,
             prompt_id,
@@ -38,6 +44,11 @@ impl PromptVersion {
             content_type,
             variables,
             changelog,
+            model_config,
+            tools,
+            output_schema,
+            token_count: None,
+            created_by,
             created_at: Utc::now(),
             feedbacks: Vec::new(),
             improvement_suggestions: Vec::new(),
@@ -80,6 +91,32 @@ impl PromptVersion {
         self.changelog.as_deref()
     }
 
+    pub fn model_config(&self) -> Option<&ModelConfig> {
+        self.model_config.as_ref()
+    }
+
+    pub fn tools(&self) -> &[ToolDefinition] {
+        &self.tools
+    }
+
+    pub fn output_schema(&self) -> Option<&OutputSchema> {
+        self.output_schema.as_ref()
+    }
+
+    /// Token count of the unrendered content, recorded when the version is created.
+    pub fn token_count(&self) -> Option<&TokenCount> {
+        self.token_count.as_ref()
+    }
+
+    pub fn record_token_count(&mut self, token_count: TokenCount) {
+        self.token_count = Some(token_count);
+    }
+
+    /// `None` for versions created before authorship was tracked.
+    pub fn created_by(&self) -> Option<Actor> {
+        self.created_by
+    }
+
     pub fn created_at(&self) -> DateTime<Utc> {
         self.created_at
     }
@@ -88,16 +125,16 @@ impl PromptVersion {
         &self.feedbacks
     }
 
-    pub fn render(&self, context: Option<&serde_json::Value>) -> Result<String, String> {
+    pub fn render(&self, context: Option<&serde_json::Value>) -> Result<String, DomainError> {
         match self.content_type {
             ContentType::Static => Ok(self.content.clone()),
             ContentType::Template => {
-                let ctx = context.ok_or("Template requires context")?;
+                let ctx = context.ok_or_else(|| DomainError::validation("context", "Template requires context"))?;
 
                 let handlebars = handlebars::Handlebars::new();
                 handlebars
                     .render_template(&self.content, ctx)
-                    .map_err(|e| format!("Template rendering failed: {}", e))
+                    .map_err(|e| DomainError::validation("context", format!("Template rendering failed: {}", e)))
             }
         }
     }
@@ -105,15 +142,26 @@ impl PromptVersion {
     pub fn add_feedback(
         &mut self,
         feedback_id: Uuid,
-        rating: u8,
-        comment: Option<String>,
-        test_scenario: Option<TestScenario>,
-    ) -> Result<&Feedback, String> {
-        let feedback = Feedback::new(feedback_id, self.id, rating, comment, test_scenario)?;
+        draft: FeedbackDraft,
+        created_by: Option<Actor>,
+    ) -> Result<&Feedback, DomainError> {
+        let FeedbackDraft { rating, comment, mut test_scenario, call_details } = draft;
+        if let (Some(scenario), Some(schema)) = (test_scenario.as_mut(), self.output_schema.as_ref()) {
+            let violations = schema.violations(scenario.actual_output());
+            scenario.set_schema_violations(Some(violations));
+        }
+
+        let feedback = Feedback::new(feedback_id, self.id, rating, comment, test_scenario, call_details, created_by)?;
         self.feedbacks.push(feedback);
         Ok(self.feedbacks.last().unwrap())
     }
 
+    /// Attaches feedback read back from storage without checking it again
+    /// against the output schema; that happened in `add_feedback`.
+    pub fn restore_feedbacks(&mut self, feedbacks: Vec<Feedback>) {
+        self.feedbacks = feedbacks;
+    }
+
     pub fn average_rating(&self) -> Option<f64> {
         if self.feedbacks.is_empty() {
             return None;
@@ -122,7 +170,13 @@ impl PromptVersion {
         Some(sum as f64 / self.feedbacks.len() as f64)
     }
 
-    fn generate_digest(content: &str, content_type: ContentType) -> String {
+    fn generate_digest(
+        content: &str,
+        content_type: ContentType,
+        model_config: Option<&ModelConfig>,
+        tools: &[ToolDefinition],
+        output_schema: Option<&OutputSchema>,
+    ) -> String {
         let mut hasher = Sha256::new();
         hasher.update(content.as_bytes());
         let type_suffix = match content_type {
@@ -130,16 +184,30 @@ impl PromptVersion {
             ContentType::Template => "template",
         };
         hasher.update(type_suffix.as_bytes());
+        if let Some(config) = model_config {
+            hasher.update(config.canonical_string().as_bytes());
+        }
+        for tool in tools {
+            let tool_json = serde_json::json!({
+                "name": tool.name(),
+                "description": tool.description(),
+                "parameters": tool.parameters(),
+            });
+            hasher.update(format!("tool={}", tool_json).as_bytes());
+        }
+        if let Some(schema) = output_schema {
+            hasher.update(format!("output_schema={}:{}", schema.name(), schema.schema()).as_bytes());
+        }
         let result = hasher.finalize();
         format!("sha256:{}", hex::encode(result))
     }
 
-    pub fn delete_feedback(&mut self, feedback_id: Uuid) -> Result<(), String> {
+    pub fn delete_feedback(&mut self, feedback_id: Uuid) -> Result<(), DomainError> {
         let initial_len = self.feedbacks.len();
         self.feedbacks.retain(|f| f.id() != feedback_id);
 
         if self.feedbacks.len() == initial_len {
-            return Err("Feedback not found".to_string());
+            return Err(DomainError::not_found("Feedback not found"));
         }
 
         Ok(())
@@ -154,10 +222,10 @@ impl PromptVersion {
         feedback_id: Uuid,
         rating: Option<u8>,
         comment: Option<Option<String>>,
-    ) -> Result<(), String> {
+    ) -> Result<(), DomainError> {
         let feedback = self.feedbacks.iter_mut()
             .find(|f| f.id() == feedback_id)
-            .ok_or("Feedback not found")?;
+            .ok_or_else(|| DomainError::not_found("Feedback not found"))?;
 
         if let Some(r) = rating {
             feedback.update_rating(r)?;
@@ -168,7 +236,7 @@ impl PromptVersion {
         Ok(())
     }
 
-    pub fn extract_variables(&self) -> Result<Vec<String>, String> {
+    pub fn extract_variables(&self) -> Result<Vec<String>, DomainError> {
         if self.content_type != ContentType::Template {
             return Ok(Vec::new());
         }
@@ -210,12 +278,14 @@ impl PromptVersion {
         suggestion_id: Uuid,
         suggested_content: String,
         ai_rationale: String,
-    ) -> Result<&ImprovementSuggestion, String> {
+        created_by: Option<Actor>,
+    ) -> Result<&ImprovementSuggestion, DomainError> {
         let suggestion = ImprovementSuggestion::new(
             suggestion_id,
             self.id,
             suggested_content,
             ai_rationale,
+            created_by,
         );
         self.improvement_suggestions.push(suggestion);
         Ok(self.improvement_suggestions.last().unwrap())
@@ -225,10 +295,10 @@ impl PromptVersion {
         &mut self,
         suggestion_id: Uuid,
         resulting_version_id: Uuid,
-    ) -> Result<(), String> {
+    ) -> Result<(), DomainError> {
         let suggestion = self.improvement_suggestions.iter_mut()
             .find(|s| s.id() == suggestion_id)
-            .ok_or("Suggestion not found")?;
+            .ok_or_else(|| DomainError::not_found("Suggestion not found"))?;
 
         suggestion.accept(resulting_version_id)
     }
@@ -237,10 +307,10 @@ impl PromptVersion {
         &mut self,
         suggestion_id: Uuid,
         reason: String,
-    ) -> Result<(), String> {
+    ) -> Result<(), DomainError> {
         let suggestion = self.improvement_suggestions.iter_mut()
             .find(|s| s.id() == suggestion_id)
-            .ok_or("Suggestion not found")?;
+            .ok_or_else(|| DomainError::not_found("Suggestion not found"))?;
 
         suggestion.decline(reason)
     }
@@ -0,0 +1,15 @@
+use super::{ContentType, ModelConfig, OutputSchema, ToolDefinition, Version};
+
+/// Author-supplied contents of a version, before it gets an id, digest and
+/// owner.
+#[derive(Debug, Clone)]
+pub struct VersionDraft {
+    pub version: Version,
+    pub content: String,
+    pub content_type: ContentType,
+    pub variables: Option<Vec<String>>,
+    pub changelog: Option<String>,
+    pub model_config: Option<ModelConfig>,
+    pub tools: Vec<ToolDefinition>,
+    pub output_schema: Option<OutputSchema>,
+}
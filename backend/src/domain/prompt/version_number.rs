@@ -1,4 +1,5 @@
 use std::fmt;
+use crate::domain::error::DomainError;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Version {
@@ -12,19 +13,19 @@ impl Version {
         Self { major, minor, patch }
     }
 
-    pub fn from_str(s: &str) -> Result<Self, String> {
+    pub fn from_str(s: &str) -> Result<Self, DomainError> {
         let parts: Vec<&str> = s.split('.').collect();
 
         if parts.len() != 3 {
-            return Err("Version must be in format 'major.minor.patch'".to_string());
+            return Err(DomainError::validation("version", "Version must be in format 'major.minor.patch'"));
         }
 
         let major = parts[0].parse::<u32>()
-            .map_err(|_| "Invalid major version number")?;
+            .map_err(|_| DomainError::validation("version", "Invalid major version number"))?;
         let minor = parts[1].parse::<u32>()
-            .map_err(|_| "Invalid minor version number")?;
+            .map_err(|_| DomainError::validation("version", "Invalid minor version number"))?;
         let patch = parts[2].parse::<u32>()
-            .map_err(|_| "Invalid patch version number")?;
+            .map_err(|_| DomainError::validation("version", "Invalid patch version number"))?;
 
         Ok(Self::new(major, minor, patch))
     }
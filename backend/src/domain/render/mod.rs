@@ -0,0 +1,3 @@
+mod render_event;
+
+pub use render_event::{RenderEvent, RenderEventRecord};
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use serde_json::{Map, Value};
+use sha2::{Sha256, Digest};
+use uuid::Uuid;
+use crate::domain::actor::Actor;
+
+/// Stored state of a render event, as read back by a repository.
+#[derive(Debug, Clone)]
+pub struct RenderEventRecord {
+    pub id: Uuid,
+    pub rendered_at: DateTime<Utc>,
+    pub actor: Actor,
+    pub prompt_id: Uuid,
+    pub version_id: Uuid,
+    pub tag: Option<String>,
+    pub context_hash: String,
+    pub context: Option<Value>,
+}
+
+/// One render of a prompt version. Callers send its id back with feedback
+/// so the feedback can be tied to the exact input that was rendered.
+#[derive(Debug, Clone)]
+pub struct RenderEvent {
+    id: Uuid,
+    rendered_at: DateTime<Utc>,
+    actor: Actor,
+    prompt_id: Uuid,
+    version_id: Uuid,
+    tag: Option<String>,
+    context_hash: String,
+    context: Option<Value>,
+}
+
+impl RenderEvent {
+    /// `tag` is the tag the version was resolved through, `None` for renders
+    /// by version id. A missing context is recorded as an empty object, which
+    /// renders the same.
+    pub fn new(actor: Actor, prompt_id: Uuid, version_id: Uuid, tag: Option<String>, context: Option<Value>) -> Self {
+        let context = context.unwrap_or_else(|| Value::Object(Map::new()));
+        Self {
+            id: Uuid::new_v4(),
+            rendered_at: Utc::now(),
+            actor,
+            prompt_id,
+            version_id,
+            tag,
+            context_hash: Self::hash_context(&context),
+            context: Some(context),
+        }
+    }
+
+    pub fn from_repository(record: RenderEventRecord) -> Self {
+        Self {
+            id: record.id,
+            rendered_at: record.rendered_at,
+            actor: record.actor,
+            prompt_id: record.prompt_id,
+            version_id: record.version_id,
+            tag: record.tag,
+            context_hash: record.context_hash,
+            context: record.context,
+        }
+    }
+
+    /// The same event keeping only the context hash, for deployments that
+    /// must not store render inputs.
+    pub fn without_context(&self) -> Self {
+        Self { context: None, ..self.clone() }
+    }
+
+    fn hash_context(context: &Value) -> String {
+        let hash = Sha256::digest(context.to_string().as_bytes());
+        format!("sha256:{}", hex::encode(hash))
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn rendered_at(&self) -> DateTime<Utc> {
+        self.rendered_at
+    }
+
+    /// The caller, including the API key it rendered with.
+    pub fn actor(&self) -> Actor {
+        self.actor
+    }
+
+    pub fn prompt_id(&self) -> Uuid {
+        self.prompt_id
+    }
+
+    pub fn version_id(&self) -> Uuid {
+        self.version_id
+    }
+
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    pub fn context_hash(&self) -> &str {
+        &self.context_hash
+    }
+
+    /// `None` when only the hash was stored.
+    pub fn context(&self) -> Option<&Value> {
+        self.context.as_ref()
+    }
+}
@@ -0,0 +1,226 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use super::DeliveryStatus;
+
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 8;
+
+/// Stored state of a webhook delivery, as read back by a repository.
+#[derive(Debug, Clone)]
+pub struct WebhookDeliveryRecord {
+    pub id: Uuid,
+    pub webhook_id: Uuid,
+    pub event_id: Uuid,
+    pub event_type: String,
+    pub payload: String,
+    pub status: DeliveryStatus,
+    pub attempts: u32,
+    pub response_status: Option<u16>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One event sent to one webhook, with the outcome of its latest attempt.
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    id: Uuid,
+    webhook_id: Uuid,
+    event_id: Uuid,
+    event_type: String,
+    payload: String,
+    status: DeliveryStatus,
+    attempts: u32,
+    response_status: Option<u16>,
+    last_error: Option<String>,
+    next_attempt_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+}
+
+impl WebhookDelivery {
+    pub fn new(webhook_id: Uuid, event_id: Uuid, event_type: String, payload: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            webhook_id,
+            event_id,
+            event_type,
+            payload,
+            status: DeliveryStatus::Pending,
+            attempts: 0,
+            response_status: None,
+            last_error: None,
+            next_attempt_at: Some(now),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    pub fn from_repository(record: WebhookDeliveryRecord) -> Self {
+        Self {
+            id: record.id,
+            webhook_id: record.webhook_id,
+            event_id: record.event_id,
+            event_type: record.event_type,
+            payload: record.payload,
+            status: record.status,
+            attempts: record.attempts,
+            response_status: record.response_status,
+            last_error: record.last_error,
+            next_attempt_at: record.next_attempt_at,
+            created_at: record.created_at,
+            updated_at: record.updated_at,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn webhook_id(&self) -> Uuid {
+        self.webhook_id
+    }
+
+    pub fn event_id(&self) -> Uuid {
+        self.event_id
+    }
+
+    pub fn event_type(&self) -> &str {
+        &self.event_type
+    }
+
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+
+    pub fn status(&self) -> DeliveryStatus {
+        self.status
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn response_status(&self) -> Option<u16> {
+        self.response_status
+    }
+
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    pub fn next_attempt_at(&self) -> Option<DateTime<Utc>> {
+        self.next_attempt_at
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn updated_at(&self) -> DateTime<Utc> {
+        self.updated_at
+    }
+
+    pub fn record_success(&mut self, response_status: u16) {
+        self.attempts += 1;
+        self.status = DeliveryStatus::Succeeded;
+        self.response_status = Some(response_status);
+        self.last_error = None;
+        self.next_attempt_at = None;
+        self.updated_at = Utc::now();
+    }
+
+    /// Schedules a retry with exponential backoff (10s, 20s, 40s, ... capped
+    /// at an hour) until `MAX_DELIVERY_ATTEMPTS` is reached.
+    pub fn record_failure(&mut self, response_status: Option<u16>, error: String) {
+        self.attempts += 1;
+        self.response_status = response_status;
+        self.last_error = Some(error);
+        self.updated_at = Utc::now();
+
+        if self.attempts >= MAX_DELIVERY_ATTEMPTS {
+            self.status = DeliveryStatus::Failed;
+            self.next_attempt_at = None;
+        } else {
+            let backoff_secs = (10i64 << (self.attempts - 1)).min(3600);
+            self.status = DeliveryStatus::Pending;
+            self.next_attempt_at = Some(self.updated_at + Duration::seconds(backoff_secs));
+        }
+    }
+
+    pub fn redeliver(&mut self) {
+        self.status = DeliveryStatus::Pending;
+        self.attempts = 0;
+        self.next_attempt_at = Some(Utc::now());
+        self.updated_at = Utc::now();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delivery() -> WebhookDelivery {
+        WebhookDelivery::new(Uuid::new_v4(), Uuid::new_v4(), "prompt.updated".to_string(), "{}".to_string())
+    }
+
+    #[test]
+    fn backs_off_exponentially_between_retries() {
+        let mut delivery = delivery();
+
+        for (attempt, backoff_secs) in [10, 20, 40, 80, 160, 320, 640].into_iter().enumerate() {
+            delivery.record_failure(Some(500), "Internal Server Error".to_string());
+
+            assert_eq!(delivery.attempts(), attempt as u32 + 1);
+            assert_eq!(delivery.status(), DeliveryStatus::Pending);
+            assert_eq!(delivery.response_status(), Some(500));
+            assert_eq!(
+                delivery.next_attempt_at(),
+                Some(delivery.updated_at() + Duration::seconds(backoff_secs)),
+            );
+        }
+    }
+
+    #[test]
+    fn fails_once_attempts_run_out() {
+        let mut delivery = delivery();
+
+        for _ in 0..MAX_DELIVERY_ATTEMPTS {
+            delivery.record_failure(None, "connection refused".to_string());
+        }
+
+        assert_eq!(delivery.attempts(), MAX_DELIVERY_ATTEMPTS);
+        assert_eq!(delivery.status(), DeliveryStatus::Failed);
+        assert_eq!(delivery.next_attempt_at(), None);
+        assert_eq!(delivery.last_error(), Some("connection refused"));
+    }
+
+    #[test]
+    fn redeliver_restarts_the_schedule() {
+        let mut delivery = delivery();
+        for _ in 0..MAX_DELIVERY_ATTEMPTS {
+            delivery.record_failure(Some(503), "Service Unavailable".to_string());
+        }
+
+        delivery.redeliver();
+        delivery.record_failure(Some(503), "Service Unavailable".to_string());
+
+        assert_eq!(delivery.attempts(), 1);
+        assert_eq!(delivery.status(), DeliveryStatus::Pending);
+        assert_eq!(delivery.next_attempt_at(), Some(delivery.updated_at() + Duration::seconds(10)));
+    }
+
+    #[test]
+    fn success_clears_the_retry() {
+        let mut delivery = delivery();
+        delivery.record_failure(Some(502), "Bad Gateway".to_string());
+
+        delivery.record_success(204);
+
+        assert_eq!(delivery.status(), DeliveryStatus::Succeeded);
+        assert_eq!(delivery.attempts(), 2);
+        assert_eq!(delivery.last_error(), None);
+        assert_eq!(delivery.next_attempt_at(), None);
+    }
+}
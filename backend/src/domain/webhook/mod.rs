@@ -0,0 +1,11 @@
+mod subscription;
+mod delivery;
+mod delivery_status;
+mod signature;
+mod target;
+
+pub use subscription::{Webhook, WebhookRecord, generate_webhook_secret};
+pub use delivery::{WebhookDelivery, WebhookDeliveryRecord};
+pub use delivery_status::DeliveryStatus;
+pub use signature::sign_payload;
+pub use target::{is_public_address, parse_webhook_url};
@@ -0,0 +1,134 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::error::DomainError;
+use crate::domain::prompt::PromptEvent;
+use super::target::parse_webhook_url;
+
+pub const EVENT_TYPES: &[&str] = &[
+    "version_created",
+    "tag_moved",
+    "suggestion_created",
+    "suggestion_accepted",
+    "suggestion_declined",
+    "feedback_submitted",
+];
+
+/// Stored state of a webhook, as read back by a repository.
+#[derive(Debug, Clone)]
+pub struct WebhookRecord {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub prompt_id: Option<Uuid>,
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Webhook {
+    id: Uuid,
+    user_id: Uuid,
+    prompt_id: Option<Uuid>,
+    url: String,
+    secret: String,
+    event_types: Vec<String>,
+    is_active: bool,
+    created_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    /// Subscribes `url` to the user's prompt events, optionally limited to
+    /// one prompt. An empty `event_types` means every event type.
+    /// `allow_private_targets` accepts loopback and private-network URLs.
+    pub fn new(
+        user_id: Uuid,
+        prompt_id: Option<Uuid>,
+        url: String,
+        secret: String,
+        event_types: Vec<String>,
+        allow_private_targets: bool,
+    ) -> Result<Self, DomainError> {
+        let url = parse_webhook_url(&url, allow_private_targets)?.to_string();
+        if let Some(unknown) = event_types.iter().find(|t| !EVENT_TYPES.contains(&t.as_str())) {
+            return Err(DomainError::validation("event_types", format!("Unknown event type: {}", unknown)));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            user_id,
+            prompt_id,
+            url,
+            secret,
+            event_types,
+            is_active: true,
+            created_at: Utc::now(),
+        })
+    }
+
+    pub fn from_repository(record: WebhookRecord) -> Self {
+        Self {
+            id: record.id,
+            user_id: record.user_id,
+            prompt_id: record.prompt_id,
+            url: record.url,
+            secret: record.secret,
+            event_types: record.event_types,
+            is_active: record.is_active,
+            created_at: record.created_at,
+        }
+    }
+
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    pub fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    pub fn prompt_id(&self) -> Option<Uuid> {
+        self.prompt_id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn event_types(&self) -> &[String] {
+        &self.event_types
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.is_active
+    }
+
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.created_at
+    }
+
+    pub fn matches(&self, event: &PromptEvent) -> bool {
+        self.is_active
+            && self.user_id == event.user_id()
+            && self.prompt_id.is_none_or(|id| id == event.prompt_id())
+            && (self.event_types.is_empty() || self.event_types.iter().any(|t| t == event.kind().as_str()))
+    }
+}
+
+pub fn generate_webhook_secret() -> String {
+    use rand::Rng;
+    const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+    const SECRET_LENGTH: usize = 32;
+
+    let mut rng = rand::rng();
+    let secret: String = (0..SECRET_LENGTH)
+        .map(|_| CHARSET[rng.random_range(0..CHARSET.len())] as char)
+        .collect();
+
+    format!("whsec_{}", secret)
+}
@@ -0,0 +1,77 @@
+use crate::application::ReadinessProbe;
+use async_trait::async_trait;
+use sqlx::migrate::Migrator;
+use sqlx::{PgPool, SqlitePool};
+
+pub static POSTGRES_MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+pub static SQLITE_MIGRATOR: Migrator = sqlx::migrate!("./migrations_sqlite");
+
+const APPLIED_MIGRATIONS: &str = "SELECT version FROM _sqlx_migrations WHERE success";
+
+/// Ready when a pooled connection answers and every migration bundled with
+/// this build has been applied.
+pub struct PostgresReadiness {
+    pool: PgPool,
+}
+
+impl PostgresReadiness {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReadinessProbe for PostgresReadiness {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        let applied = sqlx::query_scalar::<_, i64>(APPLIED_MIGRATIONS)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(unreachable_database)?;
+        check_migrations(&POSTGRES_MIGRATOR, &applied)
+    }
+}
+
+pub struct SqliteReadiness {
+    pool: SqlitePool,
+}
+
+impl SqliteReadiness {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReadinessProbe for SqliteReadiness {
+    fn name(&self) -> &'static str {
+        "database"
+    }
+
+    async fn check(&self) -> Result<(), String> {
+        let applied = sqlx::query_scalar::<_, i64>(APPLIED_MIGRATIONS)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(unreachable_database)?;
+        check_migrations(&SQLITE_MIGRATOR, &applied)
+    }
+}
+
+fn unreachable_database(error: sqlx::Error) -> String {
+    tracing::warn!(%error, "Readiness check failed");
+    "database unreachable".to_string()
+}
+
+fn check_migrations(migrator: &Migrator, applied: &[i64]) -> Result<(), String> {
+    let pending = migrator
+        .iter()
+        .filter(|m| m.migration_type.is_up_migration() && !applied.contains(&m.version))
+        .count();
+    match pending {
+        0 => Ok(()),
+        n => Err(format!("{} migration(s) not applied", n)),
+    }
+}
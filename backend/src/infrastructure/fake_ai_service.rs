@@ -0,0 +1,39 @@
+use crate::application::AIService;
+use crate::domain::prompt::{Feedback, ContentType};
+use async_trait::async_trait;
+
+/// Deterministic stand-in for `OpenAIService` in dev mode: suggests the
+/// prompt with the lowest-rated feedback comment appended as a note.
+pub struct FakeAIService;
+
+#[async_trait]
+impl AIService for FakeAIService {
+    async fn analyze_feedback_and_suggest(
+        &self,
+        prompt_content: &str,
+        _content_type: ContentType,
+        feedbacks: &[Feedback],
+    ) -> Result<(String, String), String> {
+        let worst = feedbacks
+            .iter()
+            .filter(|f| f.comment().is_some())
+            .min_by_key(|f| f.rating());
+
+        let Some(worst) = worst else {
+            return Ok((
+                prompt_content.to_string(),
+                format!("Dev mode: {} feedback item(s) without comments; no change suggested.", feedbacks.len()),
+            ));
+        };
+
+        let comment = worst.comment().unwrap_or_default();
+        Ok((
+            format!("{}\n\nNote: {}", prompt_content.trim_end(), comment),
+            format!(
+                "Dev mode: addressed the lowest-rated feedback ({}/5) out of {} item(s).",
+                worst.rating(),
+                feedbacks.len()
+            ),
+        ))
+    }
+}
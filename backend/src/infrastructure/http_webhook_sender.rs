@@ -0,0 +1,65 @@
+use crate::application::{WebhookSender, SEND_TIMEOUT};
+use crate::domain::webhook::{is_public_address, parse_webhook_url};
+use async_trait::async_trait;
+use reqwest::dns::{Addrs, Name, Resolve, Resolving};
+use std::sync::Arc;
+
+pub struct HttpWebhookSender {
+    client: reqwest::Client,
+    allow_private_targets: bool,
+}
+
+impl HttpWebhookSender {
+    /// Unless `allow_private_targets` is set, only delivers to public
+    /// addresses, checked both on the URL and after DNS resolution.
+    pub fn new(allow_private_targets: bool) -> Self {
+        let mut builder = reqwest::Client::builder()
+            .timeout(SEND_TIMEOUT)
+            .redirect(reqwest::redirect::Policy::none());
+        if !allow_private_targets {
+            builder = builder.dns_resolver(Arc::new(PublicOnlyResolver));
+        }
+        let client = builder.build().expect("Failed to build webhook HTTP client");
+        Self { client, allow_private_targets }
+    }
+}
+
+#[async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn send(&self, url: &str, headers: &[(&str, String)], body: &str) -> Result<u16, String> {
+        // Rejects private IP literals, which never reach the resolver.
+        let url = parse_webhook_url(url, self.allow_private_targets).map_err(|e| e.to_string())?;
+
+        let mut request = self.client.post(url).body(body.to_string());
+        for (name, value) in headers {
+            request = request.header(*name, value);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach webhook receiver: {}", e))?;
+
+        Ok(response.status().as_u16())
+    }
+}
+
+/// Resolves webhook hosts and drops every non-public address, so a name that
+/// points at an internal service cannot be used to reach it.
+struct PublicOnlyResolver;
+
+impl Resolve for PublicOnlyResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addrs: Vec<_> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_address(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("{} does not resolve to a public address", host).into());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
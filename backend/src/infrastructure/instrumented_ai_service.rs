@@ -0,0 +1,38 @@
+use crate::application::{AIService, Metrics};
+use crate::domain::prompt::{Feedback, ContentType};
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::Instrument;
+
+/// Records latency and failures of every call to the wrapped provider, and
+/// traces each call in its own span.
+pub struct InstrumentedAIService {
+    inner: Arc<dyn AIService>,
+    provider: &'static str,
+    metrics: Arc<Metrics>,
+}
+
+impl InstrumentedAIService {
+    pub fn new(inner: Arc<dyn AIService>, provider: &'static str, metrics: Arc<Metrics>) -> Self {
+        Self { inner, provider, metrics }
+    }
+}
+
+#[async_trait]
+impl AIService for InstrumentedAIService {
+    async fn analyze_feedback_and_suggest(
+        &self,
+        prompt_content: &str,
+        content_type: ContentType,
+        feedbacks: &[Feedback],
+    ) -> Result<(String, String), String> {
+        let started = Instant::now();
+        let result = self.inner
+            .analyze_feedback_and_suggest(prompt_content, content_type, feedbacks)
+            .instrument(tracing::info_span!("ai.analyze_feedback", provider = self.provider, feedback_count = feedbacks.len()))
+            .await;
+        self.metrics.record_ai_call(self.provider, result.is_ok(), started.elapsed());
+        result
+    }
+}
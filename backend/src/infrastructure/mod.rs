@@ -1,5 +1,17 @@
 pub mod repositories;
 pub mod openai_service;
+pub mod fake_ai_service;
+pub mod tiktoken_tokenizer;
+pub mod postgres_change_listener;
+pub mod http_webhook_sender;
+pub mod database_readiness;
+pub mod instrumented_ai_service;
 
 pub use repositories::{PostgresPromptRepository, PostgresApiKeyRepository};
-pub use openai_service::OpenAIService;
\ No newline at end of file
+pub use openai_service::OpenAIService;
+pub use fake_ai_service::FakeAIService;
+pub use tiktoken_tokenizer::TiktokenTokenizer;
+pub use postgres_change_listener::PostgresChangeListener;
+pub use http_webhook_sender::HttpWebhookSender;
+pub use database_readiness::{PostgresReadiness, SqliteReadiness, POSTGRES_MIGRATOR, SQLITE_MIGRATOR};
+pub use instrumented_ai_service::InstrumentedAIService;
\ No newline at end of file
@@ -5,13 +5,19 @@ use serde::{Deserialize, Serialize};
 
 pub struct OpenAIService {
     api_key: String,
+    model: String,
+    /// API root such as `https://api.openai.com/v1`; any OpenAI-compatible
+    /// endpoint works.
+    base_url: String,
     client: reqwest::Client,
 }
 
 impl OpenAIService {
-    pub fn new(api_key: String) -> Self {
+    pub fn new(api_key: String, model: String, base_url: String) -> Self {
         Self {
             api_key,
+            model,
+            base_url: base_url.trim_end_matches('/').to_string(),
             client: reqwest::Client::new(),
         }
     }
@@ -106,7 +112,7 @@ impl AIService for OpenAIService {
         );
 
         let request = ChatRequest {
-            model: "gpt-4o-mini".to_string(),
+            model: self.model.clone(),
             messages: vec![
                 Message {
                     role: "system".to_string(),
@@ -124,7 +130,7 @@ impl AIService for OpenAIService {
         };
 
         let response = self.client
-            .post("https://api.openai.com/v1/chat/completions")
+            .post(format!("{}/chat/completions", self.base_url))
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .json(&request)
@@ -0,0 +1,161 @@
+use crate::application::{PromptChange, PromptChangeKind, event_payload};
+use crate::domain::prompt::{PromptEvent, PromptEventKind};
+use crate::infrastructure::repositories::CachedPromptRepository;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+use sqlx::PgPool;
+use sqlx::postgres::{PgListener, PgNotification};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+pub const PROMPT_CHANGES_CHANNEL: &str = "prompt_changes";
+pub const PROMPT_EVENTS_CHANNEL: &str = "prompt_events";
+
+/// Subscribes to prompt change and domain event notifications from every
+/// backend instance, evicts changed prompts from this instance's cache and
+/// republishes both to in-process subscribers.
+pub struct PostgresChangeListener {
+    pool: PgPool,
+    cache: Option<Arc<CachedPromptRepository>>,
+    changes: broadcast::Sender<PromptChange>,
+    events: broadcast::Sender<PromptEvent>,
+}
+
+impl PostgresChangeListener {
+    pub fn new(
+        pool: PgPool,
+        cache: Option<Arc<CachedPromptRepository>>,
+        changes: broadcast::Sender<PromptChange>,
+        events: broadcast::Sender<PromptEvent>,
+    ) -> Self {
+        Self { pool, cache, changes, events }
+    }
+
+    pub async fn start(self) -> Result<(), String> {
+        let mut listener = PgListener::connect_with(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to connect change listener: {}", e))?;
+        listener
+            .listen_all([PROMPT_CHANGES_CHANNEL, PROMPT_EVENTS_CHANNEL])
+            .await
+            .map_err(|e| format!("Failed to listen for prompt changes: {}", e))?;
+
+        tokio::spawn(async move {
+            loop {
+                match listener.try_recv().await {
+                    Ok(Some(notification)) => self.handle(&notification).await,
+                    // The connection dropped and notifications may have been
+                    // lost; the next call reconnects.
+                    Ok(None) => {
+                        if let Some(cache) = &self.cache {
+                            cache.clear().await;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!(error = %e, "Prompt change listener error");
+                        tokio::time::sleep(Duration::from_secs(1)).await;
+                    }
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    async fn handle(&self, notification: &PgNotification) {
+        if notification.channel() == PROMPT_EVENTS_CHANNEL {
+            match decode_event(notification.payload()) {
+                // No receivers is fine; nobody is streaming events right now.
+                Ok(event) => { let _ = self.events.send(event); }
+                Err(e) => tracing::warn!(error = %e, "Ignoring prompt event notification"),
+            }
+            return;
+        }
+
+        let change = match parse_change(notification.payload()) {
+            Ok(change) => change,
+            Err(e) => {
+                tracing::warn!(error = %e, "Ignoring prompt change notification");
+                return;
+            }
+        };
+
+        if let Some(cache) = &self.cache {
+            cache.invalidate(change.prompt_id).await;
+        }
+        let _ = self.changes.send(change);
+    }
+}
+
+fn parse_change(payload: &str) -> Result<PromptChange, String> {
+    let value: serde_json::Value = serde_json::from_str(payload).map_err(|e| e.to_string())?;
+    let field = |name: &str| {
+        value
+            .get(name)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| format!("Missing {}", name))
+    };
+
+    Ok(PromptChange {
+        prompt_id: Uuid::parse_str(field("prompt_id")?).map_err(|e| e.to_string())?,
+        user_id: Uuid::parse_str(field("user_id")?).map_err(|e| e.to_string())?,
+        kind: PromptChangeKind::from_str(field("kind")?)?,
+    })
+}
+
+/// Notification payload for a domain event; `decode_event` is its inverse.
+pub(crate) fn encode_event(event: &PromptEvent) -> String {
+    let mut value = event_payload(event);
+    value["user_id"] = json!(event.user_id().to_string());
+    value.to_string()
+}
+
+fn decode_event(payload: &str) -> Result<PromptEvent, String> {
+    let value: serde_json::Value = serde_json::from_str(payload).map_err(|e| e.to_string())?;
+    let field = |name: &str| {
+        value
+            .get(name)
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| format!("Missing {}", name))
+    };
+    let uuid = |name: &str| Uuid::parse_str(field(name)?).map_err(|e| e.to_string());
+
+    let kind = match field("type")? {
+        "version_created" => PromptEventKind::VersionCreated {
+            version_id: uuid("version_id")?,
+            version: field("version")?.to_string(),
+        },
+        "tag_moved" => PromptEventKind::TagMoved {
+            tag_name: field("tag_name")?.to_string(),
+            version_id: uuid("version_id")?,
+            previous_version_id: value.get("previous_version_id").and_then(|v| v.as_str()).map(Uuid::parse_str).transpose().map_err(|e| e.to_string())?,
+        },
+        "suggestion_created" => PromptEventKind::SuggestionCreated {
+            version_id: uuid("version_id")?,
+            suggestion_id: uuid("suggestion_id")?,
+        },
+        "suggestion_accepted" => PromptEventKind::SuggestionAccepted {
+            version_id: uuid("version_id")?,
+            suggestion_id: uuid("suggestion_id")?,
+            resulting_version_id: uuid("resulting_version_id")?,
+        },
+        "suggestion_declined" => PromptEventKind::SuggestionDeclined {
+            version_id: uuid("version_id")?,
+            suggestion_id: uuid("suggestion_id")?,
+        },
+        "feedback_submitted" => PromptEventKind::FeedbackSubmitted {
+            version_id: uuid("version_id")?,
+            feedback_id: uuid("feedback_id")?,
+            rating: value.get("rating").and_then(|v| v.as_u64()).ok_or("Missing rating")? as u8,
+        },
+        other => return Err(format!("Unknown event type: {}", other)),
+    };
+
+    let occurred_at = DateTime::parse_from_rfc3339(field("occurred_at")?)
+        .map_err(|e| e.to_string())?
+        .with_timezone(&Utc);
+
+    Ok(PromptEvent::new(uuid("id")?, uuid("prompt_id")?, uuid("user_id")?, occurred_at, kind))
+}
@@ -0,0 +1,250 @@
+use crate::application::{AppError, PromptRepository, ReadCache, CacheStats};
+use crate::domain::audit::AuditEntry;
+use crate::domain::prompt::Prompt;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+/// Which loader produced a cached prompt. A full prompt also serves
+/// content lookups; a content graph lacks feedback and suggestions, so it
+/// never serves full lookups.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum Graph {
+    Full,
+    Content,
+}
+
+struct CacheEntry {
+    prompt: Prompt,
+    expires_at: Instant,
+}
+
+struct TagEntry {
+    prompts: Vec<Prompt>,
+    expires_at: Instant,
+}
+
+/// Caches prompts by id in memory so hot render paths skip the database,
+/// keeping full prompts and the lighter content graphs apart. Entries expire
+/// after `ttl` and are dropped whenever this instance saves or deletes the
+/// prompt. Tag bundles are cached per user and tag and dropped on any
+/// invalidation, since a change to any prompt can move a tag. Other list
+/// queries are not cached, and neither are loads for update, which must see
+/// writes from other instances before their invalidation arrives.
+pub struct CachedPromptRepository {
+    inner: Arc<dyn PromptRepository>,
+    ttl: Duration,
+    max_entries: usize,
+    entries: RwLock<HashMap<(Uuid, Graph), CacheEntry>>,
+    tags: RwLock<HashMap<(Uuid, String), TagEntry>>,
+    // Bumped on every invalidation; a load that raced with one is not cached.
+    generation: AtomicU64,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    invalidations: AtomicU64,
+    // Size of `entries`, kept apart so stats never wait on or miss the lock.
+    entry_count: AtomicUsize,
+}
+
+impl CachedPromptRepository {
+    pub fn new(inner: Arc<dyn PromptRepository>, ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            inner,
+            ttl,
+            max_entries,
+            entries: RwLock::new(HashMap::new()),
+            tags: RwLock::new(HashMap::new()),
+            generation: AtomicU64::new(0),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+            invalidations: AtomicU64::new(0),
+            entry_count: AtomicUsize::new(0),
+        }
+    }
+
+    pub async fn invalidate(&self, id: Uuid) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.invalidations.fetch_add(1, Ordering::Relaxed);
+        let mut entries = self.entries.write().await;
+        entries.remove(&(id, Graph::Full));
+        entries.remove(&(id, Graph::Content));
+        self.entry_count.store(entries.len(), Ordering::Relaxed);
+        drop(entries);
+        self.tags.write().await.clear();
+    }
+
+    /// Drops every entry, e.g. when change notifications may have been missed.
+    pub async fn clear(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        self.invalidations.fetch_add(1, Ordering::Relaxed);
+        let mut entries = self.entries.write().await;
+        entries.clear();
+        self.entry_count.store(0, Ordering::Relaxed);
+        drop(entries);
+        self.tags.write().await.clear();
+    }
+
+    fn record_lookup(&self, hit: bool) {
+        let counter = if hit { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+
+    async fn cached(&self, id: Uuid, graph: Graph) -> Option<Prompt> {
+        let now = Instant::now();
+        let entries = self.entries.read().await;
+        let keys: &[Graph] = match graph {
+            Graph::Full => &[Graph::Full],
+            Graph::Content => &[Graph::Full, Graph::Content],
+        };
+        let prompt = keys
+            .iter()
+            .filter_map(|graph| entries.get(&(id, *graph)))
+            .find(|entry| entry.expires_at > now)
+            .map(|entry| entry.prompt.clone());
+        self.record_lookup(prompt.is_some());
+        prompt
+    }
+
+    /// Caches prompts loaded while `generation` was current, unless an
+    /// invalidation happened since.
+    async fn store(&self, prompts: &[Prompt], graph: Graph, generation: u64) {
+        let mut entries = self.entries.write().await;
+        if self.generation.load(Ordering::SeqCst) != generation {
+            return;
+        }
+        let now = Instant::now();
+        for prompt in prompts {
+            if entries.len() >= self.max_entries {
+                entries.retain(|_, entry| entry.expires_at > now);
+            }
+            if entries.len() < self.max_entries {
+                entries.insert((prompt.id(), graph), CacheEntry { prompt: prompt.clone(), expires_at: now + self.ttl });
+            }
+        }
+        self.entry_count.store(entries.len(), Ordering::Relaxed);
+    }
+
+    async fn get_or_load(&self, id: Uuid) -> Result<Option<Prompt>, String> {
+        if let Some(prompt) = self.cached(id, Graph::Full).await {
+            return Ok(Some(prompt));
+        }
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let prompt = self.inner.find_by_id(id).await?;
+        if let Some(prompt) = &prompt {
+            self.store(std::slice::from_ref(prompt), Graph::Full, generation).await;
+        }
+        Ok(prompt)
+    }
+}
+
+impl ReadCache for CachedPromptRepository {
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            invalidations: self.invalidations.load(Ordering::Relaxed),
+            entries: self.entry_count.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[async_trait]
+impl PromptRepository for CachedPromptRepository {
+    async fn save(&self, prompt: &Prompt, audit: &[AuditEntry], expected_revision: Option<u64>) -> Result<u64, AppError> {
+        let result = self.inner.save(prompt, audit, expected_revision).await;
+        self.invalidate(prompt.id()).await;
+        result
+    }
+
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Prompt>, String> {
+        self.get_or_load(id).await
+    }
+
+    async fn find_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String> {
+        Ok(self.get_or_load(id).await?.filter(|p| p.user_id() == user_id))
+    }
+
+    async fn find_for_update(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String> {
+        self.inner.find_for_update(id, user_id).await
+    }
+
+    async fn find_content_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String> {
+        if let Some(prompt) = self.cached(id, Graph::Content).await {
+            return Ok(Some(prompt).filter(|p| p.user_id() == user_id));
+        }
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let prompt = self.inner.find_content_by_id_and_user(id, user_id).await?;
+        if let Some(prompt) = &prompt {
+            self.store(std::slice::from_ref(prompt), Graph::Content, generation).await;
+        }
+        Ok(prompt)
+    }
+
+    /// Serves what it can from memory and loads the rest in one query.
+    async fn find_content_by_ids(&self, ids: &[Uuid], user_id: Uuid) -> Result<Vec<Prompt>, String> {
+        let mut prompts = Vec::with_capacity(ids.len());
+        let mut missing = Vec::new();
+        for id in ids {
+            match self.cached(*id, Graph::Content).await {
+                Some(prompt) if prompt.user_id() == user_id => prompts.push(prompt),
+                Some(_) => {}
+                None => missing.push(*id),
+            }
+        }
+
+        if !missing.is_empty() {
+            let generation = self.generation.load(Ordering::SeqCst);
+            let loaded = self.inner.find_content_by_ids(&missing, user_id).await?;
+            self.store(&loaded, Graph::Content, generation).await;
+            prompts.extend(loaded);
+        }
+        Ok(prompts)
+    }
+
+    async fn find_all(&self) -> Result<Vec<Prompt>, String> {
+        self.inner.find_all().await
+    }
+
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Prompt>, String> {
+        self.inner.find_by_user(user_id).await
+    }
+
+    async fn find_content_by_tag(&self, user_id: Uuid, tag_name: &str) -> Result<Vec<Prompt>, String> {
+        let key = (user_id, tag_name.to_string());
+        if let Some(entry) = self.tags.read().await.get(&key)
+            && entry.expires_at > Instant::now()
+        {
+            self.record_lookup(true);
+            return Ok(entry.prompts.clone());
+        }
+        self.record_lookup(false);
+
+        let generation = self.generation.load(Ordering::SeqCst);
+        let prompts = self.inner.find_content_by_tag(user_id, tag_name).await?;
+        self.store(&prompts, Graph::Content, generation).await;
+
+        let mut tags = self.tags.write().await;
+        if self.generation.load(Ordering::SeqCst) == generation {
+            let now = Instant::now();
+            if tags.len() >= self.max_entries {
+                tags.retain(|_, entry| entry.expires_at > now);
+            }
+            if tags.len() < self.max_entries {
+                tags.insert(key, TagEntry { prompts: prompts.clone(), expires_at: now + self.ttl });
+            }
+        }
+        Ok(prompts)
+    }
+
+    async fn delete(&self, id: Uuid, audit: &[AuditEntry]) -> Result<(), String> {
+        let result = self.inner.delete(id, audit).await;
+        self.invalidate(id).await;
+        result
+    }
+}
@@ -0,0 +1,88 @@
+use async_trait::async_trait;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::application::AppError;
+use crate::application::api_key_repository::ApiKeyRepository;
+use crate::domain::api_key::ApiKey;
+use crate::domain::audit::AuditEntry;
+use super::InMemoryAuditRepository;
+
+pub struct InMemoryApiKeyRepository {
+    store: RwLock<HashMap<Uuid, ApiKey>>,
+    audit: Arc<InMemoryAuditRepository>,
+}
+
+impl InMemoryApiKeyRepository {
+    pub fn new(audit: Arc<InMemoryAuditRepository>) -> Self {
+        Self {
+            store: RwLock::new(HashMap::new()),
+            audit,
+        }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for InMemoryApiKeyRepository {
+    async fn create(&self, api_key: &ApiKey, audit: &[AuditEntry]) -> Result<(), String> {
+        let mut store = self.store.write().await;
+        if store.values().any(|k| k.key_hash() == api_key.key_hash()) {
+            return Err("Failed to create API key: duplicate key".to_string());
+        }
+        store.insert(api_key.id(), api_key.clone());
+        self.audit.append(audit).await;
+        Ok(())
+    }
+
+    async fn find_by_key_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, String> {
+        let store = self.store.read().await;
+        Ok(store
+            .values()
+            .find(|k| k.key_hash() == key_hash && k.is_active())
+            .cloned())
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<ApiKey>, String> {
+        let store = self.store.read().await;
+        let mut keys: Vec<ApiKey> = store
+            .values()
+            .filter(|k| k.user_id() == user_id)
+            .cloned()
+            .collect();
+        keys.sort_by_key(|k| Reverse(k.created_at()));
+        Ok(keys)
+    }
+
+    async fn update_last_used(&self, id: Uuid) -> Result<(), String> {
+        if let Some(api_key) = self.store.write().await.get_mut(&id) {
+            api_key.mark_as_used();
+        }
+        Ok(())
+    }
+
+    async fn delete(&self, id: Uuid, user_id: Uuid, audit: &[AuditEntry]) -> Result<(), AppError> {
+        let mut store = self.store.write().await;
+        match store.get(&id) {
+            Some(api_key) if api_key.user_id() == user_id => {
+                store.remove(&id);
+                self.audit.append(audit).await;
+                Ok(())
+            }
+            _ => Err(AppError::not_found("API key not found or unauthorized")),
+        }
+    }
+
+    async fn deactivate(&self, id: Uuid, user_id: Uuid, audit: &[AuditEntry]) -> Result<(), AppError> {
+        match self.store.write().await.get_mut(&id) {
+            Some(api_key) if api_key.user_id() == user_id => {
+                api_key.deactivate();
+                self.audit.append(audit).await;
+                Ok(())
+            }
+            _ => Err(AppError::not_found("API key not found or unauthorized")),
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+
+use crate::application::{AuditQuery, AuditRepository};
+use crate::domain::audit::AuditEntry;
+
+pub struct InMemoryAuditRepository {
+    // Kept in append order, which is also `occurred_at` order.
+    entries: RwLock<Vec<AuditEntry>>,
+}
+
+impl InMemoryAuditRepository {
+    pub fn new() -> Self {
+        Self {
+            entries: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// Called by the other in-memory repositories alongside the change the
+    /// entries describe.
+    pub async fn append(&self, entries: &[AuditEntry]) {
+        self.entries.write().await.extend_from_slice(entries);
+    }
+}
+
+#[async_trait]
+impl AuditRepository for InMemoryAuditRepository {
+
+    async fn find(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, String> {
+        let entries = self.entries.read().await;
+        let start = match query.after {
+            Some(after) => match entries.iter().position(|e| e.id() == after) {
+                Some(index) => index + 1,
+                None => return Ok(Vec::new()),
+            },
+            None => 0,
+        };
+
+        Ok(entries[start..]
+            .iter()
+            .filter(|e| e.actor().user_id == query.user_id)
+            .filter(|e| query.prompt_id.is_none() || e.prompt_id() == query.prompt_id)
+            .filter(|e| query.action.is_none_or(|action| e.action() == action))
+            .filter(|e| query.since.is_none_or(|since| e.occurred_at() >= since))
+            .filter(|e| query.until.is_none_or(|until| e.occurred_at() < until))
+            .take(query.limit as usize)
+            .cloned()
+            .collect())
+    }
+}
@@ -1,68 +1,185 @@
-use crate::application::PromptRepository;
-use crate::domain::prompt::Prompt;
+use crate::application::{AppError, PromptRepository, PromptChange, PromptChangeKind, ETAG_MISMATCH, REVISION_CONFLICT};
+use crate::domain::audit::AuditEntry;
+use crate::domain::prompt::{Prompt, PromptEvent, PromptRecord, PromptVersion};
+use super::{InMemoryAuditRepository, InMemoryWebhookRepository};
 use async_trait::async_trait;
+use std::cmp::Reverse;
 use std::collections::HashMap;
-use tokio::sync::RwLock;
+use std::sync::Arc;
+use tokio::sync::{broadcast, RwLock};
+use uuid::Uuid;
 
+/// Process-local storage for dev mode. Prompts are kept as they were last
+/// saved, so loads hand out clean copies with no pending changes or events.
 pub struct InMemoryPromptRepository {
-    store: RwLock<HashMap<String, Prompt>>,
+    store: RwLock<HashMap<Uuid, Prompt>>,
+    changes: broadcast::Sender<PromptChange>,
+    events: broadcast::Sender<PromptEvent>,
+    audit: Arc<InMemoryAuditRepository>,
+    webhooks: Arc<InMemoryWebhookRepository>,
 }
 
 impl InMemoryPromptRepository {
-    pub fn new() -> Self {
+    pub fn new(
+        changes: broadcast::Sender<PromptChange>,
+        events: broadcast::Sender<PromptEvent>,
+        audit: Arc<InMemoryAuditRepository>,
+        webhooks: Arc<InMemoryWebhookRepository>,
+    ) -> Self {
         Self {
             store: RwLock::new(HashMap::new()),
+            changes,
+            events,
+            audit,
+            webhooks,
         }
     }
+
+    fn notify_change(&self, prompt_id: Uuid, user_id: Uuid, kind: PromptChangeKind) {
+        // No receivers is fine; nobody is streaming changes right now.
+        let _ = self.changes.send(PromptChange { prompt_id, user_id, kind });
+    }
+
+    fn publish_events(&self, prompt: &Prompt) {
+        for event in prompt.pending_events() {
+            let _ = self.events.send(event.clone());
+        }
+    }
+
+    async fn find_many(&self, filter: impl Fn(&Prompt) -> bool) -> Vec<Prompt> {
+        let store = self.store.read().await;
+        let mut prompts: Vec<Prompt> = store.values().filter(|p| filter(p)).cloned().collect();
+        prompts.sort_by_key(|p| Reverse(p.created_at()));
+        prompts
+    }
+}
+
+/// The stored prompt's versions with the feedback and suggestions `prompt`
+/// added since it was loaded.
+fn appended_versions(stored: &Prompt, prompt: &Prompt) -> Result<Vec<PromptVersion>, AppError> {
+    let mut versions = stored.versions().to_vec();
+    let changes = prompt.pending_changes();
+
+    for &(version_id, feedback_id) in changes.feedbacks() {
+        let feedback = prompt.find_version_by_id(version_id)
+            .and_then(|v| v.feedbacks().iter().find(|f| f.id() == feedback_id));
+        let version = versions.iter_mut().find(|v| v.id() == version_id);
+        let (Some(feedback), Some(version)) = (feedback, version) else {
+            return Err(AppError::Conflict(REVISION_CONFLICT.to_string()));
+        };
+        let mut feedbacks = version.feedbacks().to_vec();
+        feedbacks.push(feedback.clone());
+        version.restore_feedbacks(feedbacks);
+    }
+
+    for &(version_id, suggestion_id) in changes.suggestions() {
+        let suggestion = prompt.find_version_by_id(version_id)
+            .and_then(|v| v.find_suggestion(suggestion_id));
+        let version = versions.iter_mut().find(|v| v.id() == version_id);
+        let (Some(suggestion), Some(version)) = (suggestion, version) else {
+            return Err(AppError::Conflict(REVISION_CONFLICT.to_string()));
+        };
+        version.improvement_suggestions_mut().push(suggestion.clone());
+    }
+
+    Ok(versions)
 }
 
 #[async_trait]
 impl PromptRepository for InMemoryPromptRepository {
-    async fn save(&self, prompt: &Prompt) -> Result<(), String> {
-        let mut store = self.store.write().await;
-        store.insert(prompt.id().to_string(), prompt.clone());
-        Ok(())
+    /// Append-only saves add their feedback and suggestions to the stored
+    /// prompt, so a stale load keeps what was saved since, as the SQL
+    /// backends' row inserts do. Other saves store the whole aggregate and
+    /// are guarded on the loaded revision.
+    async fn save(&self, prompt: &Prompt, audit: &[AuditEntry], expected_revision: Option<u64>) -> Result<u64, AppError> {
+        if prompt.pending_changes().is_empty() {
+            return Ok(prompt.revision());
+        }
+
+        let revision = {
+            let mut store = self.store.write().await;
+            let stored_revision = store.get(&prompt.id()).map_or(0, |p| p.revision());
+            if expected_revision.is_some_and(|expected| expected != stored_revision) {
+                return Err(AppError::PreconditionFailed(ETAG_MISMATCH.to_string()));
+            }
+            if !prompt.pending_changes().is_append_only() && prompt.revision() != stored_revision {
+                return Err(AppError::Conflict(REVISION_CONFLICT.to_string()));
+            }
+
+            let (base, versions) = match store.get(&prompt.id()) {
+                Some(stored) if prompt.pending_changes().is_append_only() => (stored, appended_versions(stored, prompt)?),
+                _ => (prompt, prompt.versions().to_vec()),
+            };
+            let revision = stored_revision + 1;
+            let saved = Prompt::from_repository(PromptRecord {
+                id: base.id(),
+                user_id: base.user_id(),
+                name: base.name().to_string(),
+                description: base.description().map(str::to_string),
+                prompt_type: base.prompt_type(),
+                max_render_tokens: base.max_render_tokens(),
+                created_at: base.created_at(),
+                updated_at: base.updated_at(),
+                revision,
+                versions,
+                tags: base.tags().to_vec(),
+            });
+            store.insert(prompt.id(), saved);
+            self.audit.append(audit).await;
+            self.webhooks.enqueue_event_deliveries(prompt.pending_events()).await;
+            revision
+        };
+
+        self.notify_change(prompt.id(), prompt.user_id(), PromptChangeKind::Saved);
+        self.publish_events(prompt);
+
+        Ok(revision)
     }
 
-    async fn find_by_id(&self, id: &str) -> Result<Option<Prompt>, String> {
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Prompt>, String> {
         let store = self.store.read().await;
-        Ok(store.get(id).cloned())
+        Ok(store.get(&id).cloned())
     }
 
-    async fn find_by_id_and_user(&self, id: &str, user_id: &str) -> Result<Option<Prompt>, String> {
+    async fn find_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String> {
         let store = self.store.read().await;
-        Ok(store.get(id)
+        Ok(store.get(&id)
             .filter(|p| p.user_id() == user_id)
             .cloned())
     }
 
+    // Leaving out feedback saves nothing in memory, so the content finders
+    // return whole prompts.
+    async fn find_content_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String> {
+        self.find_by_id_and_user(id, user_id).await
+    }
+
+    async fn find_content_by_ids(&self, ids: &[Uuid], user_id: Uuid) -> Result<Vec<Prompt>, String> {
+        Ok(self.find_many(|p| p.user_id() == user_id && ids.contains(&p.id())).await)
+    }
+
     async fn find_all(&self) -> Result<Vec<Prompt>, String> {
-        let store = self.store.read().await;
-        Ok(store.values().cloned().collect())
+        Ok(self.find_many(|_| true).await)
     }
 
-    async fn find_by_user(&self, user_id: &str) -> Result<Vec<Prompt>, String> {
-        let store = self.store.read().await;
-        Ok(store
-            .values()
-            .filter(|p| p.user_id() == user_id)
-            .cloned()
-            .collect())
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Prompt>, String> {
+        Ok(self.find_many(|p| p.user_id() == user_id).await)
     }
 
-    async fn find_by_tag(&self, tag_name: &str) -> Result<Vec<Prompt>, String> {
-        let store = self.store.read().await;
-        Ok(store
-            .values()
-            .filter(|p| p.tags().iter().any(|t| t.name() == tag_name))
-            .cloned()
-            .collect())
+    async fn find_content_by_tag(&self, user_id: Uuid, tag_name: &str) -> Result<Vec<Prompt>, String> {
+        Ok(self.find_many(|p| p.user_id() == user_id && p.tags().iter().any(|t| t.name() == tag_name)).await)
     }
 
-    async fn delete(&self, id: &str) -> Result<(), String> {
-        let mut store = self.store.write().await;
-        store.remove(id)
-            .ok_or_else(|| "Prompt not found".to_string())?;
+    async fn delete(&self, id: Uuid, audit: &[AuditEntry]) -> Result<(), String> {
+        let prompt = {
+            let mut store = self.store.write().await;
+            let prompt = store.remove(&id).ok_or_else(|| "Prompt not found".to_string())?;
+            self.audit.append(audit).await;
+            prompt
+        };
+
+        self.notify_change(id, prompt.user_id(), PromptChangeKind::Deleted);
+
         Ok(())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,39 @@
+use async_trait::async_trait;
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::application::RenderRepository;
+use crate::domain::render::RenderEvent;
+
+pub struct InMemoryRenderRepository {
+    events: RwLock<HashMap<Uuid, RenderEvent>>,
+}
+
+impl InMemoryRenderRepository {
+    pub fn new() -> Self {
+        Self {
+            events: RwLock::new(HashMap::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl RenderRepository for InMemoryRenderRepository {
+    async fn append(&self, events: &[RenderEvent]) -> Result<(), String> {
+        let mut stored = self.events.write().await;
+        for event in events {
+            stored.insert(event.id(), event.clone());
+        }
+        Ok(())
+    }
+
+    async fn find_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<RenderEvent>, String> {
+        Ok(self.events
+            .read()
+            .await
+            .get(&id)
+            .filter(|event| event.actor().user_id == user_id)
+            .cloned())
+    }
+}
@@ -0,0 +1,161 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::application::{AppError, WebhookRepository, CLAIM_LOST, event_payload};
+use crate::domain::audit::AuditEntry;
+use crate::domain::prompt::PromptEvent;
+use crate::domain::webhook::{Webhook, WebhookDelivery, WebhookDeliveryRecord, DeliveryStatus};
+use super::InMemoryAuditRepository;
+
+pub struct InMemoryWebhookRepository {
+    webhooks: RwLock<HashMap<Uuid, Webhook>>,
+    deliveries: RwLock<HashMap<Uuid, WebhookDelivery>>,
+    audit: Arc<InMemoryAuditRepository>,
+}
+
+impl InMemoryWebhookRepository {
+    pub fn new(audit: Arc<InMemoryAuditRepository>) -> Self {
+        Self {
+            webhooks: RwLock::new(HashMap::new()),
+            deliveries: RwLock::new(HashMap::new()),
+            audit,
+        }
+    }
+
+    /// Queues a delivery of each of `events` to every webhook it matches;
+    /// the in-memory prompt repository calls it as part of each save.
+    pub async fn enqueue_event_deliveries(&self, events: &[PromptEvent]) {
+        let webhooks = self.webhooks.read().await;
+        let mut deliveries = self.deliveries.write().await;
+        for event in events {
+            let payload = event_payload(event).to_string();
+            for webhook in webhooks.values().filter(|w| w.matches(event)) {
+                let delivery = WebhookDelivery::new(
+                    webhook.id(),
+                    event.id(),
+                    event.kind().as_str().to_string(),
+                    payload.clone(),
+                );
+                deliveries.insert(delivery.id(), delivery);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookRepository for InMemoryWebhookRepository {
+    async fn create(&self, webhook: &Webhook, audit: &[AuditEntry]) -> Result<(), String> {
+        self.webhooks.write().await.insert(webhook.id(), webhook.clone());
+        self.audit.append(audit).await;
+        Ok(())
+    }
+
+    async fn find_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Webhook>, String> {
+        let webhooks = self.webhooks.read().await;
+        Ok(webhooks.get(&id)
+            .filter(|w| w.user_id() == user_id)
+            .cloned())
+    }
+
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Webhook>, String> {
+        let webhooks = self.webhooks.read().await;
+        let mut found: Vec<Webhook> = webhooks
+            .values()
+            .filter(|w| w.user_id() == user_id)
+            .cloned()
+            .collect();
+        found.sort_by_key(|w| Reverse(w.created_at()));
+        Ok(found)
+    }
+
+    async fn delete(&self, id: Uuid, user_id: Uuid, audit: &[AuditEntry]) -> Result<(), AppError> {
+        {
+            let mut webhooks = self.webhooks.write().await;
+            match webhooks.get(&id) {
+                Some(webhook) if webhook.user_id() == user_id => { webhooks.remove(&id); }
+                _ => return Err(AppError::not_found("Webhook not found")),
+            }
+        }
+
+        self.deliveries.write().await.retain(|_, d| d.webhook_id() != id);
+        self.audit.append(audit).await;
+        Ok(())
+    }
+
+    async fn save_delivery(&self, delivery: &WebhookDelivery, audit: &[AuditEntry], claimed_until: Option<DateTime<Utc>>) -> Result<(), AppError> {
+        let mut deliveries = self.deliveries.write().await;
+        if let Some(stored) = deliveries.get_mut(&delivery.id()) {
+            if claimed_until.is_some_and(|until| stored.next_attempt_at() != Some(until)) {
+                return Err(AppError::Conflict(CLAIM_LOST.to_string()));
+            }
+            *stored = delivery.clone();
+        } else if claimed_until.is_some() {
+            return Err(AppError::Conflict(CLAIM_LOST.to_string()));
+        }
+        self.audit.append(audit).await;
+        Ok(())
+    }
+
+    async fn find_delivery(&self, id: Uuid, webhook_id: Uuid) -> Result<Option<WebhookDelivery>, String> {
+        let deliveries = self.deliveries.read().await;
+        Ok(deliveries.get(&id)
+            .filter(|d| d.webhook_id() == webhook_id)
+            .cloned())
+    }
+
+    async fn find_deliveries(&self, webhook_id: Uuid, limit: i64) -> Result<Vec<WebhookDelivery>, String> {
+        let deliveries = self.deliveries.read().await;
+        let mut found: Vec<WebhookDelivery> = deliveries
+            .values()
+            .filter(|d| d.webhook_id() == webhook_id)
+            .cloned()
+            .collect();
+        found.sort_by_key(|d| Reverse(d.created_at()));
+        found.truncate(limit.max(0) as usize);
+        Ok(found)
+    }
+
+    async fn claim_due_deliveries(&self, limit: i64, lease: Duration) -> Result<Vec<(WebhookDelivery, Webhook)>, String> {
+        let now = Utc::now();
+        let lease_until = now + chrono::Duration::from_std(lease).map_err(|e| e.to_string())?;
+        let webhooks = self.webhooks.read().await;
+        let mut deliveries = self.deliveries.write().await;
+
+        let mut due: Vec<&mut WebhookDelivery> = deliveries
+            .values_mut()
+            .filter(|d| d.status() == DeliveryStatus::Pending && d.next_attempt_at().is_some_and(|at| at <= now))
+            .collect();
+        due.sort_by_key(|d| d.next_attempt_at());
+        due.truncate(limit.max(0) as usize);
+
+        let mut claimed = Vec::with_capacity(due.len());
+        for delivery in due {
+            let Some(webhook) = webhooks.get(&delivery.webhook_id()) else {
+                continue;
+            };
+            *delivery = WebhookDelivery::from_repository(WebhookDeliveryRecord {
+                id: delivery.id(),
+                webhook_id: delivery.webhook_id(),
+                event_id: delivery.event_id(),
+                event_type: delivery.event_type().to_string(),
+                payload: delivery.payload().to_string(),
+                status: delivery.status(),
+                attempts: delivery.attempts(),
+                response_status: delivery.response_status(),
+                last_error: delivery.last_error().map(str::to_string),
+                next_attempt_at: Some(lease_until),
+                created_at: delivery.created_at(),
+                updated_at: delivery.updated_at(),
+            });
+            claimed.push((delivery.clone(), webhook.clone()));
+        }
+
+        Ok(claimed)
+    }
+}
@@ -1,5 +1,33 @@
 pub mod postgres_prompt_repository;
 pub mod postgres_api_key_repository;
+pub mod cached_prompt_repository;
+pub mod postgres_webhook_repository;
+pub mod sqlite_prompt_repository;
+pub mod sqlite_api_key_repository;
+pub mod sqlite_webhook_repository;
+pub mod in_memory_prompt_repository;
+pub mod in_memory_api_key_repository;
+pub mod in_memory_webhook_repository;
+pub mod postgres_audit_repository;
+pub mod sqlite_audit_repository;
+pub mod in_memory_audit_repository;
+pub mod postgres_render_repository;
+pub mod sqlite_render_repository;
+pub mod in_memory_render_repository;
 
 pub use postgres_prompt_repository::PostgresPromptRepository;
-pub use postgres_api_key_repository::PostgresApiKeyRepository;
\ No newline at end of file
+pub use postgres_api_key_repository::PostgresApiKeyRepository;
+pub use cached_prompt_repository::CachedPromptRepository;
+pub use postgres_webhook_repository::PostgresWebhookRepository;
+pub use sqlite_prompt_repository::SqlitePromptRepository;
+pub use sqlite_api_key_repository::SqliteApiKeyRepository;
+pub use sqlite_webhook_repository::SqliteWebhookRepository;
+pub use in_memory_prompt_repository::InMemoryPromptRepository;
+pub use in_memory_api_key_repository::InMemoryApiKeyRepository;
+pub use in_memory_webhook_repository::InMemoryWebhookRepository;
+pub use postgres_audit_repository::PostgresAuditRepository;
+pub use sqlite_audit_repository::SqliteAuditRepository;
+pub use in_memory_audit_repository::InMemoryAuditRepository;
+pub use postgres_render_repository::PostgresRenderRepository;
+pub use sqlite_render_repository::SqliteRenderRepository;
+pub use in_memory_render_repository::InMemoryRenderRepository;
\ No newline at end of file
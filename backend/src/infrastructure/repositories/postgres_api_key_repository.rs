@@ -1,10 +1,14 @@
 use async_trait::async_trait;
+use tracing::instrument;
 use sqlx::{PgPool, Row};
 use uuid::Uuid;
 use chrono::{Utc};
 
+use crate::application::AppError;
 use crate::application::api_key_repository::ApiKeyRepository;
+use crate::domain::audit::AuditEntry;
 use crate::domain::api_key::ApiKey;
+use super::postgres_audit_repository::insert_audit_entries;
 
 pub struct PostgresApiKeyRepository {
     pool: PgPool,
@@ -18,7 +22,13 @@ impl PostgresApiKeyRepository {
 
 #[async_trait]
 impl ApiKeyRepository for PostgresApiKeyRepository {
-    async fn create(&self, api_key: &ApiKey) -> Result<(), String> {
+    #[instrument(name = "db.api_keys.create", skip_all, fields(db.system = "postgresql"))]
+    async fn create(&self, api_key: &ApiKey, audit: &[AuditEntry]) -> Result<(), String> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
         sqlx::query(
             "INSERT INTO api_keys (id, user_id, name, key_hash, key_prefix, created_at, is_active)
             VALUES ($1, $2, $3, $4, $5, $6, $7)"
@@ -30,13 +40,19 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
             .bind(api_key.key_prefix())
             .bind(api_key.created_at())
             .bind(api_key.is_active())
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(|e| format!("Failed to create API key: {}", e))?;
 
+        insert_audit_entries(&mut tx, audit).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit API key: {}", e))?;
+
         Ok(())
     }
 
+    #[instrument(name = "db.api_keys.find_by_key_hash", skip_all, fields(db.system = "postgresql"))]
     async fn find_by_key_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, String> {
         let record = sqlx::query(
             "SELECT id, user_id, name, key_hash, key_prefix, last_used_at, created_at, is_active
@@ -62,6 +78,7 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
         }))
     }
 
+    #[instrument(name = "db.api_keys.find_by_user_id", skip_all, fields(db.system = "postgresql"))]
     async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<ApiKey>, String> {
         let records = sqlx::query(
             "SELECT id, user_id, name, key_hash, key_prefix, last_used_at, created_at, is_active
@@ -91,6 +108,7 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
             .collect())
     }
 
+    #[instrument(name = "db.api_keys.update_last_used", skip_all, fields(db.system = "postgresql", %id))]
     async fn update_last_used(&self, id: Uuid) -> Result<(), String> {
         sqlx::query(
             "UPDATE api_keys
@@ -106,25 +124,42 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
         Ok(())
     }
 
-    async fn delete(&self, id: Uuid, user_id: Uuid) -> Result<(), String> {
+    #[instrument(name = "db.api_keys.delete", skip_all, fields(db.system = "postgresql", %id))]
+    async fn delete(&self, id: Uuid, user_id: Uuid, audit: &[AuditEntry]) -> Result<(), AppError> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
         let result = sqlx::query(
             "DELETE FROM api_keys
             WHERE id = $1 AND user_id = $2"
         )
             .bind(id)
             .bind(user_id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(|e| format!("Failed to delete API key: {}", e))?;
 
         if result.rows_affected() == 0 {
-            return Err("API key not found or unauthorized".to_string());
+            return Err(AppError::not_found("API key not found or unauthorized"));
         }
 
+        insert_audit_entries(&mut tx, audit).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit API key deletion: {}", e))?;
+
         Ok(())
     }
 
-    async fn deactivate(&self, id: Uuid, user_id: Uuid) -> Result<(), String> {
+    #[instrument(name = "db.api_keys.deactivate", skip_all, fields(db.system = "postgresql", %id))]
+    async fn deactivate(&self, id: Uuid, user_id: Uuid, audit: &[AuditEntry]) -> Result<(), AppError> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
         let result = sqlx::query(
             "UPDATE api_keys
             SET is_active = false
@@ -132,14 +167,19 @@ impl ApiKeyRepository for PostgresApiKeyRepository {
         )
             .bind(id)
             .bind(user_id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await
             .map_err(|e| format!("Failed to deactivate API key: {}", e))?;
 
         if result.rows_affected() == 0 {
-            return Err("API key not found or unauthorized".to_string());
+            return Err(AppError::not_found("API key not found or unauthorized"));
         }
 
+        insert_audit_entries(&mut tx, audit).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit API key deactivation: {}", e))?;
+
         Ok(())
     }
 }
\ No newline at end of file
@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+use tracing::instrument;
+use sqlx::{QueryBuilder, Row, PgPool, PgConnection};
+use sqlx::postgres::PgRow;
+
+use crate::application::{AuditQuery, AuditRepository};
+use crate::domain::actor::Actor;
+use crate::domain::audit::{AuditAction, AuditEntry, AuditEntryRecord};
+
+const AUDIT_COLUMNS: &str = "id, occurred_at, user_id, api_key_id, source, action, prompt_id, target_id, before, after";
+
+pub struct PostgresAuditRepository {
+    pool: PgPool,
+}
+
+impl PostgresAuditRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn read_entry(row: &PgRow) -> Result<AuditEntry, String> {
+        let action: String = row.try_get("action").map_err(|e| e.to_string())?;
+        let source: Option<String> = row.try_get("source").map_err(|e| e.to_string())?;
+        let before: Option<sqlx::types::Json<serde_json::Value>> = row.try_get("before").map_err(|e| e.to_string())?;
+        let after: Option<sqlx::types::Json<serde_json::Value>> = row.try_get("after").map_err(|e| e.to_string())?;
+
+        Ok(AuditEntry::from_repository(AuditEntryRecord {
+            id: row.try_get("id").map_err(|e| e.to_string())?,
+            occurred_at: row.try_get("occurred_at").map_err(|e| e.to_string())?,
+            actor: Actor::from_repository(
+                row.try_get("user_id").map_err(|e| e.to_string())?,
+                row.try_get("api_key_id").map_err(|e| e.to_string())?,
+                source.as_deref(),
+            )?,
+            action: AuditAction::from_str(&action)?,
+            prompt_id: row.try_get("prompt_id").map_err(|e| e.to_string())?,
+            target_id: row.try_get("target_id").map_err(|e| e.to_string())?,
+            before: before.map(|json| json.0),
+            after: after.map(|json| json.0),
+        }))
+    }
+}
+
+/// Inserts `entries` on `conn`. Repositories call it inside the transaction
+/// of the change the entries describe, so a change is never stored without
+/// its audit trail.
+#[instrument(name = "db.audit_log.append", skip_all, fields(db.system = "postgresql", count = entries.len()))]
+pub(super) async fn insert_audit_entries(conn: &mut PgConnection, entries: &[AuditEntry]) -> Result<(), String> {
+    for entry in entries {
+        sqlx::query(
+            "INSERT INTO audit_log (id, occurred_at, user_id, api_key_id, source, action, prompt_id, target_id, before, after)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)"
+        )
+            .bind(entry.id())
+            .bind(entry.occurred_at())
+            .bind(entry.actor().user_id)
+            .bind(entry.actor().api_key_id)
+            .bind(entry.actor().source.as_str())
+            .bind(entry.action().as_str())
+            .bind(entry.prompt_id())
+            .bind(entry.target_id())
+            .bind(entry.before().map(sqlx::types::Json))
+            .bind(entry.after().map(sqlx::types::Json))
+            .execute(&mut *conn)
+            .await
+            .map_err(|e| format!("Failed to append audit entry: {}", e))?;
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl AuditRepository for PostgresAuditRepository {
+    #[instrument(name = "db.audit_log.find", skip_all, fields(db.system = "postgresql"))]
+    async fn find(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, String> {
+        let mut sql = QueryBuilder::new(format!("SELECT {} FROM audit_log WHERE user_id = ", AUDIT_COLUMNS));
+        sql.push_bind(query.user_id);
+        if let Some(prompt_id) = query.prompt_id {
+            sql.push(" AND prompt_id = ").push_bind(prompt_id);
+        }
+        if let Some(action) = query.action {
+            sql.push(" AND action = ").push_bind(action.as_str());
+        }
+        if let Some(since) = query.since {
+            sql.push(" AND occurred_at >= ").push_bind(since);
+        }
+        if let Some(until) = query.until {
+            sql.push(" AND occurred_at < ").push_bind(until);
+        }
+        if let Some(after) = query.after {
+            sql.push(" AND (occurred_at, id) > (SELECT occurred_at, id FROM audit_log WHERE id = ")
+                .push_bind(after)
+                .push(")");
+        }
+        sql.push(" ORDER BY occurred_at, id LIMIT ").push_bind(query.limit);
+
+        let rows = sql.build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to read audit log: {}", e))?;
+
+        rows.iter().map(Self::read_entry).collect()
+    }
+}
@@ -1,9 +1,28 @@
-use crate::application::PromptRepository;
-use crate::domain::prompt::{Prompt, PromptVersion, Tag, Feedback, TestScenario, Version, PromptType, ContentType, ImprovementSuggestion, SuggestionStatus};
+use crate::application::{AppError, PromptRepository, PromptChangeKind, ETAG_MISMATCH, REVISION_CONFLICT};
+use crate::infrastructure::postgres_change_listener::{PROMPT_CHANGES_CHANNEL, PROMPT_EVENTS_CHANNEL, encode_event};
+use crate::domain::prompt::{Prompt, PromptRecord, PromptVersion, VersionDraft, Tag, Feedback, FeedbackRecord, TestScenario, CallDetails, CallDetailsDraft, Version, PromptType, ContentType, ImprovementSuggestion, SuggestionStatus, ModelConfig, ModelProvider, ToolDefinition, OutputSchema, TokenCount};
+use crate::domain::actor::Actor;
+use crate::domain::audit::AuditEntry;
+use super::postgres_audit_repository::insert_audit_entries;
+use super::postgres_webhook_repository::enqueue_event_deliveries;
 use async_trait::async_trait;
-use sqlx::{PgPool, Row};
+use tracing::instrument;
+use sqlx::{PgConnection, PgPool, Row};
+use sqlx::postgres::PgRow;
+use std::collections::HashMap;
 use uuid::Uuid;
 
+const PROMPT_COLUMNS: &str = "id, user_id, name, description, prompt_type, max_render_tokens, created_at, updated_at, revision";
+
+/// How much of each prompt's graph to load.
+#[derive(Clone, Copy)]
+enum Hydration {
+    /// Versions with their feedback and improvement suggestions, and tags.
+    Full,
+    /// Versions and tags only.
+    Content,
+}
+
 pub struct PostgresPromptRepository {
     pool: PgPool,
 }
@@ -13,22 +32,106 @@ impl PostgresPromptRepository {
         Self { pool }
     }
 
-    async fn fetch_versions(&self, prompt_id: Uuid) -> Result<Vec<PromptVersion>, String> {
+    /// Tells every instance listening on the channel that the prompt changed.
+    /// Sent on the transaction making the change, so Postgres delivers it
+    /// exactly when that commits and a failure here rolls the change back.
+    async fn notify_change(conn: &mut PgConnection, prompt_id: Uuid, user_id: Uuid, kind: PromptChangeKind) -> Result<(), String> {
+        let payload = serde_json::json!({
+            "prompt_id": prompt_id.to_string(),
+            "user_id": user_id.to_string(),
+            "kind": kind.as_str(),
+        });
+
+        sqlx::query("SELECT pg_notify($1, $2)")
+            .bind(PROMPT_CHANGES_CHANNEL)
+            .bind(payload.to_string())
+            .execute(conn)
+            .await
+            .map_err(|e| format!("Failed to notify prompt change: {}", e))?;
+        Ok(())
+    }
+
+    /// Publishes the domain events recorded on the prompt since it was
+    /// loaded, on the saving transaction like `notify_change`.
+    async fn publish_events(conn: &mut PgConnection, prompt: &Prompt) -> Result<(), String> {
+        for event in prompt.pending_events() {
+            sqlx::query("SELECT pg_notify($1, $2)")
+                .bind(PROMPT_EVENTS_CHANNEL)
+                .bind(encode_event(event))
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to publish prompt event: {}", e))?;
+        }
+        Ok(())
+    }
+
+    async fn fetch_prompt_row(&self, id: Uuid, user_id: Uuid) -> Result<Option<PgRow>, String> {
+        sqlx::query(&format!("SELECT {} FROM prompts WHERE id = $1 AND user_id = $2", PROMPT_COLUMNS))
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to find prompt: {}", e))
+    }
+
+    /// Loads the given prompts with their versions and tags in a fixed number
+    /// of queries, whatever the number of prompts or versions.
+    async fn load_prompts(&self, rows: Vec<PgRow>, hydration: Hydration) -> Result<Vec<Prompt>, String> {
+        let prompt_ids = rows.iter()
+            .map(|row| row.try_get("id").map_err(|e| e.to_string()))
+            .collect::<Result<Vec<Uuid>, String>>()?;
+        if prompt_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut versions = self.fetch_versions(&prompt_ids, hydration).await?;
+        let mut tags = self.fetch_tags(&prompt_ids).await?;
+
+        rows.iter()
+            .zip(&prompt_ids)
+            .map(|(row, prompt_id)| Self::build_prompt(
+                row,
+                versions.remove(prompt_id).unwrap_or_default(),
+                tags.remove(prompt_id).unwrap_or_default(),
+            ))
+            .collect()
+    }
+
+    async fn load_prompt(&self, row: Option<PgRow>, hydration: Hydration) -> Result<Option<Prompt>, String> {
+        match row {
+            Some(row) => Ok(self.load_prompts(vec![row], hydration).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    /// Versions of the given prompts keyed by prompt id, in creation order.
+    async fn fetch_versions(&self, prompt_ids: &[Uuid], hydration: Hydration) -> Result<HashMap<Uuid, Vec<PromptVersion>>, String> {
         let rows = sqlx::query(
-            "SELECT id, prompt_id, version, digest, content, content_type, variables, changelog, created_at
-             FROM versions WHERE prompt_id = $1 ORDER BY created_at"        )
-            .bind(prompt_id)
+            "SELECT id, prompt_id, version, digest, content, content_type, variables, changelog, created_at,
+                    model_provider, model_name, temperature, top_p, max_tokens, stop_sequences, seed,
+                    tools, output_schema, token_count, token_encoding,
+                    created_by, created_by_api_key_id, created_by_source
+             FROM versions WHERE prompt_id = ANY($1) ORDER BY created_at"        )
+            .bind(prompt_ids)
             .fetch_all(&self.pool)
             .await
             .map_err(|e| format!("Failed to fetch versions: {}", e))?;
 
-        let mut versions = Vec::new();
+        let (mut feedbacks, mut suggestions) = match hydration {
+            Hydration::Full => (
+                self.fetch_feedbacks(prompt_ids).await?,
+                self.fetch_improvement_suggestions(prompt_ids).await?,
+            ),
+            Hydration::Content => (HashMap::new(), HashMap::new()),
+        };
+
+        let mut versions: HashMap<Uuid, Vec<PromptVersion>> = HashMap::new();
         for row in rows {
             let version_id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
             let prompt_id: Uuid = row.try_get("prompt_id").map_err(|e| e.to_string())?;
 
-            let feedbacks = self.fetch_feedbacks(version_id).await?;
-            let suggestions = self.fetch_improvement_suggestions(version_id).await?;
+            let feedbacks = feedbacks.remove(&version_id).unwrap_or_default();
+            let suggestions = suggestions.remove(&version_id).unwrap_or_default();
 
             let version_string: String = row.try_get("version").map_err(|e| e.to_string())?;
             let version = Version::from_str(&version_string)?;
@@ -44,285 +147,572 @@ impl PostgresPromptRepository {
                 .map_err(|e| e.to_string())?
                 .map(|j| j.0);
 
-            let mut version = PromptVersion::new(
-                version_id,
-                prompt_id,
+            let model_config = Self::read_model_config(&row)?;
+            let tools = Self::read_tools(&row)?;
+            let output_schema = Self::read_output_schema(&row)?;
+
+            let draft = VersionDraft {
                 version,
-                row.try_get("content").map_err(|e| e.to_string())?,
+                content: row.try_get("content").map_err(|e| e.to_string())?,
                 content_type,
                 variables,
-                row.try_get("changelog").map_err(|e| e.to_string())?,
-            );
+                changelog: row.try_get("changelog").map_err(|e| e.to_string())?,
+                model_config,
+                tools,
+                output_schema,
+            };
+            let mut version = PromptVersion::new(version_id, prompt_id, draft, Self::read_author(&row)?);
 
-            for feedback in feedbacks {
-                let _ = version.add_feedback(
-                    feedback.id(),
-                    feedback.rating(),
-                    feedback.comment().map(|s| s.to_string()),
-                    feedback.test_scenario().cloned(),
-                );
+            let token_count: Option<i32> = row.try_get("token_count").map_err(|e| e.to_string())?;
+            let token_encoding: Option<String> = row.try_get("token_encoding").map_err(|e| e.to_string())?;
+            if let (Some(tokens), Some(encoding)) = (token_count, token_encoding) {
+                version.record_token_count(TokenCount::new(tokens as u32, encoding));
             }
 
+            version.restore_feedbacks(feedbacks);
+
             for suggestion in suggestions {
                 version.improvement_suggestions_mut().push(suggestion);
             }
 
-            versions.push(version);
+            versions.entry(prompt_id).or_default().push(version);
         }
 
         Ok(versions)
     }
 
-    async fn fetch_tags(&self, prompt_id: Uuid) -> Result<Vec<Tag>, String> {
-        let rows = sqlx::query(
-            "SELECT id, prompt_id, version_id, name, updated_at
-             FROM tags WHERE prompt_id = $1"        )
-            .bind(prompt_id)
-            .fetch_all(&self.pool)
-            .await
-            .map_err(|e| format!("Failed to fetch tags: {}", e))?;
+    fn read_model_config(row: &PgRow) -> Result<Option<ModelConfig>, String> {
+        let provider: Option<String> = row.try_get("model_provider").map_err(|e| e.to_string())?;
+        let model: Option<String> = row.try_get("model_name").map_err(|e| e.to_string())?;
 
-        rows.iter()
-            .map(|row| {
-                let id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
-                let prompt_id: Uuid = row.try_get("prompt_id").map_err(|e| e.to_string())?;
-                let version_id: Uuid = row.try_get("version_id").map_err(|e| e.to_string())?;
-
-                Ok(Tag::new(
-                    id,
-                    prompt_id,
-                    version_id,
-                    row.try_get("name").map_err(|e| e.to_string())?,
-                ))
+        let (provider, model) = match (provider, model) {
+            (Some(provider), Some(model)) => (ModelProvider::from_str(&provider)?, model),
+            _ => return Ok(None),
+        };
+
+        let stop_sequences: Vec<String> = row.try_get::<Option<sqlx::types::Json<Vec<String>>>, _>("stop_sequences")
+            .map_err(|e| e.to_string())?
+            .map(|j| j.0)
+            .unwrap_or_default();
+
+        let config = ModelConfig::new(
+            provider,
+            model,
+            row.try_get("temperature").map_err(|e| e.to_string())?,
+            row.try_get("top_p").map_err(|e| e.to_string())?,
+            row.try_get::<Option<i32>, _>("max_tokens").map_err(|e| e.to_string())?.map(|t| t as u32),
+            stop_sequences,
+            row.try_get("seed").map_err(|e| e.to_string())?,
+        )?;
+
+        Ok(Some(config))
+    }
+
+    fn read_tools(row: &PgRow) -> Result<Vec<ToolDefinition>, String> {
+        let tools: Option<sqlx::types::Json<Vec<serde_json::Value>>> = row.try_get("tools")
+            .map_err(|e| e.to_string())?;
+
+        tools
+            .map(|j| j.0)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|tool| {
+                let name = tool.get("name")
+                    .and_then(|n| n.as_str())
+                    .ok_or("Stored tool is missing its name".to_string())?
+                    .to_string();
+                let description = tool.get("description")
+                    .and_then(|d| d.as_str())
+                    .map(|d| d.to_string());
+                let parameters = tool.get("parameters").cloned().unwrap_or_default();
+                Ok(ToolDefinition::new(name, description, parameters)?)
             })
             .collect()
     }
 
-    async fn fetch_feedbacks(&self, version_id: Uuid) -> Result<Vec<Feedback>, String> {
+    fn read_output_schema(row: &PgRow) -> Result<Option<OutputSchema>, String> {
+        let output_schema: Option<sqlx::types::Json<serde_json::Value>> = row.try_get("output_schema")
+            .map_err(|e| e.to_string())?;
+
+        output_schema
+            .map(|j| {
+                let name = j.0.get("name")
+                    .and_then(|n| n.as_str())
+                    .ok_or("Stored output schema is missing its name".to_string())?
+                    .to_string();
+                let schema = j.0.get("schema").cloned().unwrap_or_default();
+                Ok(OutputSchema::new(name, schema)?)
+            })
+            .transpose()
+    }
+
+    /// `None` when the caller reported nothing about the call.
+    fn read_call_details(row: &PgRow) -> Result<Option<CallDetails>, String> {
+        let details = CallDetails::new(CallDetailsDraft {
+            model: row.try_get("model").map_err(|e| e.to_string())?,
+            latency_ms: row.try_get::<Option<i64>, _>("latency_ms").map_err(|e| e.to_string())?.map(|v| v as u64),
+            input_tokens: row.try_get::<Option<i32>, _>("input_tokens").map_err(|e| e.to_string())?.map(|v| v as u32),
+            output_tokens: row.try_get::<Option<i32>, _>("output_tokens").map_err(|e| e.to_string())?.map(|v| v as u32),
+            cost_usd: row.try_get("cost_usd").map_err(|e| e.to_string())?,
+            render_context: row.try_get::<Option<sqlx::types::Json<serde_json::Value>>, _>("render_context").map_err(|e| e.to_string())?.map(|j| j.0),
+            trace_id: row.try_get("trace_id").map_err(|e| e.to_string())?,
+            metadata: row.try_get::<Option<sqlx::types::Json<serde_json::Value>>, _>("metadata").map_err(|e| e.to_string())?.map(|j| j.0),
+        })?;
+        let details = match row.try_get::<Option<Uuid>, _>("render_id").map_err(|e| e.to_string())? {
+            Some(render_id) => details.link_render(render_id, None),
+            None => details,
+        };
+        Ok(Some(details).filter(|d| !d.is_empty()))
+    }
+
+    /// Who created a version, feedback or suggestion; `None` for rows
+    /// written before authorship was recorded.
+    fn read_author(row: &PgRow) -> Result<Option<Actor>, String> {
+        let Some(user_id) = row.try_get::<Option<Uuid>, _>("created_by").map_err(|e| e.to_string())? else {
+            return Ok(None);
+        };
+        let api_key_id = row.try_get("created_by_api_key_id").map_err(|e| e.to_string())?;
+        let source: Option<String> = row.try_get("created_by_source").map_err(|e| e.to_string())?;
+        Actor::from_repository(user_id, api_key_id, source.as_deref()).map(Some)
+    }
+
+    /// Tags of the given prompts keyed by prompt id.
+    async fn fetch_tags(&self, prompt_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Tag>>, String> {
         let rows = sqlx::query(
-            "SELECT id, version_id, rating, comment, test_input, test_actual_output, test_expected_output, created_at
-             FROM feedbacks WHERE version_id = $1 ORDER BY created_at"        )
-            .bind(version_id)
+            "SELECT id, prompt_id, version_id, name, updated_at
+             FROM tags WHERE prompt_id = ANY($1)"        )
+            .bind(prompt_ids)
             .fetch_all(&self.pool)
             .await
-            .map_err(|e| format!("Failed to fetch feedbacks: {}", e))?;
+            .map_err(|e| format!("Failed to fetch tags: {}", e))?;
 
-        rows.iter()
-            .map(|row| {
-                let id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
-                let version_id: Uuid = row.try_get("version_id").map_err(|e| e.to_string())?;
-
-                let test_scenario = match (
-                    row.try_get::<Option<String>, _>("test_input").map_err(|e| e.to_string())?,
-                    row.try_get::<Option<String>, _>("test_actual_output").map_err(|e| e.to_string())?,
-                ) {
-                    (Some(input), Some(actual_output)) => {
-                        let expected_output = row.try_get("test_expected_output").map_err(|e| e.to_string())?;
-                        Some(TestScenario::new(input, actual_output, expected_output)?)
-                    }
-                    _ => None,
-                };
-
-                Feedback::new(
-                    id,
-                    version_id,
-                    row.try_get::<i16, _>("rating").map_err(|e| e.to_string())? as u8,
-                    row.try_get("comment").map_err(|e| e.to_string())?,
-                    test_scenario,
-                )
-            })
-            .collect()
+        let mut tags: HashMap<Uuid, Vec<Tag>> = HashMap::new();
+        for row in rows {
+            let id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
+            let prompt_id: Uuid = row.try_get("prompt_id").map_err(|e| e.to_string())?;
+            let version_id: Uuid = row.try_get("version_id").map_err(|e| e.to_string())?;
+
+            tags.entry(prompt_id).or_default().push(Tag::new(
+                id,
+                prompt_id,
+                version_id,
+                row.try_get("name").map_err(|e| e.to_string())?,
+            ));
+        }
+
+        Ok(tags)
     }
 
-    async fn fetch_improvement_suggestions(&self, version_id: Uuid) -> Result<Vec<ImprovementSuggestion>, String> {
+    /// Feedback on every version of the given prompts keyed by version id.
+    async fn fetch_feedbacks(&self, prompt_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Feedback>>, String> {
         let rows = sqlx::query(
-            "SELECT id, source_version_id, suggested_content, ai_rationale, status, decline_reason, created_at, resolved_at, resulting_version_id
-         FROM improvement_suggestions WHERE source_version_id = $1 ORDER BY created_at"        )
-            .bind(version_id)
+            "SELECT f.id, f.version_id, f.rating, f.comment, f.test_input, f.test_actual_output, f.test_expected_output, f.test_schema_violations, f.created_at,
+                    f.created_by, f.created_by_api_key_id, f.created_by_source,
+                    f.model, f.latency_ms, f.input_tokens, f.output_tokens, f.cost_usd, f.render_context, f.trace_id, f.metadata, f.render_id
+             FROM feedbacks f
+             INNER JOIN versions v ON v.id = f.version_id
+             WHERE v.prompt_id = ANY($1)
+             ORDER BY f.created_at"        )
+            .bind(prompt_ids)
             .fetch_all(&self.pool)
             .await
-            .map_err(|e| format!("Failed to fetch improvement suggestions: {}", e))?;
+            .map_err(|e| format!("Failed to fetch feedbacks: {}", e))?;
 
-        rows.iter()
-            .map(|row| {
-                let id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
-                let source_version_id: Uuid = row.try_get("source_version_id").map_err(|e| e.to_string())?;
-                let suggested_content: String = row.try_get("suggested_content").map_err(|e| e.to_string())?;
-                let ai_rationale: String = row.try_get("ai_rationale").map_err(|e| e.to_string())?;
-
-                let status_str: String = row.try_get("status").map_err(|e| e.to_string())?;
-                let status = match status_str.as_str() {
-                    "pending" => SuggestionStatus::Pending,
-                    "accepted" => SuggestionStatus::Accepted,
-                    "declined" => SuggestionStatus::Declined,
-                    _ => return Err("Invalid suggestion status".to_string()),
-                };
-
-                let mut suggestion = ImprovementSuggestion::new(
-                    id,
-                    source_version_id,
-                    suggested_content,
-                    ai_rationale,
-                );
-
-                if status == SuggestionStatus::Accepted {
-                    let resulting_version_id: Option<Uuid> = row.try_get("resulting_version_id")
-                        .map_err(|e| e.to_string())?;
-                    let resulting_version_id = resulting_version_id
-                        .ok_or("Accepted suggestion missing resulting_version_id")?;
-                    suggestion.accept(resulting_version_id)?;
-                } else if status == SuggestionStatus::Declined {
-                    let reason: Option<String> = row.try_get("decline_reason")
-                        .map_err(|e| e.to_string())?;
-                    let reason = reason.ok_or("Declined suggestion missing reason")?;
-                    suggestion.decline(reason)?;
+        let mut feedbacks: HashMap<Uuid, Vec<Feedback>> = HashMap::new();
+        for row in rows {
+            let id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
+            let version_id: Uuid = row.try_get("version_id").map_err(|e| e.to_string())?;
+
+            let test_scenario = match (
+                row.try_get::<Option<String>, _>("test_input").map_err(|e| e.to_string())?,
+                row.try_get::<Option<String>, _>("test_actual_output").map_err(|e| e.to_string())?,
+            ) {
+                (Some(input), Some(actual_output)) => {
+                    let expected_output = row.try_get("test_expected_output").map_err(|e| e.to_string())?;
+                    let schema_violations = row.try_get::<Option<sqlx::types::Json<Vec<String>>>, _>("test_schema_violations")
+                        .map_err(|e| e.to_string())?
+                        .map(|j| j.0);
+                    Some(TestScenario::from_repository(input, actual_output, expected_output, schema_violations))
                 }
+                _ => None,
+            };
 
-                Ok(suggestion)
-            })
-            .collect()
+            feedbacks.entry(version_id).or_default().push(Feedback::from_repository(FeedbackRecord {
+                id,
+                version_id,
+                rating: row.try_get::<i16, _>("rating").map_err(|e| e.to_string())? as u8,
+                comment: row.try_get("comment").map_err(|e| e.to_string())?,
+                test_scenario,
+                call_details: Self::read_call_details(&row)?,
+                created_by: Self::read_author(&row)?,
+                created_at: row.try_get("created_at").map_err(|e| e.to_string())?,
+            }));
+        }
+
+        Ok(feedbacks)
     }
 
-    async fn save_versions(&self, prompt_id: Uuid, versions: &[PromptVersion]) -> Result<(), String> {
-        sqlx::query("DELETE FROM versions WHERE prompt_id = $1")
-            .bind(prompt_id)
-            .execute(&self.pool)
+    /// Improvement suggestions for every version of the given prompts keyed by source version id.
+    async fn fetch_improvement_suggestions(&self, prompt_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<ImprovementSuggestion>>, String> {
+        let rows = sqlx::query(
+            "SELECT s.id, s.source_version_id, s.suggested_content, s.ai_rationale, s.status, s.decline_reason, s.created_at, s.resolved_at, s.resulting_version_id,
+                s.created_by, s.created_by_api_key_id, s.created_by_source
+         FROM improvement_suggestions s
+         INNER JOIN versions v ON v.id = s.source_version_id
+         WHERE v.prompt_id = ANY($1)
+         ORDER BY s.created_at"        )
+            .bind(prompt_ids)
+            .fetch_all(&self.pool)
             .await
-            .map_err(|e| format!("Failed to delete versions: {}", e))?;
+            .map_err(|e| format!("Failed to fetch improvement suggestions: {}", e))?;
 
-        // STEP 1: Save ALL versions first
-        for version in versions {
-            let content_type_str = match version.content_type() {
-                ContentType::Static => "static",
-                ContentType::Template => "template",
+        let mut suggestions: HashMap<Uuid, Vec<ImprovementSuggestion>> = HashMap::new();
+        for row in rows {
+            let id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
+            let source_version_id: Uuid = row.try_get("source_version_id").map_err(|e| e.to_string())?;
+            let suggested_content: String = row.try_get("suggested_content").map_err(|e| e.to_string())?;
+            let ai_rationale: String = row.try_get("ai_rationale").map_err(|e| e.to_string())?;
+
+            let status_str: String = row.try_get("status").map_err(|e| e.to_string())?;
+            let status = match status_str.as_str() {
+                "pending" => SuggestionStatus::Pending,
+                "accepted" => SuggestionStatus::Accepted,
+                "declined" => SuggestionStatus::Declined,
+                _ => return Err("Invalid suggestion status".to_string()),
             };
 
-            let variables_json = version.variables().map(|v| sqlx::types::Json(v.to_vec()));
+            let mut suggestion = ImprovementSuggestion::new(
+                id,
+                source_version_id,
+                suggested_content,
+                ai_rationale,
+                Self::read_author(&row)?,
+            );
 
-            sqlx::query(
-                "INSERT INTO versions (id, prompt_id, version, digest, content, content_type, variables, changelog, created_at)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"            )
-                .bind(version.id())
-                .bind(version.prompt_id())
-                .bind(version.version_string())
-                .bind(version.digest())
-                .bind(version.content())
-                .bind(content_type_str)
-                .bind(variables_json)
-                .bind(version.changelog())
-                .bind(version.created_at())
-                .execute(&self.pool)
-                .await
-                .map_err(|e| format!("Failed to save version: {}", e))?;
-        }
+            if status == SuggestionStatus::Accepted {
+                let resulting_version_id: Option<Uuid> = row.try_get("resulting_version_id")
+                    .map_err(|e| e.to_string())?;
+                let resulting_version_id = resulting_version_id
+                    .ok_or("Accepted suggestion missing resulting_version_id")?;
+                suggestion.accept(resulting_version_id)?;
+            } else if status == SuggestionStatus::Declined {
+                let reason: Option<String> = row.try_get("decline_reason")
+                    .map_err(|e| e.to_string())?;
+                let reason = reason.ok_or("Declined suggestion missing reason")?;
+                suggestion.decline(reason)?;
+            }
 
-        // STEP 2: Save all feedbacks (now versions exist)
-        for version in versions {
-            self.save_feedbacks(version.id(), version.feedbacks()).await?;
+            suggestions.entry(source_version_id).or_default().push(suggestion);
         }
 
-        // STEP 3: Save all improvement suggestions (now ALL versions exist, including resulting_version_id)
-        for version in versions {
-            self.save_improvement_suggestions(version.id(), version.improvement_suggestions()).await?;
-        }
+        Ok(suggestions)
+    }
+
+    async fn insert_version(conn: &mut PgConnection, version: &PromptVersion) -> Result<(), String> {
+        let content_type_str = match version.content_type() {
+            ContentType::Static => "static",
+            ContentType::Template => "template",
+        };
+
+        let variables_json = version.variables().map(|v| sqlx::types::Json(v.to_vec()));
+        let model_config = version.model_config();
+        let tools_json = (!version.tools().is_empty()).then(|| {
+            sqlx::types::Json(version.tools().iter().map(|tool| serde_json::json!({
+                "name": tool.name(),
+                "description": tool.description(),
+                "parameters": tool.parameters(),
+            })).collect::<Vec<_>>())
+        });
+        let output_schema_json = version.output_schema().map(|schema| sqlx::types::Json(serde_json::json!({
+            "name": schema.name(),
+            "schema": schema.schema(),
+        })));
+
+        sqlx::query(
+            "INSERT INTO versions (id, prompt_id, version, digest, content, content_type, variables, changelog, created_at,
+                                   model_provider, model_name, temperature, top_p, max_tokens, stop_sequences, seed,
+                                   tools, output_schema, token_count, token_encoding,
+                                   created_by, created_by_api_key_id, created_by_source)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)
+         ON CONFLICT (id) DO UPDATE SET token_count = EXCLUDED.token_count, token_encoding = EXCLUDED.token_encoding"        )
+            .bind(version.id())
+            .bind(version.prompt_id())
+            .bind(version.version_string())
+            .bind(version.digest())
+            .bind(version.content())
+            .bind(content_type_str)
+            .bind(variables_json)
+            .bind(version.changelog())
+            .bind(version.created_at())
+            .bind(model_config.map(|c| c.provider().as_str()))
+            .bind(model_config.map(|c| c.model()))
+            .bind(model_config.and_then(|c| c.temperature()))
+            .bind(model_config.and_then(|c| c.top_p()))
+            .bind(model_config.and_then(|c| c.max_tokens()).map(|t| t as i32))
+            .bind(model_config.map(|c| sqlx::types::Json(c.stop_sequences().to_vec())))
+            .bind(model_config.and_then(|c| c.seed()))
+            .bind(tools_json)
+            .bind(output_schema_json)
+            .bind(version.token_count().map(|t| t.tokens() as i32))
+            .bind(version.token_count().map(|t| t.encoding()))
+            .bind(version.created_by().map(|a| a.user_id))
+            .bind(version.created_by().and_then(|a| a.api_key_id))
+            .bind(version.created_by().map(|a| a.source.as_str()))
+            .execute(conn)
+            .await
+            .map_err(|e| format!("Failed to save version: {}", e))?;
 
         Ok(())
     }
 
-    async fn save_tags(&self, prompt_id: Uuid, tags: &[Tag]) -> Result<(), String> {
-        sqlx::query("DELETE FROM tags WHERE prompt_id = $1")
-            .bind(prompt_id)
-            .execute(&self.pool)
+    async fn upsert_tag(conn: &mut PgConnection, tag: &Tag) -> Result<(), String> {
+        sqlx::query(
+            "INSERT INTO tags (id, prompt_id, version_id, name, updated_at)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (id) DO UPDATE SET version_id = EXCLUDED.version_id, updated_at = EXCLUDED.updated_at"        )
+            .bind(tag.id())
+            .bind(tag.prompt_id())
+            .bind(tag.version_id())
+            .bind(tag.name())
+            .bind(tag.updated_at())
+            .execute(conn)
             .await
-            .map_err(|e| format!("Failed to delete tags: {}", e))?;
+            .map_err(|e| format!("Failed to save tag: {}", e))?;
 
-        for tag in tags {
-            sqlx::query(
-                "INSERT INTO tags (id, prompt_id, version_id, name, updated_at)
-             VALUES ($1, $2, $3, $4, $5)"            )
-                .bind(tag.id())
-                .bind(tag.prompt_id())
-                .bind(tag.version_id())
-                .bind(tag.name())
-                .bind(tag.updated_at())
-                .execute(&self.pool)
-                .await
-                .map_err(|e| format!("Failed to save tag: {}", e))?;
-        }
         Ok(())
     }
 
-    async fn save_feedbacks(&self, version_id: Uuid, feedbacks: &[Feedback]) -> Result<(), String> {
-        sqlx::query("DELETE FROM feedbacks WHERE version_id = $1")
+    async fn upsert_feedback(conn: &mut PgConnection, version_id: Uuid, feedback: &Feedback) -> Result<(), String> {
+        let (test_input, test_actual_output, test_expected_output, test_schema_violations) =
+            if let Some(scenario) = feedback.test_scenario() {
+                (
+                    Some(scenario.input()),
+                    Some(scenario.actual_output()),
+                    scenario.expected_output(),
+                    scenario.schema_violations(),
+                )
+            } else {
+                (None, None, None, None)
+            };
+        let call_details = feedback.call_details();
+
+        sqlx::query(
+            "INSERT INTO feedbacks (id, version_id, rating, comment, test_input, test_actual_output, test_expected_output, created_at,
+                                    created_by, created_by_api_key_id, created_by_source,
+                                    model, latency_ms, input_tokens, output_tokens, cost_usd, render_context, trace_id, metadata, render_id,
+                                    test_schema_violations)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21)
+             ON CONFLICT (id) DO UPDATE SET
+                rating = EXCLUDED.rating,
+                comment = EXCLUDED.comment,
+                test_input = EXCLUDED.test_input,
+                test_actual_output = EXCLUDED.test_actual_output,
+                test_expected_output = EXCLUDED.test_expected_output,
+                test_schema_violations = EXCLUDED.test_schema_violations"        )
+            .bind(feedback.id())
             .bind(version_id)
-            .execute(&self.pool)
+            .bind(feedback.rating() as i16)
+            .bind(feedback.comment())
+            .bind(test_input)
+            .bind(test_actual_output)
+            .bind(test_expected_output)
+            .bind(feedback.created_at())
+            .bind(feedback.created_by().map(|a| a.user_id))
+            .bind(feedback.created_by().and_then(|a| a.api_key_id))
+            .bind(feedback.created_by().map(|a| a.source.as_str()))
+            .bind(call_details.and_then(|d| d.model()))
+            .bind(call_details.and_then(|d| d.latency_ms()).map(|v| v as i64))
+            .bind(call_details.and_then(|d| d.input_tokens()).map(|v| v as i32))
+            .bind(call_details.and_then(|d| d.output_tokens()).map(|v| v as i32))
+            .bind(call_details.and_then(|d| d.cost_usd()))
+            .bind(call_details.and_then(|d| d.render_context()).map(sqlx::types::Json))
+            .bind(call_details.and_then(|d| d.trace_id()))
+            .bind(call_details.and_then(|d| d.metadata()).map(sqlx::types::Json))
+            .bind(call_details.and_then(|d| d.render_id()))
+            .bind(test_schema_violations.map(sqlx::types::Json))
+            .execute(conn)
             .await
-            .map_err(|e| format!("Failed to delete feedbacks: {}", e))?;
-
-        for feedback in feedbacks {
-            let (test_input, test_actual_output, test_expected_output) =
-                if let Some(scenario) = feedback.test_scenario() {
-                    (
-                        Some(scenario.input()),
-                        Some(scenario.actual_output()),
-                        scenario.expected_output(),
-                    )
-                } else {
-                    (None, None, None)
-                };
+            .map_err(|e| format!("Failed to save feedback: {}", e))?;
 
-            sqlx::query(
-                "INSERT INTO feedbacks (id, version_id, rating, comment, test_input, test_actual_output, test_expected_output, created_at)
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"            )
-                .bind(feedback.id())
-                .bind(version_id)
-                .bind(feedback.rating() as i16)
-                .bind(feedback.comment())
-                .bind(test_input)
-                .bind(test_actual_output)
-                .bind(test_expected_output)
-                .bind(feedback.created_at())
-                .execute(&self.pool)
-                .await
-                .map_err(|e| format!("Failed to save feedback: {}", e))?;
-        }
         Ok(())
     }
 
-    async fn save_improvement_suggestions(&self, version_id: Uuid, suggestions: &[ImprovementSuggestion]) -> Result<(), String> {
-        sqlx::query("DELETE FROM improvement_suggestions WHERE source_version_id = $1")
-            .bind(version_id)
-            .execute(&self.pool)
+    async fn upsert_improvement_suggestion(conn: &mut PgConnection, suggestion: &ImprovementSuggestion) -> Result<(), String> {
+        let status_str = match suggestion.status() {
+            SuggestionStatus::Pending => "pending",
+            SuggestionStatus::Accepted => "accepted",
+            SuggestionStatus::Declined => "declined",
+        };
+
+        sqlx::query(
+            "INSERT INTO improvement_suggestions (id, source_version_id, suggested_content, ai_rationale, status, decline_reason, created_at, resolved_at, resulting_version_id,
+                                          created_by, created_by_api_key_id, created_by_source)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+         ON CONFLICT (id) DO UPDATE SET
+            status = EXCLUDED.status,
+            decline_reason = EXCLUDED.decline_reason,
+            resolved_at = EXCLUDED.resolved_at,
+            resulting_version_id = EXCLUDED.resulting_version_id"        )
+            .bind(suggestion.id())
+            .bind(suggestion.source_version_id())
+            .bind(suggestion.suggested_content())
+            .bind(suggestion.ai_rationale())
+            .bind(status_str)
+            .bind(suggestion.decline_reason())
+            .bind(suggestion.created_at())
+            .bind(suggestion.resolved_at())
+            .bind(suggestion.resulting_version_id())
+            .bind(suggestion.created_by().map(|a| a.user_id))
+            .bind(suggestion.created_by().and_then(|a| a.api_key_id))
+            .bind(suggestion.created_by().map(|a| a.source.as_str()))
+            .execute(conn)
             .await
-            .map_err(|e| format!("Failed to delete improvement suggestions: {}", e))?;
+            .map_err(|e| format!("Failed to save improvement suggestion: {}", e))?;
 
-        for suggestion in suggestions {
-            let status_str = match suggestion.status() {
-                SuggestionStatus::Pending => "pending",
-                SuggestionStatus::Accepted => "accepted",
-                SuggestionStatus::Declined => "declined",
-            };
+        Ok(())
+    }
+
+    /// Inserts or updates the prompt row and bumps its revision, returning
+    /// the stored revision. The bump is guarded by `expected_revision` when
+    /// the caller sent one, and otherwise by the revision the prompt was
+    /// loaded at unless the save only appends feedback or suggestions; a
+    /// moved revision fails with `PreconditionFailed` or `Conflict`
+    /// respectively. Name, description and token budget are only written
+    /// when they changed, and `updated_at` only when versions or tags did.
+    async fn write_prompt_row(conn: &mut PgConnection, prompt: &Prompt, expected_revision: Option<u64>) -> Result<u64, AppError> {
+        let prompt_type_str = match prompt.prompt_type() {
+            PromptType::System => "system",
+            PromptType::User => "user",
+        };
 
+        if prompt.revision() == 0 {
             sqlx::query(
-                "INSERT INTO improvement_suggestions (id, source_version_id, suggested_content, ai_rationale, status, decline_reason, created_at, resolved_at, resulting_version_id)
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)"            )
-                .bind(suggestion.id())
-                .bind(suggestion.source_version_id())
-                .bind(suggestion.suggested_content())
-                .bind(suggestion.ai_rationale())
-                .bind(status_str)
-                .bind(suggestion.decline_reason())
-                .bind(suggestion.created_at())
-                .bind(suggestion.resolved_at())
-                .bind(suggestion.resulting_version_id())
-                .execute(&self.pool)
+                "INSERT INTO prompts (id, user_id, name, description, prompt_type, max_render_tokens, created_at, updated_at, revision)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 1)"            )
+                .bind(prompt.id())
+                .bind(prompt.user_id())
+                .bind(prompt.name())
+                .bind(prompt.description())
+                .bind(prompt_type_str)
+                .bind(prompt.max_render_tokens().map(|m| m as i32))
+                .bind(prompt.created_at())
+                .bind(prompt.updated_at())
+                .execute(conn)
                 .await
-                .map_err(|e| format!("Failed to save improvement suggestion: {}", e))?;
+                .map_err(|e| format!("Failed to save prompt: {}", e))?;
+
+            return Ok(1);
         }
-        Ok(())
+
+        let changes = prompt.pending_changes();
+        let guard = expected_revision.or((!changes.is_append_only()).then_some(prompt.revision()));
+        let query = if changes.prompt() {
+            sqlx::query(
+                "UPDATE prompts
+             SET name = $3, description = $4, prompt_type = $5, max_render_tokens = $6, updated_at = $7, revision = revision + 1
+             WHERE id = $1 AND ($2 IS NULL OR revision = $2)
+             RETURNING revision"        )
+                .bind(prompt.id())
+                .bind(guard.map(|r| r as i64))
+                .bind(prompt.name())
+                .bind(prompt.description())
+                .bind(prompt_type_str)
+                .bind(prompt.max_render_tokens().map(|m| m as i32))
+                .bind(prompt.updated_at())
+        } else {
+            sqlx::query(
+                "UPDATE prompts SET updated_at = COALESCE($3, updated_at), revision = revision + 1
+             WHERE id = $1 AND ($2 IS NULL OR revision = $2)
+             RETURNING revision"        )
+                .bind(prompt.id())
+                .bind(guard.map(|r| r as i64))
+                .bind(changes.touched().then(|| prompt.updated_at()))
+        };
+
+        let revision: Option<i64> = query
+            .fetch_optional(conn)
+            .await
+            .map_err(|e| format!("Failed to save prompt: {}", e))?
+            .map(|row| row.try_get("revision"))
+            .transpose()
+            .map_err(|e| format!("Failed to save prompt: {}", e))?;
+
+        match revision {
+            Some(revision) => Ok(revision as u64),
+            None if expected_revision.is_some() => Err(AppError::PreconditionFailed(ETAG_MISMATCH.to_string())),
+            None if guard.is_some() => Err(AppError::Conflict(REVISION_CONFLICT.to_string())),
+            None => Err(AppError::not_found("Prompt not found")),
+        }
+    }
+
+    /// Writes the prompt row and the rows listed in its change set. Deletes
+    /// run before inserts so a tag removed and re-created under the same name
+    /// does not collide, and versions are inserted before the feedback,
+    /// suggestions and tags that reference them.
+    async fn write_changes(conn: &mut PgConnection, prompt: &Prompt, expected_revision: Option<u64>) -> Result<u64, AppError> {
+        let changes = prompt.pending_changes();
+
+        // Written first so the row lock serialises concurrent saves of the
+        // same prompt; a guarded loser sees a moved revision and rolls back.
+        let revision = Self::write_prompt_row(&mut *conn, prompt, expected_revision).await?;
+
+        if !changes.deleted_feedbacks().is_empty() {
+            sqlx::query("DELETE FROM feedbacks WHERE id = ANY($1)")
+                .bind(changes.deleted_feedbacks())
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to delete feedbacks: {}", e))?;
+        }
+
+        if !changes.deleted_tags().is_empty() {
+            sqlx::query("DELETE FROM tags WHERE id = ANY($1)")
+                .bind(changes.deleted_tags())
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to delete tags: {}", e))?;
+        }
+
+        if !changes.deleted_versions().is_empty() {
+            sqlx::query("DELETE FROM versions WHERE id = ANY($1) AND prompt_id = $2")
+                .bind(changes.deleted_versions())
+                .bind(prompt.id())
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to delete versions: {}", e))?;
+        }
+
+        for version_id in changes.versions() {
+            if let Some(version) = prompt.find_version_by_id(*version_id) {
+                Self::insert_version(conn, version).await?;
+            }
+        }
+
+        for (version_id, feedback_id) in changes.feedbacks() {
+            let feedback = prompt.find_version_by_id(*version_id)
+                .and_then(|v| v.feedbacks().iter().find(|f| f.id() == *feedback_id));
+            if let Some(feedback) = feedback {
+                Self::upsert_feedback(conn, *version_id, feedback).await?;
+            }
+        }
+
+        for (version_id, suggestion_id) in changes.suggestions() {
+            let suggestion = prompt.find_version_by_id(*version_id)
+                .and_then(|v| v.find_suggestion(*suggestion_id));
+            if let Some(suggestion) = suggestion {
+                Self::upsert_improvement_suggestion(conn, suggestion).await?;
+            }
+        }
+
+        for tag_id in changes.tags() {
+            if let Some(tag) = prompt.tags().iter().find(|t| t.id() == *tag_id) {
+                Self::upsert_tag(conn, tag).await?;
+            }
+        }
+
+        Ok(revision)
     }
 
-    async fn build_prompt(&self, row: &sqlx::postgres::PgRow) -> Result<Prompt, String> {
+    fn build_prompt(row: &PgRow, versions: Vec<PromptVersion>, tags: Vec<Tag>) -> Result<Prompt, String> {
         let prompt_id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
         let user_id: Uuid = row.try_get("user_id").map_err(|e| e.to_string())?;
 
@@ -333,148 +723,141 @@ impl PostgresPromptRepository {
             _ => return Err("Invalid prompt_type".to_string()),
         };
 
-        let versions = self.fetch_versions(prompt_id).await?;
-        let tags = self.fetch_tags(prompt_id).await?;
-
-        let mut prompt = Prompt::new(
-            prompt_id,
+        Ok(Prompt::from_repository(PromptRecord {
+            id: prompt_id,
             user_id,
-            row.try_get("name").map_err(|e| e.to_string())?,
-            row.try_get("description").map_err(|e| e.to_string())?,
+            name: row.try_get("name").map_err(|e| e.to_string())?,
+            description: row.try_get("description").map_err(|e| e.to_string())?,
             prompt_type,
-        );
-
-        for version in versions {
-            prompt.versions_mut().push(version);
-        }
-
-        for tag in tags {
-            prompt.tags_mut().push(tag);
-        }
-
-        Ok(prompt)
+            max_render_tokens: row.try_get::<Option<i32>, _>("max_render_tokens").map_err(|e| e.to_string())?.map(|m| m as u32),
+            created_at: row.try_get("created_at").map_err(|e| e.to_string())?,
+            updated_at: row.try_get("updated_at").map_err(|e| e.to_string())?,
+            revision: row.try_get::<i64, _>("revision").map_err(|e| e.to_string())? as u64,
+            versions,
+            tags,
+        }))
     }
 }
 
 #[async_trait]
 impl PromptRepository for PostgresPromptRepository {
-    async fn save(&self, prompt: &Prompt) -> Result<(), String> {
-        let prompt_type_str = match prompt.prompt_type() {
-            PromptType::System => "system",
-            PromptType::User => "user",
-        };
+    #[instrument(name = "db.prompts.save", skip_all, fields(db.system = "postgresql", prompt_id = %prompt.id()))]
+    async fn save(&self, prompt: &Prompt, audit: &[AuditEntry], expected_revision: Option<u64>) -> Result<u64, AppError> {
+        if prompt.pending_changes().is_empty() {
+            return Ok(prompt.revision());
+        }
 
-        sqlx::query(
-            "INSERT INTO prompts (id, user_id, name, description, prompt_type, created_at, updated_at)
-         VALUES ($1, $2, $3, $4, $5, $6, $7)         ON CONFLICT (id) DO UPDATE SET         name = EXCLUDED.name,         description = EXCLUDED.description,         prompt_type = EXCLUDED.prompt_type,         updated_at = EXCLUDED.updated_at"        )
-            .bind(prompt.id())
-            .bind(prompt.user_id())
-            .bind(prompt.name())
-            .bind(prompt.description())
-            .bind(prompt_type_str)
-            .bind(prompt.created_at())
-            .bind(prompt.updated_at())
-            .execute(&self.pool)
+        let mut tx = self.pool
+            .begin()
             .await
-            .map_err(|e| format!("Failed to save prompt: {}", e))?;
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
 
-        self.save_versions(prompt.id(), prompt.versions()).await?;
-        self.save_tags(prompt.id(), prompt.tags()).await?;
+        let revision = Self::write_changes(&mut tx, prompt, expected_revision).await?;
+        insert_audit_entries(&mut tx, audit).await?;
+        enqueue_event_deliveries(&mut tx, prompt.pending_events()).await?;
+        Self::notify_change(&mut tx, prompt.id(), prompt.user_id(), PromptChangeKind::Saved).await?;
+        Self::publish_events(&mut tx, prompt).await?;
 
-        Ok(())
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit prompt: {}", e))?;
+
+        Ok(revision)
     }
 
+    #[instrument(name = "db.prompts.find_by_id", skip_all, fields(db.system = "postgresql", %id))]
     async fn find_by_id(&self, id: Uuid) -> Result<Option<Prompt>, String> {
-        let row = sqlx::query(
-            "SELECT id, user_id, name, description, prompt_type, created_at, updated_at
-             FROM prompts WHERE id = $1"        )
+        let row = sqlx::query(&format!("SELECT {} FROM prompts WHERE id = $1", PROMPT_COLUMNS))
             .bind(id)
             .fetch_optional(&self.pool)
             .await
             .map_err(|e| format!("Failed to find prompt: {}", e))?;
 
-        match row {
-            Some(row) => Ok(Some(self.build_prompt(&row).await?)),
-            None => Ok(None),
-        }
+        self.load_prompt(row, Hydration::Full).await
     }
 
+    #[instrument(name = "db.prompts.find_by_id_and_user", skip_all, fields(db.system = "postgresql", %id))]
     async fn find_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String> {
-        let row = sqlx::query(
-            "SELECT id, user_id, name, description, prompt_type, created_at, updated_at
-             FROM prompts WHERE id = $1 AND user_id = $2"        )
-            .bind(id)
+        let row = self.fetch_prompt_row(id, user_id).await?;
+        self.load_prompt(row, Hydration::Full).await
+    }
+
+    #[instrument(name = "db.prompts.find_content_by_id_and_user", skip_all, fields(db.system = "postgresql", %id))]
+    async fn find_content_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String> {
+        let row = self.fetch_prompt_row(id, user_id).await?;
+        self.load_prompt(row, Hydration::Content).await
+    }
+
+    #[instrument(name = "db.prompts.find_content_by_ids", skip_all, fields(db.system = "postgresql", count = ids.len()))]
+    async fn find_content_by_ids(&self, ids: &[Uuid], user_id: Uuid) -> Result<Vec<Prompt>, String> {
+        let rows = sqlx::query(&format!("SELECT {} FROM prompts WHERE id = ANY($1) AND user_id = $2", PROMPT_COLUMNS))
+            .bind(ids)
             .bind(user_id)
-            .fetch_optional(&self.pool)
+            .fetch_all(&self.pool)
             .await
-            .map_err(|e| format!("Failed to find prompt: {}", e))?;
+            .map_err(|e| format!("Failed to fetch prompts: {}", e))?;
 
-        match row {
-            Some(row) => Ok(Some(self.build_prompt(&row).await?)),
-            None => Ok(None),
-        }
+        self.load_prompts(rows, Hydration::Content).await
     }
 
+    #[instrument(name = "db.prompts.find_all", skip_all, fields(db.system = "postgresql"))]
     async fn find_all(&self) -> Result<Vec<Prompt>, String> {
-        let rows = sqlx::query(
-            "SELECT id, user_id, name, description, prompt_type, created_at, updated_at
-             FROM prompts ORDER BY created_at DESC"        )
+        let rows = sqlx::query(&format!("SELECT {} FROM prompts ORDER BY created_at DESC", PROMPT_COLUMNS))
             .fetch_all(&self.pool)
             .await
             .map_err(|e| format!("Failed to fetch prompts: {}", e))?;
 
-        let mut prompts = Vec::new();
-        for row in rows {
-            prompts.push(self.build_prompt(&row).await?);
-        }
-
-        Ok(prompts)
+        self.load_prompts(rows, Hydration::Full).await
     }
 
+    #[instrument(name = "db.prompts.find_by_user", skip_all, fields(db.system = "postgresql"))]
     async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Prompt>, String> {
-        let rows = sqlx::query(
-            "SELECT id, user_id, name, description, prompt_type, created_at, updated_at
-             FROM prompts WHERE user_id = $1 ORDER BY created_at DESC"        )
+        let rows = sqlx::query(&format!("SELECT {} FROM prompts WHERE user_id = $1 ORDER BY created_at DESC", PROMPT_COLUMNS))
             .bind(user_id)
             .fetch_all(&self.pool)
             .await
             .map_err(|e| format!("Failed to fetch prompts: {}", e))?;
 
-        let mut prompts = Vec::new();
-        for row in rows {
-            prompts.push(self.build_prompt(&row).await?);
-        }
-
-        Ok(prompts)
+        self.load_prompts(rows, Hydration::Full).await
     }
 
-    async fn find_by_tag(&self, tag_name: &str) -> Result<Vec<Prompt>, String> {
+    #[instrument(name = "db.prompts.find_content_by_tag", skip_all, fields(db.system = "postgresql"))]
+    async fn find_content_by_tag(&self, user_id: Uuid, tag_name: &str) -> Result<Vec<Prompt>, String> {
         let rows = sqlx::query(
-            "SELECT DISTINCT p.id, p.user_id, p.name, p.description, p.prompt_type, p.created_at, p.updated_at
-             FROM prompts p             INNER JOIN tags t ON p.id = t.prompt_id             WHERE t.name = $1             ORDER BY p.created_at DESC"        )
+            "SELECT p.id, p.user_id, p.name, p.description, p.prompt_type, p.max_render_tokens, p.created_at, p.updated_at, p.revision
+             FROM prompts p
+             INNER JOIN tags t ON p.id = t.prompt_id
+             WHERE p.user_id = $1 AND t.name = $2
+             ORDER BY p.created_at DESC"        )
+            .bind(user_id)
             .bind(tag_name)
             .fetch_all(&self.pool)
             .await
             .map_err(|e| format!("Failed to fetch prompts by tag: {}", e))?;
 
-        let mut prompts = Vec::new();
-        for row in rows {
-            prompts.push(self.build_prompt(&row).await?);
-        }
-
-        Ok(prompts)
+        self.load_prompts(rows, Hydration::Content).await
     }
 
-    async fn delete(&self, id: Uuid) -> Result<(), String> {
-        let result = sqlx::query("DELETE FROM prompts WHERE id = $1")
+    #[instrument(name = "db.prompts.delete", skip_all, fields(db.system = "postgresql", %id))]
+    async fn delete(&self, id: Uuid, audit: &[AuditEntry]) -> Result<(), String> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let row = sqlx::query("DELETE FROM prompts WHERE id = $1 RETURNING user_id")
             .bind(id)
-            .execute(&self.pool)
+            .fetch_optional(&mut *tx)
             .await
-            .map_err(|e| format!("Failed to delete prompt: {}", e))?;
+            .map_err(|e| format!("Failed to delete prompt: {}", e))?
+            .ok_or_else(|| "Prompt not found".to_string())?;
+        let user_id: Uuid = row.try_get("user_id").map_err(|e| e.to_string())?;
+        insert_audit_entries(&mut tx, audit).await?;
+        Self::notify_change(&mut tx, id, user_id, PromptChangeKind::Deleted).await?;
 
-        if result.rows_affected() == 0 {
-            return Err("Prompt not found".to_string());
-        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit prompt deletion: {}", e))?;
 
         Ok(())
     }
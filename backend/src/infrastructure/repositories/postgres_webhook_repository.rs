@@ -0,0 +1,317 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tracing::instrument;
+use sqlx::{PgConnection, PgPool, Row};
+use sqlx::postgres::PgRow;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::application::{AppError, WebhookRepository, CLAIM_LOST, event_payload};
+use crate::domain::audit::AuditEntry;
+use crate::domain::prompt::PromptEvent;
+use crate::domain::webhook::{Webhook, WebhookDelivery, WebhookDeliveryRecord, WebhookRecord, DeliveryStatus};
+use super::postgres_audit_repository::insert_audit_entries;
+
+const WEBHOOK_COLUMNS: &str = "id, user_id, prompt_id, url, secret, event_types, is_active, created_at";
+const DELIVERY_COLUMNS: &str = "id, webhook_id, event_id, event_type, payload, status, attempts, response_status, last_error, next_attempt_at, created_at, updated_at";
+
+pub struct PostgresWebhookRepository {
+    pool: PgPool,
+}
+
+impl PostgresWebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    fn read_webhook(row: &PgRow) -> Result<Webhook, String> {
+        let event_types: sqlx::types::Json<Vec<String>> = row.try_get("event_types").map_err(|e| e.to_string())?;
+        Ok(Webhook::from_repository(WebhookRecord {
+            id: row.try_get("id").map_err(|e| e.to_string())?,
+            user_id: row.try_get("user_id").map_err(|e| e.to_string())?,
+            prompt_id: row.try_get("prompt_id").map_err(|e| e.to_string())?,
+            url: row.try_get("url").map_err(|e| e.to_string())?,
+            secret: row.try_get("secret").map_err(|e| e.to_string())?,
+            event_types: event_types.0,
+            is_active: row.try_get("is_active").map_err(|e| e.to_string())?,
+            created_at: row.try_get("created_at").map_err(|e| e.to_string())?,
+        }))
+    }
+
+    fn read_delivery(row: &PgRow) -> Result<WebhookDelivery, String> {
+        let status: String = row.try_get("status").map_err(|e| e.to_string())?;
+        let attempts: i32 = row.try_get("attempts").map_err(|e| e.to_string())?;
+        let response_status: Option<i32> = row.try_get("response_status").map_err(|e| e.to_string())?;
+
+        Ok(WebhookDelivery::from_repository(WebhookDeliveryRecord {
+            id: row.try_get("id").map_err(|e| e.to_string())?,
+            webhook_id: row.try_get("webhook_id").map_err(|e| e.to_string())?,
+            event_id: row.try_get("event_id").map_err(|e| e.to_string())?,
+            event_type: row.try_get("event_type").map_err(|e| e.to_string())?,
+            payload: row.try_get("payload").map_err(|e| e.to_string())?,
+            status: DeliveryStatus::from_str(&status)?,
+            attempts: attempts as u32,
+            response_status: response_status.map(|s| s as u16),
+            last_error: row.try_get("last_error").map_err(|e| e.to_string())?,
+            next_attempt_at: row.try_get("next_attempt_at").map_err(|e| e.to_string())?,
+            created_at: row.try_get("created_at").map_err(|e| e.to_string())?,
+            updated_at: row.try_get("updated_at").map_err(|e| e.to_string())?,
+        }))
+    }
+}
+
+/// Queues a delivery of each of `events` to every webhook it matches. The
+/// prompt repository calls it inside the transaction that saves the change,
+/// so the delivery rows act as an outbox: they are stored exactly when the
+/// change is, whatever happens to the process or the notifications after.
+#[instrument(name = "db.webhooks.enqueue_deliveries", skip_all, fields(db.system = "postgresql", count = events.len()))]
+pub(super) async fn enqueue_event_deliveries(conn: &mut PgConnection, events: &[PromptEvent]) -> Result<(), String> {
+    let Some(first) = events.first() else {
+        return Ok(());
+    };
+
+    // Events recorded by one save all belong to the same prompt and user.
+    let rows = sqlx::query(&format!("SELECT {} FROM webhooks WHERE user_id = $1 AND is_active", WEBHOOK_COLUMNS))
+        .bind(first.user_id())
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| format!("Failed to load webhooks: {}", e))?;
+    let webhooks = rows.iter()
+        .map(PostgresWebhookRepository::read_webhook)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for event in events {
+        let payload = event_payload(event).to_string();
+        for webhook in webhooks.iter().filter(|w| w.matches(event)) {
+            let delivery = WebhookDelivery::new(
+                webhook.id(),
+                event.id(),
+                event.kind().as_str().to_string(),
+                payload.clone(),
+            );
+            sqlx::query(
+                "INSERT INTO webhook_deliveries (id, webhook_id, event_id, event_type, payload, status, attempts, next_attempt_at, created_at, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                ON CONFLICT (webhook_id, event_id) DO NOTHING"
+            )
+                .bind(delivery.id())
+                .bind(delivery.webhook_id())
+                .bind(delivery.event_id())
+                .bind(delivery.event_type())
+                .bind(delivery.payload())
+                .bind(delivery.status().as_str())
+                .bind(delivery.attempts() as i32)
+                .bind(delivery.next_attempt_at())
+                .bind(delivery.created_at())
+                .bind(delivery.updated_at())
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to enqueue webhook delivery: {}", e))?;
+        }
+    }
+
+    Ok(())
+}
+
+#[async_trait]
+impl WebhookRepository for PostgresWebhookRepository {
+    #[instrument(name = "db.webhooks.create", skip_all, fields(db.system = "postgresql", webhook_id = %webhook.id()))]
+    async fn create(&self, webhook: &Webhook, audit: &[AuditEntry]) -> Result<(), String> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        sqlx::query(
+            "INSERT INTO webhooks (id, user_id, prompt_id, url, secret, event_types, is_active, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
+        )
+            .bind(webhook.id())
+            .bind(webhook.user_id())
+            .bind(webhook.prompt_id())
+            .bind(webhook.url())
+            .bind(webhook.secret())
+            .bind(sqlx::types::Json(webhook.event_types()))
+            .bind(webhook.is_active())
+            .bind(webhook.created_at())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to create webhook: {}", e))?;
+
+        insert_audit_entries(&mut tx, audit).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit webhook: {}", e))?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "db.webhooks.find_by_id_and_user", skip_all, fields(db.system = "postgresql", %id))]
+    async fn find_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Webhook>, String> {
+        let row = sqlx::query(&format!("SELECT {} FROM webhooks WHERE id = $1 AND user_id = $2", WEBHOOK_COLUMNS))
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to find webhook: {}", e))?;
+
+        row.as_ref().map(Self::read_webhook).transpose()
+    }
+
+    #[instrument(name = "db.webhooks.find_by_user_id", skip_all, fields(db.system = "postgresql"))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<Webhook>, String> {
+        let rows = sqlx::query(&format!("SELECT {} FROM webhooks WHERE user_id = $1 ORDER BY created_at DESC", WEBHOOK_COLUMNS))
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to list webhooks: {}", e))?;
+
+        rows.iter().map(Self::read_webhook).collect()
+    }
+
+    #[instrument(name = "db.webhooks.delete", skip_all, fields(db.system = "postgresql", %id))]
+    async fn delete(&self, id: Uuid, user_id: Uuid, audit: &[AuditEntry]) -> Result<(), AppError> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let result = sqlx::query("DELETE FROM webhooks WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to delete webhook: {}", e))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found("Webhook not found"));
+        }
+
+        insert_audit_entries(&mut tx, audit).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit webhook deletion: {}", e))?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "db.webhooks.save_delivery", skip_all, fields(db.system = "postgresql", delivery_id = %delivery.id()))]
+    async fn save_delivery(&self, delivery: &WebhookDelivery, audit: &[AuditEntry], claimed_until: Option<DateTime<Utc>>) -> Result<(), AppError> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let result = sqlx::query(
+            "UPDATE webhook_deliveries
+            SET status = $2, attempts = $3, response_status = $4, last_error = $5, next_attempt_at = $6, updated_at = $7
+            WHERE id = $1 AND ($8::timestamptz IS NULL OR next_attempt_at = $8)"
+        )
+            .bind(delivery.id())
+            .bind(delivery.status().as_str())
+            .bind(delivery.attempts() as i32)
+            .bind(delivery.response_status().map(|s| s as i32))
+            .bind(delivery.last_error())
+            .bind(delivery.next_attempt_at())
+            .bind(delivery.updated_at())
+            .bind(claimed_until)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to save webhook delivery: {}", e))?;
+
+        if claimed_until.is_some() && result.rows_affected() == 0 {
+            return Err(AppError::Conflict(CLAIM_LOST.to_string()));
+        }
+
+        insert_audit_entries(&mut tx, audit).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit webhook delivery: {}", e))?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "db.webhooks.find_delivery", skip_all, fields(db.system = "postgresql", %id, %webhook_id))]
+    async fn find_delivery(&self, id: Uuid, webhook_id: Uuid) -> Result<Option<WebhookDelivery>, String> {
+        let row = sqlx::query(&format!("SELECT {} FROM webhook_deliveries WHERE id = $1 AND webhook_id = $2", DELIVERY_COLUMNS))
+            .bind(id)
+            .bind(webhook_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to find webhook delivery: {}", e))?;
+
+        row.as_ref().map(Self::read_delivery).transpose()
+    }
+
+    #[instrument(name = "db.webhooks.find_deliveries", skip_all, fields(db.system = "postgresql", %webhook_id))]
+    async fn find_deliveries(&self, webhook_id: Uuid, limit: i64) -> Result<Vec<WebhookDelivery>, String> {
+        let rows = sqlx::query(&format!(
+            "SELECT {} FROM webhook_deliveries WHERE webhook_id = $1 ORDER BY created_at DESC LIMIT $2",
+            DELIVERY_COLUMNS
+        ))
+            .bind(webhook_id)
+            .bind(limit)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to list webhook deliveries: {}", e))?;
+
+        rows.iter().map(Self::read_delivery).collect()
+    }
+
+    #[instrument(name = "db.webhooks.claim_due_deliveries", skip_all, level = "debug", fields(db.system = "postgresql"))]
+    async fn claim_due_deliveries(&self, limit: i64, lease: Duration) -> Result<Vec<(WebhookDelivery, Webhook)>, String> {
+        let rows = sqlx::query(
+            "UPDATE webhook_deliveries d
+            SET next_attempt_at = NOW() + make_interval(secs => $2)
+            WHERE d.id IN (
+                SELECT id FROM webhook_deliveries
+                WHERE status = 'pending' AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING d.id"
+        )
+            .bind(limit)
+            .bind(lease.as_secs_f64())
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to claim webhook deliveries: {}", e))?;
+
+        let ids: Vec<Uuid> = rows.iter().map(|r| r.get("id")).collect();
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let rows = sqlx::query(
+            "SELECT d.id, d.webhook_id, d.event_id, d.event_type, d.payload, d.status, d.attempts, d.response_status,
+                    d.last_error, d.next_attempt_at, d.created_at, d.updated_at,
+                    w.user_id, w.prompt_id, w.url, w.secret, w.event_types, w.is_active, w.created_at AS webhook_created_at
+            FROM webhook_deliveries d
+            INNER JOIN webhooks w ON w.id = d.webhook_id
+            WHERE d.id = ANY($1)"
+        )
+            .bind(&ids)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to load claimed webhook deliveries: {}", e))?;
+
+        rows.iter()
+            .map(|row| {
+                let delivery = Self::read_delivery(row)?;
+                let event_types: sqlx::types::Json<Vec<String>> = row.try_get("event_types").map_err(|e| e.to_string())?;
+                let webhook = Webhook::from_repository(WebhookRecord {
+                    id: delivery.webhook_id(),
+                    user_id: row.try_get("user_id").map_err(|e| e.to_string())?,
+                    prompt_id: row.try_get("prompt_id").map_err(|e| e.to_string())?,
+                    url: row.try_get("url").map_err(|e| e.to_string())?,
+                    secret: row.try_get("secret").map_err(|e| e.to_string())?,
+                    event_types: event_types.0,
+                    is_active: row.try_get("is_active").map_err(|e| e.to_string())?,
+                    created_at: row.try_get("webhook_created_at").map_err(|e| e.to_string())?,
+                });
+                Ok((delivery, webhook))
+            })
+            .collect()
+    }
+}
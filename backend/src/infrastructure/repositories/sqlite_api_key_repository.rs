@@ -0,0 +1,185 @@
+use async_trait::async_trait;
+use tracing::instrument;
+use sqlx::{SqlitePool, Row};
+use uuid::Uuid;
+use chrono::{Utc};
+
+use crate::application::AppError;
+use crate::application::api_key_repository::ApiKeyRepository;
+use crate::domain::audit::AuditEntry;
+use crate::domain::api_key::ApiKey;
+use super::sqlite_audit_repository::insert_audit_entries;
+
+pub struct SqliteApiKeyRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteApiKeyRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ApiKeyRepository for SqliteApiKeyRepository {
+    #[instrument(name = "db.api_keys.create", skip_all, fields(db.system = "sqlite"))]
+    async fn create(&self, api_key: &ApiKey, audit: &[AuditEntry]) -> Result<(), String> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        sqlx::query(
+            "INSERT INTO api_keys (id, user_id, name, key_hash, key_prefix, created_at, is_active)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)"
+        )
+            .bind(api_key.id())
+            .bind(api_key.user_id())
+            .bind(api_key.name())
+            .bind(api_key.key_hash())
+            .bind(api_key.key_prefix())
+            .bind(api_key.created_at())
+            .bind(api_key.is_active())
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to create API key: {}", e))?;
+
+        insert_audit_entries(&mut tx, audit).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit API key: {}", e))?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "db.api_keys.find_by_key_hash", skip_all, fields(db.system = "sqlite"))]
+    async fn find_by_key_hash(&self, key_hash: &str) -> Result<Option<ApiKey>, String> {
+        let record = sqlx::query(
+            "SELECT id, user_id, name, key_hash, key_prefix, last_used_at, created_at, is_active
+            FROM api_keys
+            WHERE key_hash = $1 AND is_active = true"
+        )
+            .bind(key_hash)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to find API key: {}", e))?;
+
+        Ok(record.map(|r| {
+            ApiKey::from_repository(
+                r.get("id"),
+                r.get("user_id"),
+                r.get("name"),
+                r.get("key_hash"),
+                r.get("key_prefix"),
+                r.get("last_used_at"),
+                r.get("created_at"),
+                r.get("is_active"),
+            )
+        }))
+    }
+
+    #[instrument(name = "db.api_keys.find_by_user_id", skip_all, fields(db.system = "sqlite"))]
+    async fn find_by_user_id(&self, user_id: Uuid) -> Result<Vec<ApiKey>, String> {
+        let records = sqlx::query(
+            "SELECT id, user_id, name, key_hash, key_prefix, last_used_at, created_at, is_active
+            FROM api_keys
+            WHERE user_id = $1
+            ORDER BY created_at DESC"
+        )
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to list API keys: {}", e))?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| {
+                ApiKey::from_repository(
+                    r.get("id"),
+                    r.get("user_id"),
+                    r.get("name"),
+                    r.get("key_hash"),
+                    r.get("key_prefix"),
+                    r.get("last_used_at"),
+                    r.get("created_at"),
+                    r.get("is_active"),
+                )
+            })
+            .collect())
+    }
+
+    #[instrument(name = "db.api_keys.update_last_used", skip_all, fields(db.system = "sqlite", %id))]
+    async fn update_last_used(&self, id: Uuid) -> Result<(), String> {
+        sqlx::query(
+            "UPDATE api_keys
+            SET last_used_at = $1
+            WHERE id = $2"
+        )
+            .bind(Utc::now())
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to update API key last used: {}", e))?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "db.api_keys.delete", skip_all, fields(db.system = "sqlite", %id))]
+    async fn delete(&self, id: Uuid, user_id: Uuid, audit: &[AuditEntry]) -> Result<(), AppError> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let result = sqlx::query(
+            "DELETE FROM api_keys
+            WHERE id = $1 AND user_id = $2"
+        )
+            .bind(id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to delete API key: {}", e))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found("API key not found or unauthorized"));
+        }
+
+        insert_audit_entries(&mut tx, audit).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit API key deletion: {}", e))?;
+
+        Ok(())
+    }
+
+    #[instrument(name = "db.api_keys.deactivate", skip_all, fields(db.system = "sqlite", %id))]
+    async fn deactivate(&self, id: Uuid, user_id: Uuid, audit: &[AuditEntry]) -> Result<(), AppError> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let result = sqlx::query(
+            "UPDATE api_keys
+            SET is_active = false
+            WHERE id = $1 AND user_id = $2"
+        )
+            .bind(id)
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to deactivate API key: {}", e))?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::not_found("API key not found or unauthorized"));
+        }
+
+        insert_audit_entries(&mut tx, audit).await?;
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit API key deactivation: {}", e))?;
+
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,880 @@
+use crate::application::{AppError, PromptRepository, PromptChange, PromptChangeKind, ETAG_MISMATCH, REVISION_CONFLICT};
+use crate::domain::prompt::{Prompt, PromptRecord, PromptVersion, VersionDraft, PromptEvent, Tag, Feedback, FeedbackRecord, TestScenario, CallDetails, CallDetailsDraft, Version, PromptType, ContentType, ImprovementSuggestion, SuggestionStatus, ModelConfig, ModelProvider, ToolDefinition, OutputSchema, TokenCount};
+use crate::domain::actor::Actor;
+use crate::domain::audit::AuditEntry;
+use super::sqlite_audit_repository::insert_audit_entries;
+use super::sqlite_webhook_repository::enqueue_event_deliveries;
+use async_trait::async_trait;
+use tracing::instrument;
+use sqlx::{QueryBuilder, Row, Sqlite, SqliteConnection, SqlitePool};
+use sqlx::sqlite::SqliteRow;
+use std::collections::HashMap;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+const PROMPT_COLUMNS: &str = "id, user_id, name, description, prompt_type, max_render_tokens, created_at, updated_at, revision";
+
+/// How much of each prompt's graph to load.
+#[derive(Clone, Copy)]
+enum Hydration {
+    /// Versions with their feedback and improvement suggestions, and tags.
+    Full,
+    /// Versions and tags only.
+    Content,
+}
+
+pub struct SqlitePromptRepository {
+    pool: SqlitePool,
+    changes: broadcast::Sender<PromptChange>,
+    events: broadcast::Sender<PromptEvent>,
+}
+
+impl SqlitePromptRepository {
+    /// A file database serves a single instance, so changes and events go
+    /// straight to the in-process channels instead of through the database.
+    pub fn new(
+        pool: SqlitePool,
+        changes: broadcast::Sender<PromptChange>,
+        events: broadcast::Sender<PromptEvent>,
+    ) -> Self {
+        Self { pool, changes, events }
+    }
+
+    fn notify_change(&self, prompt_id: Uuid, user_id: Uuid, kind: PromptChangeKind) {
+        // No receivers is fine; nobody is streaming changes right now.
+        let _ = self.changes.send(PromptChange { prompt_id, user_id, kind });
+    }
+
+    fn publish_events(&self, prompt: &Prompt) {
+        for event in prompt.pending_events() {
+            let _ = self.events.send(event.clone());
+        }
+    }
+
+    async fn fetch_prompt_row(&self, id: Uuid, user_id: Uuid) -> Result<Option<SqliteRow>, String> {
+        sqlx::query(&format!("SELECT {} FROM prompts WHERE id = $1 AND user_id = $2", PROMPT_COLUMNS))
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to find prompt: {}", e))
+    }
+
+    /// Loads the given prompts with their versions and tags in a fixed number
+    /// of queries, whatever the number of prompts or versions.
+    async fn load_prompts(&self, rows: Vec<SqliteRow>, hydration: Hydration) -> Result<Vec<Prompt>, String> {
+        let prompt_ids = rows.iter()
+            .map(|row| row.try_get("id").map_err(|e| e.to_string()))
+            .collect::<Result<Vec<Uuid>, String>>()?;
+        if prompt_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut versions = self.fetch_versions(&prompt_ids, hydration).await?;
+        let mut tags = self.fetch_tags(&prompt_ids).await?;
+
+        rows.iter()
+            .zip(&prompt_ids)
+            .map(|(row, prompt_id)| Self::build_prompt(
+                row,
+                versions.remove(prompt_id).unwrap_or_default(),
+                tags.remove(prompt_id).unwrap_or_default(),
+            ))
+            .collect()
+    }
+
+    async fn load_prompt(&self, row: Option<SqliteRow>, hydration: Hydration) -> Result<Option<Prompt>, String> {
+        match row {
+            Some(row) => Ok(self.load_prompts(vec![row], hydration).await?.pop()),
+            None => Ok(None),
+        }
+    }
+
+    /// Versions of the given prompts keyed by prompt id, in creation order.
+    async fn fetch_versions(&self, prompt_ids: &[Uuid], hydration: Hydration) -> Result<HashMap<Uuid, Vec<PromptVersion>>, String> {
+        let rows = query_in(
+            "SELECT id, prompt_id, version, digest, content, content_type, variables, changelog, created_at,
+                    model_provider, model_name, temperature, top_p, max_tokens, stop_sequences, seed,
+                    tools, output_schema, token_count, token_encoding,
+                    created_by, created_by_api_key_id, created_by_source
+             FROM versions WHERE prompt_id IN ",
+            prompt_ids,
+            " ORDER BY created_at",
+        )
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch versions: {}", e))?;
+
+        let (mut feedbacks, mut suggestions) = match hydration {
+            Hydration::Full => (
+                self.fetch_feedbacks(prompt_ids).await?,
+                self.fetch_improvement_suggestions(prompt_ids).await?,
+            ),
+            Hydration::Content => (HashMap::new(), HashMap::new()),
+        };
+
+        let mut versions: HashMap<Uuid, Vec<PromptVersion>> = HashMap::new();
+        for row in rows {
+            let version_id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
+            let prompt_id: Uuid = row.try_get("prompt_id").map_err(|e| e.to_string())?;
+
+            let feedbacks = feedbacks.remove(&version_id).unwrap_or_default();
+            let suggestions = suggestions.remove(&version_id).unwrap_or_default();
+
+            let version_string: String = row.try_get("version").map_err(|e| e.to_string())?;
+            let version = Version::from_str(&version_string)?;
+
+            let content_type_str: String = row.try_get("content_type").map_err(|e| e.to_string())?;
+            let content_type = match content_type_str.as_str() {
+                "static" => ContentType::Static,
+                "template" => ContentType::Template,
+                _ => return Err("Invalid content_type".to_string()),
+            };
+
+            let variables: Option<Vec<String>> = row.try_get::<Option<sqlx::types::Json<Vec<String>>>, _>("variables")
+                .map_err(|e| e.to_string())?
+                .map(|j| j.0);
+
+            let model_config = Self::read_model_config(&row)?;
+            let tools = Self::read_tools(&row)?;
+            let output_schema = Self::read_output_schema(&row)?;
+
+            let draft = VersionDraft {
+                version,
+                content: row.try_get("content").map_err(|e| e.to_string())?,
+                content_type,
+                variables,
+                changelog: row.try_get("changelog").map_err(|e| e.to_string())?,
+                model_config,
+                tools,
+                output_schema,
+            };
+            let mut version = PromptVersion::new(version_id, prompt_id, draft, Self::read_author(&row)?);
+
+            let token_count: Option<i32> = row.try_get("token_count").map_err(|e| e.to_string())?;
+            let token_encoding: Option<String> = row.try_get("token_encoding").map_err(|e| e.to_string())?;
+            if let (Some(tokens), Some(encoding)) = (token_count, token_encoding) {
+                version.record_token_count(TokenCount::new(tokens as u32, encoding));
+            }
+
+            version.restore_feedbacks(feedbacks);
+
+            for suggestion in suggestions {
+                version.improvement_suggestions_mut().push(suggestion);
+            }
+
+            versions.entry(prompt_id).or_default().push(version);
+        }
+
+        Ok(versions)
+    }
+
+    fn read_model_config(row: &SqliteRow) -> Result<Option<ModelConfig>, String> {
+        let provider: Option<String> = row.try_get("model_provider").map_err(|e| e.to_string())?;
+        let model: Option<String> = row.try_get("model_name").map_err(|e| e.to_string())?;
+
+        let (provider, model) = match (provider, model) {
+            (Some(provider), Some(model)) => (ModelProvider::from_str(&provider)?, model),
+            _ => return Ok(None),
+        };
+
+        let stop_sequences: Vec<String> = row.try_get::<Option<sqlx::types::Json<Vec<String>>>, _>("stop_sequences")
+            .map_err(|e| e.to_string())?
+            .map(|j| j.0)
+            .unwrap_or_default();
+
+        let config = ModelConfig::new(
+            provider,
+            model,
+            row.try_get("temperature").map_err(|e| e.to_string())?,
+            row.try_get("top_p").map_err(|e| e.to_string())?,
+            row.try_get::<Option<i32>, _>("max_tokens").map_err(|e| e.to_string())?.map(|t| t as u32),
+            stop_sequences,
+            row.try_get("seed").map_err(|e| e.to_string())?,
+        )?;
+
+        Ok(Some(config))
+    }
+
+    fn read_tools(row: &SqliteRow) -> Result<Vec<ToolDefinition>, String> {
+        let tools: Option<sqlx::types::Json<Vec<serde_json::Value>>> = row.try_get("tools")
+            .map_err(|e| e.to_string())?;
+
+        tools
+            .map(|j| j.0)
+            .unwrap_or_default()
+            .into_iter()
+            .map(|tool| {
+                let name = tool.get("name")
+                    .and_then(|n| n.as_str())
+                    .ok_or("Stored tool is missing its name".to_string())?
+                    .to_string();
+                let description = tool.get("description")
+                    .and_then(|d| d.as_str())
+                    .map(|d| d.to_string());
+                let parameters = tool.get("parameters").cloned().unwrap_or_default();
+                Ok(ToolDefinition::new(name, description, parameters)?)
+            })
+            .collect()
+    }
+
+    fn read_output_schema(row: &SqliteRow) -> Result<Option<OutputSchema>, String> {
+        let output_schema: Option<sqlx::types::Json<serde_json::Value>> = row.try_get("output_schema")
+            .map_err(|e| e.to_string())?;
+
+        output_schema
+            .map(|j| {
+                let name = j.0.get("name")
+                    .and_then(|n| n.as_str())
+                    .ok_or("Stored output schema is missing its name".to_string())?
+                    .to_string();
+                let schema = j.0.get("schema").cloned().unwrap_or_default();
+                Ok(OutputSchema::new(name, schema)?)
+            })
+            .transpose()
+    }
+
+    /// `None` when the caller reported nothing about the call.
+    fn read_call_details(row: &SqliteRow) -> Result<Option<CallDetails>, String> {
+        let details = CallDetails::new(CallDetailsDraft {
+            model: row.try_get("model").map_err(|e| e.to_string())?,
+            latency_ms: row.try_get::<Option<i64>, _>("latency_ms").map_err(|e| e.to_string())?.map(|v| v as u64),
+            input_tokens: row.try_get::<Option<i32>, _>("input_tokens").map_err(|e| e.to_string())?.map(|v| v as u32),
+            output_tokens: row.try_get::<Option<i32>, _>("output_tokens").map_err(|e| e.to_string())?.map(|v| v as u32),
+            cost_usd: row.try_get("cost_usd").map_err(|e| e.to_string())?,
+            render_context: row.try_get::<Option<sqlx::types::Json<serde_json::Value>>, _>("render_context").map_err(|e| e.to_string())?.map(|j| j.0),
+            trace_id: row.try_get("trace_id").map_err(|e| e.to_string())?,
+            metadata: row.try_get::<Option<sqlx::types::Json<serde_json::Value>>, _>("metadata").map_err(|e| e.to_string())?.map(|j| j.0),
+        })?;
+        let details = match row.try_get::<Option<Uuid>, _>("render_id").map_err(|e| e.to_string())? {
+            Some(render_id) => details.link_render(render_id, None),
+            None => details,
+        };
+        Ok(Some(details).filter(|d| !d.is_empty()))
+    }
+
+    /// Who created a version, feedback or suggestion; `None` for rows
+    /// written before authorship was recorded.
+    fn read_author(row: &SqliteRow) -> Result<Option<Actor>, String> {
+        let Some(user_id) = row.try_get::<Option<Uuid>, _>("created_by").map_err(|e| e.to_string())? else {
+            return Ok(None);
+        };
+        let api_key_id = row.try_get("created_by_api_key_id").map_err(|e| e.to_string())?;
+        let source: Option<String> = row.try_get("created_by_source").map_err(|e| e.to_string())?;
+        Actor::from_repository(user_id, api_key_id, source.as_deref()).map(Some)
+    }
+
+    /// Tags of the given prompts keyed by prompt id.
+    async fn fetch_tags(&self, prompt_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Tag>>, String> {
+        let rows = query_in(
+            "SELECT id, prompt_id, version_id, name, updated_at
+             FROM tags WHERE prompt_id IN ",
+            prompt_ids,
+            "",
+        )
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch tags: {}", e))?;
+
+        let mut tags: HashMap<Uuid, Vec<Tag>> = HashMap::new();
+        for row in rows {
+            let id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
+            let prompt_id: Uuid = row.try_get("prompt_id").map_err(|e| e.to_string())?;
+            let version_id: Uuid = row.try_get("version_id").map_err(|e| e.to_string())?;
+
+            tags.entry(prompt_id).or_default().push(Tag::new(
+                id,
+                prompt_id,
+                version_id,
+                row.try_get("name").map_err(|e| e.to_string())?,
+            ));
+        }
+
+        Ok(tags)
+    }
+
+    /// Feedback on every version of the given prompts keyed by version id.
+    async fn fetch_feedbacks(&self, prompt_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<Feedback>>, String> {
+        let rows = query_in(
+            "SELECT f.id, f.version_id, f.rating, f.comment, f.test_input, f.test_actual_output, f.test_expected_output, f.test_schema_violations, f.created_at,
+                    f.created_by, f.created_by_api_key_id, f.created_by_source,
+                    f.model, f.latency_ms, f.input_tokens, f.output_tokens, f.cost_usd, f.render_context, f.trace_id, f.metadata, f.render_id
+             FROM feedbacks f
+             INNER JOIN versions v ON v.id = f.version_id
+             WHERE v.prompt_id IN ",
+            prompt_ids,
+            " ORDER BY f.created_at",
+        )
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch feedbacks: {}", e))?;
+
+        let mut feedbacks: HashMap<Uuid, Vec<Feedback>> = HashMap::new();
+        for row in rows {
+            let id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
+            let version_id: Uuid = row.try_get("version_id").map_err(|e| e.to_string())?;
+
+            let test_scenario = match (
+                row.try_get::<Option<String>, _>("test_input").map_err(|e| e.to_string())?,
+                row.try_get::<Option<String>, _>("test_actual_output").map_err(|e| e.to_string())?,
+            ) {
+                (Some(input), Some(actual_output)) => {
+                    let expected_output = row.try_get("test_expected_output").map_err(|e| e.to_string())?;
+                    let schema_violations = row.try_get::<Option<sqlx::types::Json<Vec<String>>>, _>("test_schema_violations")
+                        .map_err(|e| e.to_string())?
+                        .map(|j| j.0);
+                    Some(TestScenario::from_repository(input, actual_output, expected_output, schema_violations))
+                }
+                _ => None,
+            };
+
+            feedbacks.entry(version_id).or_default().push(Feedback::from_repository(FeedbackRecord {
+                id,
+                version_id,
+                rating: row.try_get::<i16, _>("rating").map_err(|e| e.to_string())? as u8,
+                comment: row.try_get("comment").map_err(|e| e.to_string())?,
+                test_scenario,
+                call_details: Self::read_call_details(&row)?,
+                created_by: Self::read_author(&row)?,
+                created_at: row.try_get("created_at").map_err(|e| e.to_string())?,
+            }));
+        }
+
+        Ok(feedbacks)
+    }
+
+    /// Improvement suggestions for every version of the given prompts keyed by source version id.
+    async fn fetch_improvement_suggestions(&self, prompt_ids: &[Uuid]) -> Result<HashMap<Uuid, Vec<ImprovementSuggestion>>, String> {
+        let rows = query_in(
+            "SELECT s.id, s.source_version_id, s.suggested_content, s.ai_rationale, s.status, s.decline_reason, s.created_at, s.resolved_at, s.resulting_version_id,
+                s.created_by, s.created_by_api_key_id, s.created_by_source
+         FROM improvement_suggestions s
+         INNER JOIN versions v ON v.id = s.source_version_id
+         WHERE v.prompt_id IN ",
+            prompt_ids,
+            " ORDER BY s.created_at",
+        )
+            .build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch improvement suggestions: {}", e))?;
+
+        let mut suggestions: HashMap<Uuid, Vec<ImprovementSuggestion>> = HashMap::new();
+        for row in rows {
+            let id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
+            let source_version_id: Uuid = row.try_get("source_version_id").map_err(|e| e.to_string())?;
+            let suggested_content: String = row.try_get("suggested_content").map_err(|e| e.to_string())?;
+            let ai_rationale: String = row.try_get("ai_rationale").map_err(|e| e.to_string())?;
+
+            let status_str: String = row.try_get("status").map_err(|e| e.to_string())?;
+            let status = match status_str.as_str() {
+                "pending" => SuggestionStatus::Pending,
+                "accepted" => SuggestionStatus::Accepted,
+                "declined" => SuggestionStatus::Declined,
+                _ => return Err("Invalid suggestion status".to_string()),
+            };
+
+            let mut suggestion = ImprovementSuggestion::new(
+                id,
+                source_version_id,
+                suggested_content,
+                ai_rationale,
+                Self::read_author(&row)?,
+            );
+
+            if status == SuggestionStatus::Accepted {
+                let resulting_version_id: Option<Uuid> = row.try_get("resulting_version_id")
+                    .map_err(|e| e.to_string())?;
+                let resulting_version_id = resulting_version_id
+                    .ok_or("Accepted suggestion missing resulting_version_id")?;
+                suggestion.accept(resulting_version_id)?;
+            } else if status == SuggestionStatus::Declined {
+                let reason: Option<String> = row.try_get("decline_reason")
+                    .map_err(|e| e.to_string())?;
+                let reason = reason.ok_or("Declined suggestion missing reason")?;
+                suggestion.decline(reason)?;
+            }
+
+            suggestions.entry(source_version_id).or_default().push(suggestion);
+        }
+
+        Ok(suggestions)
+    }
+
+    async fn insert_version(conn: &mut SqliteConnection, version: &PromptVersion) -> Result<(), String> {
+        let content_type_str = match version.content_type() {
+            ContentType::Static => "static",
+            ContentType::Template => "template",
+        };
+
+        let variables_json = version.variables().map(|v| sqlx::types::Json(v.to_vec()));
+        let model_config = version.model_config();
+        let tools_json = (!version.tools().is_empty()).then(|| {
+            sqlx::types::Json(version.tools().iter().map(|tool| serde_json::json!({
+                "name": tool.name(),
+                "description": tool.description(),
+                "parameters": tool.parameters(),
+            })).collect::<Vec<_>>())
+        });
+        let output_schema_json = version.output_schema().map(|schema| sqlx::types::Json(serde_json::json!({
+            "name": schema.name(),
+            "schema": schema.schema(),
+        })));
+
+        sqlx::query(
+            "INSERT INTO versions (id, prompt_id, version, digest, content, content_type, variables, changelog, created_at,
+                                   model_provider, model_name, temperature, top_p, max_tokens, stop_sequences, seed,
+                                   tools, output_schema, token_count, token_encoding,
+                                   created_by, created_by_api_key_id, created_by_source)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21, $22, $23)
+         ON CONFLICT (id) DO UPDATE SET token_count = EXCLUDED.token_count, token_encoding = EXCLUDED.token_encoding"        )
+            .bind(version.id())
+            .bind(version.prompt_id())
+            .bind(version.version_string())
+            .bind(version.digest())
+            .bind(version.content())
+            .bind(content_type_str)
+            .bind(variables_json)
+            .bind(version.changelog())
+            .bind(version.created_at())
+            .bind(model_config.map(|c| c.provider().as_str()))
+            .bind(model_config.map(|c| c.model()))
+            .bind(model_config.and_then(|c| c.temperature()))
+            .bind(model_config.and_then(|c| c.top_p()))
+            .bind(model_config.and_then(|c| c.max_tokens()).map(|t| t as i32))
+            .bind(model_config.map(|c| sqlx::types::Json(c.stop_sequences().to_vec())))
+            .bind(model_config.and_then(|c| c.seed()))
+            .bind(tools_json)
+            .bind(output_schema_json)
+            .bind(version.token_count().map(|t| t.tokens() as i32))
+            .bind(version.token_count().map(|t| t.encoding()))
+            .bind(version.created_by().map(|a| a.user_id))
+            .bind(version.created_by().and_then(|a| a.api_key_id))
+            .bind(version.created_by().map(|a| a.source.as_str()))
+            .execute(conn)
+            .await
+            .map_err(|e| format!("Failed to save version: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn upsert_tag(conn: &mut SqliteConnection, tag: &Tag) -> Result<(), String> {
+        sqlx::query(
+            "INSERT INTO tags (id, prompt_id, version_id, name, updated_at)
+             VALUES ($1, $2, $3, $4, $5)
+             ON CONFLICT (id) DO UPDATE SET version_id = EXCLUDED.version_id, updated_at = EXCLUDED.updated_at"        )
+            .bind(tag.id())
+            .bind(tag.prompt_id())
+            .bind(tag.version_id())
+            .bind(tag.name())
+            .bind(tag.updated_at())
+            .execute(conn)
+            .await
+            .map_err(|e| format!("Failed to save tag: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn upsert_feedback(conn: &mut SqliteConnection, version_id: Uuid, feedback: &Feedback) -> Result<(), String> {
+        let (test_input, test_actual_output, test_expected_output, test_schema_violations) =
+            if let Some(scenario) = feedback.test_scenario() {
+                (
+                    Some(scenario.input()),
+                    Some(scenario.actual_output()),
+                    scenario.expected_output(),
+                    scenario.schema_violations(),
+                )
+            } else {
+                (None, None, None, None)
+            };
+        let call_details = feedback.call_details();
+
+        sqlx::query(
+            "INSERT INTO feedbacks (id, version_id, rating, comment, test_input, test_actual_output, test_expected_output, created_at,
+                                    created_by, created_by_api_key_id, created_by_source,
+                                    model, latency_ms, input_tokens, output_tokens, cost_usd, render_context, trace_id, metadata, render_id,
+                                    test_schema_violations)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19, $20, $21)
+             ON CONFLICT (id) DO UPDATE SET
+                rating = EXCLUDED.rating,
+                comment = EXCLUDED.comment,
+                test_input = EXCLUDED.test_input,
+                test_actual_output = EXCLUDED.test_actual_output,
+                test_expected_output = EXCLUDED.test_expected_output,
+                test_schema_violations = EXCLUDED.test_schema_violations"        )
+            .bind(feedback.id())
+            .bind(version_id)
+            .bind(feedback.rating() as i16)
+            .bind(feedback.comment())
+            .bind(test_input)
+            .bind(test_actual_output)
+            .bind(test_expected_output)
+            .bind(feedback.created_at())
+            .bind(feedback.created_by().map(|a| a.user_id))
+            .bind(feedback.created_by().and_then(|a| a.api_key_id))
+            .bind(feedback.created_by().map(|a| a.source.as_str()))
+            .bind(call_details.and_then(|d| d.model()))
+            .bind(call_details.and_then(|d| d.latency_ms()).map(|v| v as i64))
+            .bind(call_details.and_then(|d| d.input_tokens()).map(|v| v as i32))
+            .bind(call_details.and_then(|d| d.output_tokens()).map(|v| v as i32))
+            .bind(call_details.and_then(|d| d.cost_usd()))
+            .bind(call_details.and_then(|d| d.render_context()).map(sqlx::types::Json))
+            .bind(call_details.and_then(|d| d.trace_id()))
+            .bind(call_details.and_then(|d| d.metadata()).map(sqlx::types::Json))
+            .bind(call_details.and_then(|d| d.render_id()))
+            .bind(test_schema_violations.map(sqlx::types::Json))
+            .execute(conn)
+            .await
+            .map_err(|e| format!("Failed to save feedback: {}", e))?;
+
+        Ok(())
+    }
+
+    async fn upsert_improvement_suggestion(conn: &mut SqliteConnection, suggestion: &ImprovementSuggestion) -> Result<(), String> {
+        let status_str = match suggestion.status() {
+            SuggestionStatus::Pending => "pending",
+            SuggestionStatus::Accepted => "accepted",
+            SuggestionStatus::Declined => "declined",
+        };
+
+        sqlx::query(
+            "INSERT INTO improvement_suggestions (id, source_version_id, suggested_content, ai_rationale, status, decline_reason, created_at, resolved_at, resulting_version_id,
+                                          created_by, created_by_api_key_id, created_by_source)
+         VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+         ON CONFLICT (id) DO UPDATE SET
+            status = EXCLUDED.status,
+            decline_reason = EXCLUDED.decline_reason,
+            resolved_at = EXCLUDED.resolved_at,
+            resulting_version_id = EXCLUDED.resulting_version_id"        )
+            .bind(suggestion.id())
+            .bind(suggestion.source_version_id())
+            .bind(suggestion.suggested_content())
+            .bind(suggestion.ai_rationale())
+            .bind(status_str)
+            .bind(suggestion.decline_reason())
+            .bind(suggestion.created_at())
+            .bind(suggestion.resolved_at())
+            .bind(suggestion.resulting_version_id())
+            .bind(suggestion.created_by().map(|a| a.user_id))
+            .bind(suggestion.created_by().and_then(|a| a.api_key_id))
+            .bind(suggestion.created_by().map(|a| a.source.as_str()))
+            .execute(conn)
+            .await
+            .map_err(|e| format!("Failed to save improvement suggestion: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Inserts or updates the prompt row and bumps its revision, returning
+    /// the stored revision. The bump is guarded by `expected_revision` when
+    /// the caller sent one, and otherwise by the revision the prompt was
+    /// loaded at unless the save only appends feedback or suggestions; a
+    /// moved revision fails with `PreconditionFailed` or `Conflict`
+    /// respectively. Name, description and token budget are only written
+    /// when they changed, and `updated_at` only when versions or tags did.
+    async fn write_prompt_row(conn: &mut SqliteConnection, prompt: &Prompt, expected_revision: Option<u64>) -> Result<u64, AppError> {
+        let prompt_type_str = match prompt.prompt_type() {
+            PromptType::System => "system",
+            PromptType::User => "user",
+        };
+
+        if prompt.revision() == 0 {
+            sqlx::query(
+                "INSERT INTO prompts (id, user_id, name, description, prompt_type, max_render_tokens, created_at, updated_at, revision)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, 1)"            )
+                .bind(prompt.id())
+                .bind(prompt.user_id())
+                .bind(prompt.name())
+                .bind(prompt.description())
+                .bind(prompt_type_str)
+                .bind(prompt.max_render_tokens().map(|m| m as i32))
+                .bind(prompt.created_at())
+                .bind(prompt.updated_at())
+                .execute(conn)
+                .await
+                .map_err(|e| format!("Failed to save prompt: {}", e))?;
+
+            return Ok(1);
+        }
+
+        let changes = prompt.pending_changes();
+        let guard = expected_revision.or((!changes.is_append_only()).then_some(prompt.revision()));
+        let query = if changes.prompt() {
+            sqlx::query(
+                "UPDATE prompts
+             SET name = $3, description = $4, prompt_type = $5, max_render_tokens = $6, updated_at = $7, revision = revision + 1
+             WHERE id = $1 AND ($2 IS NULL OR revision = $2)
+             RETURNING revision"        )
+                .bind(prompt.id())
+                .bind(guard.map(|r| r as i64))
+                .bind(prompt.name())
+                .bind(prompt.description())
+                .bind(prompt_type_str)
+                .bind(prompt.max_render_tokens().map(|m| m as i32))
+                .bind(prompt.updated_at())
+        } else {
+            sqlx::query(
+                "UPDATE prompts SET updated_at = COALESCE($3, updated_at), revision = revision + 1
+             WHERE id = $1 AND ($2 IS NULL OR revision = $2)
+             RETURNING revision"        )
+                .bind(prompt.id())
+                .bind(guard.map(|r| r as i64))
+                .bind(changes.touched().then(|| prompt.updated_at()))
+        };
+
+        let revision: Option<i64> = query
+            .fetch_optional(conn)
+            .await
+            .map_err(|e| format!("Failed to save prompt: {}", e))?
+            .map(|row| row.try_get("revision"))
+            .transpose()
+            .map_err(|e| format!("Failed to save prompt: {}", e))?;
+
+        match revision {
+            Some(revision) => Ok(revision as u64),
+            None if expected_revision.is_some() => Err(AppError::PreconditionFailed(ETAG_MISMATCH.to_string())),
+            None if guard.is_some() => Err(AppError::Conflict(REVISION_CONFLICT.to_string())),
+            None => Err(AppError::not_found("Prompt not found")),
+        }
+    }
+
+    /// Writes the prompt row and the rows listed in its change set. Deletes
+    /// run before inserts so a tag removed and re-created under the same name
+    /// does not collide, and versions are inserted before the feedback,
+    /// suggestions and tags that reference them.
+    async fn write_changes(conn: &mut SqliteConnection, prompt: &Prompt, expected_revision: Option<u64>) -> Result<u64, AppError> {
+        let changes = prompt.pending_changes();
+
+        // Written first: SQLite serialises writers, so a concurrent save that
+        // committed in between shows up as a moved revision here when the
+        // save is guarded.
+        let revision = Self::write_prompt_row(&mut *conn, prompt, expected_revision).await?;
+
+        if !changes.deleted_feedbacks().is_empty() {
+            query_in("DELETE FROM feedbacks WHERE id IN ", changes.deleted_feedbacks(), "")
+                .build()
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to delete feedbacks: {}", e))?;
+        }
+
+        if !changes.deleted_tags().is_empty() {
+            query_in("DELETE FROM tags WHERE id IN ", changes.deleted_tags(), "")
+                .build()
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to delete tags: {}", e))?;
+        }
+
+        if !changes.deleted_versions().is_empty() {
+            let mut query = query_in("DELETE FROM versions WHERE id IN ", changes.deleted_versions(), " AND prompt_id = ");
+            query.push_bind(prompt.id());
+            query.build()
+                .execute(&mut *conn)
+                .await
+                .map_err(|e| format!("Failed to delete versions: {}", e))?;
+        }
+
+        for version_id in changes.versions() {
+            if let Some(version) = prompt.find_version_by_id(*version_id) {
+                Self::insert_version(conn, version).await?;
+            }
+        }
+
+        for (version_id, feedback_id) in changes.feedbacks() {
+            let feedback = prompt.find_version_by_id(*version_id)
+                .and_then(|v| v.feedbacks().iter().find(|f| f.id() == *feedback_id));
+            if let Some(feedback) = feedback {
+                Self::upsert_feedback(conn, *version_id, feedback).await?;
+            }
+        }
+
+        for (version_id, suggestion_id) in changes.suggestions() {
+            let suggestion = prompt.find_version_by_id(*version_id)
+                .and_then(|v| v.find_suggestion(*suggestion_id));
+            if let Some(suggestion) = suggestion {
+                Self::upsert_improvement_suggestion(conn, suggestion).await?;
+            }
+        }
+
+        for tag_id in changes.tags() {
+            if let Some(tag) = prompt.tags().iter().find(|t| t.id() == *tag_id) {
+                Self::upsert_tag(conn, tag).await?;
+            }
+        }
+
+        Ok(revision)
+    }
+
+    fn build_prompt(row: &SqliteRow, versions: Vec<PromptVersion>, tags: Vec<Tag>) -> Result<Prompt, String> {
+        let prompt_id: Uuid = row.try_get("id").map_err(|e| e.to_string())?;
+        let user_id: Uuid = row.try_get("user_id").map_err(|e| e.to_string())?;
+
+        let prompt_type_str: String = row.try_get("prompt_type").map_err(|e| e.to_string())?;
+        let prompt_type = match prompt_type_str.as_str() {
+            "system" => PromptType::System,
+            "user" => PromptType::User,
+            _ => return Err("Invalid prompt_type".to_string()),
+        };
+
+        Ok(Prompt::from_repository(PromptRecord {
+            id: prompt_id,
+            user_id,
+            name: row.try_get("name").map_err(|e| e.to_string())?,
+            description: row.try_get("description").map_err(|e| e.to_string())?,
+            prompt_type,
+            max_render_tokens: row.try_get::<Option<i32>, _>("max_render_tokens").map_err(|e| e.to_string())?.map(|m| m as u32),
+            created_at: row.try_get("created_at").map_err(|e| e.to_string())?,
+            updated_at: row.try_get("updated_at").map_err(|e| e.to_string())?,
+            revision: row.try_get::<i64, _>("revision").map_err(|e| e.to_string())? as u64,
+            versions,
+            tags,
+        }))
+    }
+}
+
+#[async_trait]
+impl PromptRepository for SqlitePromptRepository {
+    #[instrument(name = "db.prompts.save", skip_all, fields(db.system = "sqlite", prompt_id = %prompt.id()))]
+    async fn save(&self, prompt: &Prompt, audit: &[AuditEntry], expected_revision: Option<u64>) -> Result<u64, AppError> {
+        if prompt.pending_changes().is_empty() {
+            return Ok(prompt.revision());
+        }
+
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let revision = Self::write_changes(&mut tx, prompt, expected_revision).await?;
+        insert_audit_entries(&mut tx, audit).await?;
+        enqueue_event_deliveries(&mut tx, prompt.pending_events()).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit prompt: {}", e))?;
+
+        self.notify_change(prompt.id(), prompt.user_id(), PromptChangeKind::Saved);
+        self.publish_events(prompt);
+
+        Ok(revision)
+    }
+
+    #[instrument(name = "db.prompts.find_by_id", skip_all, fields(db.system = "sqlite", %id))]
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Prompt>, String> {
+        let row = sqlx::query(&format!("SELECT {} FROM prompts WHERE id = $1", PROMPT_COLUMNS))
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to find prompt: {}", e))?;
+
+        self.load_prompt(row, Hydration::Full).await
+    }
+
+    #[instrument(name = "db.prompts.find_by_id_and_user", skip_all, fields(db.system = "sqlite", %id))]
+    async fn find_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String> {
+        let row = self.fetch_prompt_row(id, user_id).await?;
+        self.load_prompt(row, Hydration::Full).await
+    }
+
+    #[instrument(name = "db.prompts.find_content_by_id_and_user", skip_all, fields(db.system = "sqlite", %id))]
+    async fn find_content_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<Prompt>, String> {
+        let row = self.fetch_prompt_row(id, user_id).await?;
+        self.load_prompt(row, Hydration::Content).await
+    }
+
+    #[instrument(name = "db.prompts.find_content_by_ids", skip_all, fields(db.system = "sqlite", count = ids.len()))]
+    async fn find_content_by_ids(&self, ids: &[Uuid], user_id: Uuid) -> Result<Vec<Prompt>, String> {
+        if ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut query = query_in(&format!("SELECT {} FROM prompts WHERE id IN ", PROMPT_COLUMNS), ids, " AND user_id = ");
+        query.push_bind(user_id);
+        let rows = query.build()
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch prompts: {}", e))?;
+
+        self.load_prompts(rows, Hydration::Content).await
+    }
+
+    #[instrument(name = "db.prompts.find_all", skip_all, fields(db.system = "sqlite"))]
+    async fn find_all(&self) -> Result<Vec<Prompt>, String> {
+        let rows = sqlx::query(&format!("SELECT {} FROM prompts ORDER BY created_at DESC", PROMPT_COLUMNS))
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch prompts: {}", e))?;
+
+        self.load_prompts(rows, Hydration::Full).await
+    }
+
+    #[instrument(name = "db.prompts.find_by_user", skip_all, fields(db.system = "sqlite"))]
+    async fn find_by_user(&self, user_id: Uuid) -> Result<Vec<Prompt>, String> {
+        let rows = sqlx::query(&format!("SELECT {} FROM prompts WHERE user_id = $1 ORDER BY created_at DESC", PROMPT_COLUMNS))
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch prompts: {}", e))?;
+
+        self.load_prompts(rows, Hydration::Full).await
+    }
+
+    #[instrument(name = "db.prompts.find_content_by_tag", skip_all, fields(db.system = "sqlite"))]
+    async fn find_content_by_tag(&self, user_id: Uuid, tag_name: &str) -> Result<Vec<Prompt>, String> {
+        let rows = sqlx::query(
+            "SELECT p.id, p.user_id, p.name, p.description, p.prompt_type, p.max_render_tokens, p.created_at, p.updated_at, p.revision
+             FROM prompts p
+             INNER JOIN tags t ON p.id = t.prompt_id
+             WHERE p.user_id = $1 AND t.name = $2
+             ORDER BY p.created_at DESC"        )
+            .bind(user_id)
+            .bind(tag_name)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch prompts by tag: {}", e))?;
+
+        self.load_prompts(rows, Hydration::Content).await
+    }
+
+    #[instrument(name = "db.prompts.delete", skip_all, fields(db.system = "sqlite", %id))]
+    async fn delete(&self, id: Uuid, audit: &[AuditEntry]) -> Result<(), String> {
+        let mut tx = self.pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+        let row = sqlx::query("DELETE FROM prompts WHERE id = $1 RETURNING user_id")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to delete prompt: {}", e))?
+            .ok_or_else(|| "Prompt not found".to_string())?;
+        insert_audit_entries(&mut tx, audit).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit prompt deletion: {}", e))?;
+
+        let user_id: Uuid = row.try_get("user_id").map_err(|e| e.to_string())?;
+        self.notify_change(id, user_id, PromptChangeKind::Deleted);
+
+        Ok(())
+    }
+}
+/// `prefix(?, ?, ...)suffix` with one bind per id, as SQLite has no array
+/// parameters. Callers must not pass an empty list.
+fn query_in<'a>(prefix: &str, ids: &'a [Uuid], suffix: &str) -> QueryBuilder<'a, Sqlite> {
+    let mut query = QueryBuilder::new(prefix);
+    query.push("(");
+    let mut separated = query.separated(", ");
+    for id in ids {
+        separated.push_bind(*id);
+    }
+    query.push(")");
+    query.push(suffix);
+    query
+}
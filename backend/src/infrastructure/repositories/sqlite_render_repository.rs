@@ -0,0 +1,87 @@
+use async_trait::async_trait;
+use tracing::instrument;
+use sqlx::{QueryBuilder, Row, SqlitePool};
+use sqlx::sqlite::SqliteRow;
+use uuid::Uuid;
+
+use crate::application::RenderRepository;
+use crate::domain::actor::Actor;
+use crate::domain::render::{RenderEvent, RenderEventRecord};
+
+/// Keeps each insert well under the bind parameter limit.
+const INSERT_CHUNK: usize = 500;
+
+pub struct SqliteRenderRepository {
+    pool: SqlitePool,
+}
+
+impl SqliteRenderRepository {
+    pub fn new(pool: SqlitePool) -> Self {
+        Self { pool }
+    }
+
+    fn read_event(row: &SqliteRow) -> Result<RenderEvent, String> {
+        let source: Option<String> = row.try_get("source").map_err(|e| e.to_string())?;
+        let context: Option<sqlx::types::Json<serde_json::Value>> = row.try_get("context").map_err(|e| e.to_string())?;
+
+        Ok(RenderEvent::from_repository(RenderEventRecord {
+            id: row.try_get("id").map_err(|e| e.to_string())?,
+            rendered_at: row.try_get("rendered_at").map_err(|e| e.to_string())?,
+            actor: Actor::from_repository(
+                row.try_get("user_id").map_err(|e| e.to_string())?,
+                row.try_get("api_key_id").map_err(|e| e.to_string())?,
+                source.as_deref(),
+            )?,
+            prompt_id: row.try_get("prompt_id").map_err(|e| e.to_string())?,
+            version_id: row.try_get("version_id").map_err(|e| e.to_string())?,
+            tag: row.try_get("tag").map_err(|e| e.to_string())?,
+            context_hash: row.try_get("context_hash").map_err(|e| e.to_string())?,
+            context: context.map(|json| json.0),
+        }))
+    }
+}
+
+#[async_trait]
+impl RenderRepository for SqliteRenderRepository {
+    #[instrument(name = "db.render_events.append", skip_all, fields(db.system = "sqlite", render_count = events.len()))]
+    async fn append(&self, events: &[RenderEvent]) -> Result<(), String> {
+        for chunk in events.chunks(INSERT_CHUNK) {
+            let mut sql = QueryBuilder::new(
+                "INSERT INTO render_events (id, rendered_at, user_id, api_key_id, source, prompt_id, version_id, tag, context_hash, context) "
+            );
+            sql.push_values(chunk, |mut row, event| {
+                row.push_bind(event.id())
+                    .push_bind(event.rendered_at())
+                    .push_bind(event.actor().user_id)
+                    .push_bind(event.actor().api_key_id)
+                    .push_bind(event.actor().source.as_str())
+                    .push_bind(event.prompt_id())
+                    .push_bind(event.version_id())
+                    .push_bind(event.tag())
+                    .push_bind(event.context_hash())
+                    .push_bind(event.context().map(sqlx::types::Json));
+            });
+            sql.build()
+                .execute(&self.pool)
+                .await
+                .map_err(|e| format!("Failed to append render events: {}", e))?;
+        }
+
+        Ok(())
+    }
+
+    #[instrument(name = "db.render_events.find_by_id_and_user", skip_all, fields(db.system = "sqlite"))]
+    async fn find_by_id_and_user(&self, id: Uuid, user_id: Uuid) -> Result<Option<RenderEvent>, String> {
+        let row = sqlx::query(
+            "SELECT id, rendered_at, user_id, api_key_id, source, prompt_id, version_id, tag, context_hash, context
+             FROM render_events WHERE id = $1 AND user_id = $2"
+        )
+            .bind(id)
+            .bind(user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to fetch render event: {}", e))?;
+
+        row.as_ref().map(Self::read_event).transpose()
+    }
+}
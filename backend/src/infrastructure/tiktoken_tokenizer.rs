@@ -0,0 +1,95 @@
+use crate::application::Tokenizer;
+use crate::domain::prompt::{ModelConfig, ModelProvider, TokenCount};
+use tiktoken_rs::tokenizer::{get_tokenizer, Tokenizer as Encoding};
+use tiktoken_rs::CoreBPE;
+
+/// Counts tokens with the BPE vocabularies bundled in `tiktoken-rs`, so no
+/// network access is needed. OpenAI models use their exact encoding; other
+/// providers don't publish their vocabularies and are approximated with
+/// `o200k_base`.
+pub struct TiktokenTokenizer;
+
+impl TiktokenTokenizer {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn encoding_for(model_config: Option<&ModelConfig>) -> Encoding {
+        model_config
+            .filter(|c| c.provider() == ModelProvider::OpenAI)
+            .and_then(|c| get_tokenizer(c.model()))
+            .filter(|e| matches!(e, Encoding::O200kBase | Encoding::Cl100kBase))
+            .unwrap_or(Encoding::O200kBase)
+    }
+
+    fn bpe(encoding: Encoding) -> &'static CoreBPE {
+        match encoding {
+            Encoding::Cl100kBase => tiktoken_rs::cl100k_base_singleton(),
+            _ => tiktoken_rs::o200k_base_singleton(),
+        }
+    }
+
+    fn encoding_name(encoding: Encoding) -> &'static str {
+        match encoding {
+            Encoding::Cl100kBase => "cl100k_base",
+            _ => "o200k_base",
+        }
+    }
+}
+
+impl Tokenizer for TiktokenTokenizer {
+    fn count_tokens(&self, text: &str, model_config: Option<&ModelConfig>) -> TokenCount {
+        let encoding = Self::encoding_for(model_config);
+        let tokens = Self::bpe(encoding).encode_with_special_tokens(text).len();
+        TokenCount::new(tokens as u32, Self::encoding_name(encoding).to_string())
+    }
+
+    /// Matched by the longest model prefix, like prices, so `o1-mini` does
+    /// not take the window of `o1`.
+    fn context_window(&self, model_config: &ModelConfig) -> Option<u32> {
+        const CONTEXT_WINDOWS: &[(&str, u32)] = &[
+            ("gpt-4.1", 1_047_576),
+            ("gpt-4o", 128_000),
+            ("gpt-4o-mini", 128_000),
+            ("gpt-4-turbo", 128_000),
+            ("gpt-4", 8_192),
+            ("gpt-3.5-turbo", 16_385),
+            ("o1", 200_000),
+            ("o1-mini", 128_000),
+            ("o1-preview", 128_000),
+            ("o3", 200_000),
+            ("o3-mini", 200_000),
+            ("o4", 200_000),
+            ("o4-mini", 200_000),
+            ("claude-", 200_000),
+            ("gemini-1.5-pro", 2_097_152),
+            ("gemini-", 1_048_576),
+        ];
+
+        CONTEXT_WINDOWS
+            .iter()
+            .filter(|(prefix, _)| model_config.model().starts_with(prefix))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, window)| *window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(provider: ModelProvider, model: &str) -> Option<u32> {
+        let config = ModelConfig::new(provider, model.to_string(), None, None, None, Vec::new(), None).unwrap();
+        TiktokenTokenizer::new().context_window(&config)
+    }
+
+    #[test]
+    fn uses_the_longest_matching_prefix() {
+        assert_eq!(window(ModelProvider::OpenAI, "o1-mini-2024-09-12"), Some(128_000));
+        assert_eq!(window(ModelProvider::OpenAI, "o1-2024-12-17"), Some(200_000));
+        assert_eq!(window(ModelProvider::OpenAI, "gpt-4-turbo-2024-04-09"), Some(128_000));
+        assert_eq!(window(ModelProvider::OpenAI, "gpt-4-0613"), Some(8_192));
+        assert_eq!(window(ModelProvider::Gemini, "gemini-1.5-pro-002"), Some(2_097_152));
+        assert_eq!(window(ModelProvider::OpenAI, "davinci-002"), None);
+    }
+}
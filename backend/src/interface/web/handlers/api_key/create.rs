@@ -6,7 +6,8 @@ use axum::{
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::interface::web::handlers::{app_state::AppState, auth::extract_user_id};
+use crate::domain::actor::Actor;
+use crate::interface::web::handlers::{api_error::{ApiError, ApiJson}, app_state::AppState, auth::extract_user_id};
 
 #[derive(Deserialize)]
 pub struct CreateApiKeyRequest {
@@ -23,15 +24,14 @@ pub struct CreateApiKeyResponse {
 pub async fn create_api_key(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-    Json(payload): Json<CreateApiKeyRequest>,
-) -> Result<(StatusCode, Json<CreateApiKeyResponse>), (StatusCode, String)> {
-    let user_id = extract_user_id(&headers).await?;
+    ApiJson(payload): ApiJson<CreateApiKeyRequest>,
+) -> Result<(StatusCode, Json<CreateApiKeyResponse>), ApiError> {
+    let actor = Actor::user(extract_user_id(&headers).await?);
 
     let (id, api_key) = state
         .create_api_key
-        .execute(user_id, payload.name)
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .execute(actor, payload.name)
+        .await?;
 
     Ok((StatusCode::CREATED, Json(CreateApiKeyResponse {
         id: id.to_string(),
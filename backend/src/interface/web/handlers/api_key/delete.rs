@@ -4,7 +4,9 @@ use axum::{
 };
 use std::sync::Arc;
 
+use crate::domain::actor::Actor;
 use crate::interface::web::handlers::{
+    api_error::ApiError,
     app_state::AppState,
     auth::extract_user_id,
     uuid_helpers::parse_uuid,
@@ -14,15 +16,14 @@ pub async fn delete_api_key(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(api_key_id): Path<String>,
-) -> Result<StatusCode, (StatusCode, String)> {
-    let user_id = extract_user_id(&headers).await?;
+) -> Result<StatusCode, ApiError> {
+    let actor = Actor::user(extract_user_id(&headers).await?);
     let key_uuid = parse_uuid(&api_key_id, "api_key_id")?;
 
     state
         .delete_api_key
-        .execute(key_uuid, user_id)
-        .await
-        .map_err(|e| (StatusCode::NOT_FOUND, e))?;
+        .execute(key_uuid, actor)
+        .await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
\ No newline at end of file
@@ -1,11 +1,12 @@
 use axum::{
     extract::State,
-    http::{StatusCode, HeaderMap},
+    http::HeaderMap,
     Json,
 };
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::ApiError,
     app_state::AppState,
     auth::extract_user_id,
     response_types::ApiKeyResponse,
@@ -14,14 +15,13 @@ use crate::interface::web::handlers::{
 pub async fn list_api_keys(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-) -> Result<Json<Vec<ApiKeyResponse>>, (StatusCode, String)> {
+) -> Result<Json<Vec<ApiKeyResponse>>, ApiError> {
     let user_id = extract_user_id(&headers).await?;
 
     let api_keys = state
         .list_api_keys
         .execute(user_id)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        .await?;
 
     Ok(Json(api_keys.iter().map(ApiKeyResponse::from).collect()))
 }
\ No newline at end of file
@@ -1,6 +1,9 @@
 use std::sync::Arc;
 use crate::application::use_cases::*;
 use crate::application::api_key_repository::ApiKeyRepository;
+use crate::application::{PricingTable, ReadCache, PromptChange, Metrics, ReadinessProbe};
+use crate::domain::prompt::PromptEvent;
+use tokio::sync::{broadcast, watch};
 
 pub struct AppState {
     pub create_prompt: Arc<CreatePrompt>,
@@ -14,19 +17,31 @@ pub struct AppState {
     pub delete_version: Arc<DeleteVersion>,
     pub render_version: Arc<RenderVersion>,
     pub render_version_by_tag: Arc<RenderVersionByTag>,
+    pub render_batch: Arc<RenderBatch>,
+    pub compare_versions: Arc<CompareVersions>,
 
     pub create_tag: Arc<CreateTag>,
     pub delete_tag: Arc<DeleteTag>,
     pub get_version_by_tag: Arc<GetVersionByTag>,
+    pub get_tag_bundle: Arc<GetTagBundle>,
 
     pub submit_feedback: Arc<SubmitFeedback>,
     pub update_feedback: Arc<UpdateFeedback>,
     pub delete_feedback: Arc<DeleteFeedback>,
+    pub list_feedback: Arc<ListFeedback>,
 
     pub create_api_key: Arc<CreateApiKey>,
     pub list_api_keys: Arc<ListApiKeys>,
     pub delete_api_key: Arc<DeleteApiKey>,
 
+    pub create_webhook: Arc<CreateWebhook>,
+    pub list_webhooks: Arc<ListWebhooks>,
+    pub delete_webhook: Arc<DeleteWebhook>,
+    pub list_webhook_deliveries: Arc<ListWebhookDeliveries>,
+    pub redeliver_webhook_delivery: Arc<RedeliverWebhookDelivery>,
+
+    pub list_audit_entries: Arc<ListAuditEntries>,
+
     pub create_improvement_suggestion: Arc<CreateImprovementSuggestion>,
     pub accept_improvement_suggestion: Arc<AcceptImprovementSuggestion>,
     pub decline_improvement_suggestion: Arc<DeclineImprovementSuggestion>,
@@ -34,4 +49,26 @@ pub struct AppState {
     pub analyze_feedback_and_suggest: Arc<AnalyzeFeedbackAndSuggest>,
 
     pub api_key_repository: Arc<dyn ApiKeyRepository>,
+    pub pricing: Arc<PricingTable>,
+    pub read_cache: Option<Arc<dyn ReadCache>>,
+    pub prompt_changes: broadcast::Sender<PromptChange>,
+    pub prompt_events: broadcast::Sender<PromptEvent>,
+    pub metrics: Arc<Metrics>,
+    /// Bearer token for the operational endpoints; `None` turns them off.
+    pub metrics_token: Option<String>,
+    /// Dependencies `/readyz` checks; empty when running on in-memory storage.
+    pub readiness_probes: Vec<Arc<dyn ReadinessProbe>>,
+    /// Turns `true` once the server starts draining for shutdown.
+    pub shutdown: watch::Receiver<bool>,
+}
+
+impl AppState {
+    /// Resolves when the server starts shutting down, so long-lived streams
+    /// can end instead of holding the drain open.
+    pub fn shutdown_signal(&self) -> impl Future<Output = ()> + Send + use<> {
+        let mut shutdown = self.shutdown.clone();
+        async move {
+            let _ = shutdown.wait_for(|draining| *draining).await;
+        }
+    }
 }
\ No newline at end of file
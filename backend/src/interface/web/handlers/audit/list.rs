@@ -0,0 +1,153 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::{Path, Query, State},
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use futures_util::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::application::AuditQuery;
+use crate::domain::audit::AuditAction;
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    auth::extract_user_id_with_api_key,
+    response_types::AuditEntryResponse,
+    uuid_helpers::parse_uuid,
+};
+
+const DEFAULT_PAGE_SIZE: i64 = 100;
+/// Page size used internally while streaming an export.
+const EXPORT_PAGE_SIZE: i64 = 500;
+const NDJSON: &str = "application/x-ndjson";
+
+#[derive(Deserialize)]
+pub struct AuditLogQuery {
+    pub prompt_id: Option<String>,
+    pub action: Option<String>,
+    /// RFC 3339; inclusive.
+    pub since: Option<String>,
+    /// RFC 3339; exclusive.
+    pub until: Option<String>,
+    /// `next_cursor` from the previous page.
+    pub after: Option<String>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct AuditLogResponse {
+    pub entries: Vec<AuditEntryResponse>,
+    /// Pass as `after` to fetch the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+/// The caller's audit log, oldest first. With `Accept: application/x-ndjson`
+/// every matching entry is streamed one per line and `limit` is ignored.
+pub async fn list_audit_entries(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Response, ApiError> {
+    let prompt_id = query.prompt_id.as_deref().map(|id| parse_uuid(id, "prompt_id")).transpose()?;
+    respond(state, headers, query, prompt_id).await
+}
+
+/// One prompt's history; still available after the prompt is deleted.
+pub async fn list_prompt_audit_entries(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(prompt_id): Path<String>,
+    Query(query): Query<AuditLogQuery>,
+) -> Result<Response, ApiError> {
+    let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    respond(state, headers, query, Some(prompt_uuid)).await
+}
+
+async fn respond(
+    state: Arc<AppState>,
+    headers: HeaderMap,
+    query: AuditLogQuery,
+    prompt_id: Option<Uuid>,
+) -> Result<Response, ApiError> {
+    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+
+    let stream_ndjson = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains(NDJSON));
+
+    let audit_query = AuditQuery {
+        user_id,
+        prompt_id,
+        action: query.action.as_deref()
+            .map(|action| AuditAction::from_str(action).map_err(|e| ApiError::validation("action", e)))
+            .transpose()?,
+        since: query.since.as_deref().map(|since| parse_timestamp(since, "since")).transpose()?,
+        until: query.until.as_deref().map(|until| parse_timestamp(until, "until")).transpose()?,
+        after: query.after.as_deref().map(|after| parse_uuid(after, "after")).transpose()?,
+        limit: if stream_ndjson { EXPORT_PAGE_SIZE } else { query.limit.unwrap_or(DEFAULT_PAGE_SIZE) },
+    };
+
+    // Validates the query before any bytes are sent, so a bad export
+    // request still gets a JSON error.
+    let first_page = state.list_audit_entries.execute(&audit_query).await?;
+
+    if !stream_ndjson {
+        let next_cursor = (first_page.len() as i64 == audit_query.limit)
+            .then(|| first_page.last().map(|entry| entry.id().to_string()))
+            .flatten();
+        let entries = first_page.iter().map(AuditEntryResponse::from).collect();
+        return Ok(Json(AuditLogResponse { entries, next_cursor }).into_response());
+    }
+
+    // Each step yields the page in hand and fetches the next one after it,
+    // stopping after a short page.
+    let list_audit_entries = state.list_audit_entries.clone();
+    let pages = stream::unfold((Some(Ok(first_page)), audit_query), move |(page, mut audit_query)| {
+        let list_audit_entries = list_audit_entries.clone();
+        async move {
+            let page = page?;
+            let next = match &page {
+                Ok(entries) if entries.len() as i64 == EXPORT_PAGE_SIZE => {
+                    audit_query.after = entries.last().map(|entry| entry.id());
+                    Some(list_audit_entries.execute(&audit_query).await)
+                }
+                _ => None,
+            };
+            Some((page, (next, audit_query)))
+        }
+    });
+
+    // A failed page aborts the body, so a truncated export is not mistaken
+    // for a complete one.
+    let lines = pages.flat_map(|page| {
+        let lines: Vec<std::io::Result<Bytes>> = match page {
+            Ok(entries) => entries
+                .iter()
+                .map(|entry| {
+                    let mut line = serde_json::to_vec(&AuditEntryResponse::from(entry)).expect("audit entry serializes to JSON");
+                    line.push(b'\n');
+                    Ok(Bytes::from(line))
+                })
+                .collect(),
+            Err(e) => {
+                tracing::error!(error = e.message(), "Audit log export failed");
+                vec![Err(std::io::Error::other(e.message().to_string()))]
+            }
+        };
+        stream::iter(lines)
+    });
+
+    Ok(([(header::CONTENT_TYPE, NDJSON)], Body::from_stream(lines)).into_response())
+}
+
+fn parse_timestamp(value: &str, field: &str) -> Result<DateTime<Utc>, ApiError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|_| ApiError::validation(field, format!("{} must be an RFC 3339 timestamp", field)))
+}
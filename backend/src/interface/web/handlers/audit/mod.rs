@@ -0,0 +1,3 @@
+mod list;
+
+pub use list::{list_audit_entries, list_prompt_audit_entries};
@@ -1,4 +1,4 @@
-use axum::http::{HeaderMap, StatusCode};
+use axum::http::{HeaderMap, header};
 use uuid::Uuid;
 use std::sync::Arc;
 use jsonwebtoken::{decode, decode_header, DecodingKey, Validation, Algorithm};
@@ -7,8 +7,14 @@ use std::collections::HashMap;
 use std::sync::OnceLock;
 use tokio::sync::RwLock;
 
+use crate::application::AppError;
 use crate::application::api_key_repository::ApiKeyRepository;
 use crate::domain::api_key::hash_api_key;
+use sha2::{Digest, Sha256};
+use crate::domain::actor::Actor;
+use crate::interface::web::handlers::api_error::ApiError;
+use crate::interface::web::request_tracing::record_user_id;
+use tracing::Instrument;
 
 #[derive(Debug, Serialize, Deserialize)]
 struct SupabaseClaims {
@@ -38,7 +44,18 @@ struct JwksResponse {
     keys: Vec<Jwk>,
 }
 
+/// Sent by integrations such as the n8n node alongside their API key, so
+/// what they create is attributed to the integration rather than the key.
+pub const INTEGRATION_HEADER: &str = "x-integration";
+
 static JWKS_CACHE: OnceLock<RwLock<HashMap<String, DecodingKey>>> = OnceLock::new();
+static DEV_USER: OnceLock<Uuid> = OnceLock::new();
+
+/// Dev mode only: requests without an authorization header act as `user_id`
+/// instead of being rejected.
+pub fn enable_dev_user(user_id: Uuid) {
+    let _ = DEV_USER.set(user_id);
+}
 
 fn get_cache() -> &'static RwLock<HashMap<String, DecodingKey>> {
     JWKS_CACHE.get_or_init(|| RwLock::new(HashMap::new()))
@@ -124,24 +141,27 @@ async fn get_decoding_key(kid: &str) -> Result<DecodingKey, String> {
     Ok(key)
 }
 
-async fn extract_user_id_from_jwt(headers: &HeaderMap) -> Result<Uuid, (StatusCode, String)> {
-    let auth_header = headers
-        .get("authorization")
-        .and_then(|v| v.to_str().ok())
-        .ok_or_else(|| (StatusCode::UNAUTHORIZED, "Missing authorization header".to_string()))?;
+async fn extract_user_id_from_jwt(headers: &HeaderMap) -> Result<Uuid, ApiError> {
+    let Some(auth_header) = headers.get("authorization") else {
+        return DEV_USER.get().copied()
+            .ok_or_else(|| ApiError::unauthorized("Missing authorization header"));
+    };
+    let auth_header = auth_header
+        .to_str()
+        .map_err(|_| ApiError::unauthorized("Invalid authorization header"))?;
 
     let token = auth_header
         .strip_prefix("Bearer ")
-        .ok_or_else(|| (StatusCode::UNAUTHORIZED, "Invalid authorization format".to_string()))?;
+        .ok_or_else(|| ApiError::unauthorized("Invalid authorization format"))?;
 
     let header = decode_header(token)
-        .map_err(|e| (StatusCode::UNAUTHORIZED, format!("Invalid JWT header: {}", e)))?;
+        .map_err(|e| ApiError::unauthorized(format!("Invalid JWT header: {}", e)))?;
 
     let kid = header.kid
-        .ok_or_else(|| (StatusCode::UNAUTHORIZED, "Missing kid in JWT header".to_string()))?;
+        .ok_or_else(|| ApiError::unauthorized("Missing kid in JWT header"))?;
 
     let key = get_decoding_key(&kid).await
-        .map_err(|e| (StatusCode::UNAUTHORIZED, format!("Failed to get decoding key: {}", e)))?;
+        .map_err(|e| ApiError::unauthorized(format!("Failed to get decoding key: {}", e)))?;
 
     let alg = header.alg;
     let mut validation = Validation::new(alg);
@@ -149,41 +169,80 @@ async fn extract_user_id_from_jwt(headers: &HeaderMap) -> Result<Uuid, (StatusCo
     validation.set_audience(&["authenticated"]);
 
     let token_data = decode::<SupabaseClaims>(token, &key, &validation)
-        .map_err(|e| (StatusCode::UNAUTHORIZED, format!("Invalid JWT: {}", e)))?;
+        .map_err(|e| ApiError::unauthorized(format!("Invalid JWT: {}", e)))?;
 
     Uuid::parse_str(&token_data.claims.sub)
-        .map_err(|e| (StatusCode::UNAUTHORIZED, format!("Invalid user ID in token: {}", e)))
+        .map_err(|e| ApiError::unauthorized(format!("Invalid user ID in token: {}", e)))
 }
 
-pub async fn extract_user_id(headers: &HeaderMap) -> Result<Uuid, (StatusCode, String)> {
-    extract_user_id_from_jwt(headers).await
+/// For the operational endpoints, which expose data across accounts. They
+/// take `Authorization: Bearer <token>` with the configured metrics token
+/// and are unavailable when none is configured.
+pub fn require_metrics_token(headers: &HeaderMap, token: Option<&str>) -> Result<(), ApiError> {
+    let expected = token
+        .ok_or_else(|| AppError::Unavailable("Metrics are disabled; set METRICS_TOKEN to enable them".to_string()))?;
+    let presented = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .ok_or_else(|| ApiError::unauthorized("Missing metrics token"))?;
+
+    // Comparing digests keeps the comparison time independent of the token.
+    if Sha256::digest(presented.as_bytes()) != Sha256::digest(expected.as_bytes()) {
+        return Err(ApiError::unauthorized("Invalid metrics token"));
+    }
+    Ok(())
+}
+
+/// For endpoints that need a signed-in user. An API key is recognised but
+/// not enough, so it is refused with 403 rather than asked to authenticate.
+pub async fn extract_user_id(headers: &HeaderMap) -> Result<Uuid, ApiError> {
+    if headers.contains_key("x-api-key") && !headers.contains_key("authorization") {
+        return Err(ApiError::from(AppError::Forbidden("This endpoint requires a user session; API keys are not accepted".to_string())));
+    }
+    extract_user_id_from_jwt(headers).await.inspect(|&user_id| record_user_id(user_id))
 }
 
 pub async fn extract_user_id_with_api_key(
     headers: &HeaderMap,
     api_key_repo: Arc<dyn ApiKeyRepository>,
-) -> Result<Uuid, (StatusCode, String)> {
+) -> Result<Uuid, ApiError> {
+    extract_actor_with_api_key(headers, api_key_repo).await.map(|actor| actor.user_id)
+}
+
+/// Like `extract_user_id_with_api_key`, but keeps which API key made the
+/// request so mutations can be attributed to it in the audit log.
+pub async fn extract_actor_with_api_key(
+    headers: &HeaderMap,
+    api_key_repo: Arc<dyn ApiKeyRepository>,
+) -> Result<Actor, ApiError> {
     if headers.contains_key("x-api-key") {
         let api_key = headers
             .get("x-api-key")
             .and_then(|v| v.to_str().ok())
-            .ok_or_else(|| (StatusCode::UNAUTHORIZED, "Invalid x-api-key header".to_string()))?;
+            .ok_or_else(|| ApiError::unauthorized("Invalid x-api-key header"))?;
 
         let key_hash = hash_api_key(api_key);
         let api_key_entity = api_key_repo
             .find_by_key_hash(&key_hash)
             .await
-            .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, format!("Database error: {}", e)))?
-            .ok_or_else(|| (StatusCode::UNAUTHORIZED, "Invalid API key".to_string()))?;
+            .map_err(|e| ApiError::from(AppError::Internal(e)))?
+            .ok_or_else(|| ApiError::unauthorized("Invalid API key"))?;
 
         let repo_clone = api_key_repo.clone();
         let key_id = api_key_entity.id();
         tokio::spawn(async move {
             let _ = repo_clone.update_last_used(key_id).await;
-        });
+        }.in_current_span());
 
-        return Ok(api_key_entity.user_id());
+        record_user_id(api_key_entity.user_id());
+        if headers.get(INTEGRATION_HEADER).is_some_and(|v| !v.is_empty()) {
+            return Ok(Actor::integration(api_key_entity.user_id(), key_id));
+        }
+        return Ok(Actor::api_key(api_key_entity.user_id(), key_id));
     }
 
     extract_user_id_from_jwt(headers).await
+        .inspect(|&user_id| record_user_id(user_id))
+        .map(Actor::user)
 }
\ No newline at end of file
@@ -0,0 +1,3 @@
+pub mod stats;
+
+pub use stats::get_cache_stats;
@@ -0,0 +1,42 @@
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    Json,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    auth::require_metrics_token,
+};
+
+#[derive(Serialize)]
+pub struct CacheStatsResponse {
+    pub enabled: bool,
+    pub hits: u64,
+    pub misses: u64,
+    pub hit_ratio: Option<f64>,
+    pub invalidations: u64,
+    pub entries: usize,
+}
+
+pub async fn get_cache_stats(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<CacheStatsResponse>, ApiError> {
+    require_metrics_token(&headers, state.metrics_token.as_deref())?;
+
+    let stats = state.read_cache.as_ref().map(|c| c.stats()).unwrap_or_default();
+    let lookups = stats.hits + stats.misses;
+
+    Ok(Json(CacheStatsResponse {
+        enabled: state.read_cache.is_some(),
+        hits: stats.hits,
+        misses: stats.misses,
+        hit_ratio: (lookups > 0).then(|| stats.hits as f64 / lookups as f64),
+        invalidations: stats.invalidations,
+        entries: stats.entries,
+    }))
+}
@@ -0,0 +1,41 @@
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::sse::{Event, Sse},
+};
+use futures_util::stream::Stream;
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    auth::extract_user_id_with_api_key,
+    sse::{prompt_stream, PromptScope},
+};
+
+#[derive(Deserialize)]
+pub struct ChangesQuery {
+    pub prompt_id: Option<String>,
+}
+
+/// Streams `prompt.saved` / `prompt.deleted` events for the caller's prompts
+/// as they happen on any instance. A `resync` event means changes were
+/// dropped and the client should refetch what it holds.
+pub async fn stream_changes(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<ChangesQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    let scope = PromptScope::new(user_id, query.prompt_id.as_deref())?;
+
+    Ok(prompt_stream(&state, state.prompt_changes.subscribe(), scope, |change| {
+        let data = serde_json::json!({
+            "prompt_id": change.prompt_id.to_string(),
+            "kind": change.kind.as_str(),
+        });
+        Some(Event::default().event(format!("prompt.{}", change.kind.as_str())).data(data.to_string()))
+    }))
+}
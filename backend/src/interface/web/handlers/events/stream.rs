@@ -0,0 +1,46 @@
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::sse::{Event, Sse},
+};
+use futures_util::stream::Stream;
+use serde::Deserialize;
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    auth::extract_user_id_with_api_key,
+    sse::{prompt_stream, PromptScope},
+};
+use crate::application::event_payload;
+
+#[derive(Deserialize)]
+pub struct EventsQuery {
+    pub prompt_id: Option<String>,
+    /// Comma-separated event types to receive, e.g. `tag_moved,version_created`.
+    pub types: Option<String>,
+}
+
+/// Streams domain events for the caller's prompts, named by event type. A
+/// `resync` event means events were dropped and the client should refetch.
+pub async fn stream_events(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Query(query): Query<EventsQuery>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    let scope = PromptScope::new(user_id, query.prompt_id.as_deref())?;
+    let type_filter: Option<Vec<String>> = query
+        .types
+        .map(|types| types.split(',').map(|t| t.trim().to_string()).filter(|t| !t.is_empty()).collect());
+
+    Ok(prompt_stream(&state, state.prompt_events.subscribe(), scope, move |event| {
+        let kind = event.kind().as_str();
+        if type_filter.as_ref().is_some_and(|types| !types.iter().any(|t| t == kind)) {
+            return None;
+        }
+        Some(Event::default().event(kind).data(event_payload(event).to_string()))
+    }))
+}
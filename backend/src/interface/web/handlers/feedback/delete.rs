@@ -1,12 +1,14 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
+    http::{StatusCode, HeaderMap, header},
 };
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::ApiError,
     app_state::AppState,
-    auth::extract_user_id_with_api_key,
+    auth::extract_actor_with_api_key,
+    http_cache::{if_match_revision, prompt_etag},
     uuid_helpers::parse_uuid,
 };
 
@@ -14,17 +16,17 @@ pub async fn delete_feedback(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path((prompt_id, version_id, feedback_id)): Path<(String, String, String)>,
-) -> Result<StatusCode, (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+) -> Result<(StatusCode, [(header::HeaderName, String); 1]), ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    let expected_revision = if_match_revision(&headers)?;
     let version_uuid = parse_uuid(&version_id, "version_id")?;
     let feedback_uuid = parse_uuid(&feedback_id, "feedback_id")?;
 
-    state
+    let revision = state
         .delete_feedback
-        .execute(prompt_uuid, user_id, version_uuid, feedback_uuid)
-        .await
-        .map_err(|e| (StatusCode::NOT_FOUND, e))?;
+        .execute(prompt_uuid, actor, version_uuid, feedback_uuid, expected_revision)
+        .await?;
 
-    Ok(StatusCode::NO_CONTENT)
+    Ok((StatusCode::NO_CONTENT, [(header::ETAG, prompt_etag(revision))]))
 }
\ No newline at end of file
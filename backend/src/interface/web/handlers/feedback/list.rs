@@ -0,0 +1,53 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::application::use_cases::FeedbackFilter;
+use crate::domain::actor::ActorSource;
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    auth::extract_user_id_with_api_key,
+    response_types::FeedbackResponse,
+    uuid_helpers::parse_uuid,
+};
+
+#[derive(Deserialize)]
+pub struct ListFeedbackQuery {
+    pub version_id: Option<String>,
+    /// The user who submitted the feedback.
+    pub author_id: Option<String>,
+    pub api_key_id: Option<String>,
+    /// `ui`, `api_key` or `integration`.
+    pub source: Option<String>,
+}
+
+pub async fn list_feedback(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(prompt_id): Path<String>,
+    Query(query): Query<ListFeedbackQuery>,
+) -> Result<Json<Vec<FeedbackResponse>>, ApiError> {
+    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+
+    let filter = FeedbackFilter {
+        version_id: query.version_id.as_deref().map(|id| parse_uuid(id, "version_id")).transpose()?,
+        author_id: query.author_id.as_deref().map(|id| parse_uuid(id, "author_id")).transpose()?,
+        api_key_id: query.api_key_id.as_deref().map(|id| parse_uuid(id, "api_key_id")).transpose()?,
+        source: query.source.as_deref()
+            .map(|source| ActorSource::from_str(source).map_err(|e| ApiError::validation("source", e)))
+            .transpose()?,
+    };
+
+    let feedback = state
+        .list_feedback
+        .execute(prompt_uuid, user_id, &filter)
+        .await?;
+
+    Ok(Json(feedback.iter().map(FeedbackResponse::from).collect()))
+}
@@ -1,7 +1,9 @@
 pub mod submit;
 pub mod update;
 pub mod delete;
+pub mod list;
 
 pub use submit::submit_feedback;
 pub use update::update_feedback;
-pub use delete::delete_feedback;
\ No newline at end of file
+pub use delete::delete_feedback;
+pub use list::list_feedback;
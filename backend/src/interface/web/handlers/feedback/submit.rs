@@ -1,25 +1,67 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
+    http::{StatusCode, HeaderMap, header},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::{ApiError, ApiJson},
     app_state::AppState,
-    auth::extract_user_id_with_api_key,
+    auth::extract_actor_with_api_key,
+    http_cache::{if_match_revision, prompt_etag},
     uuid_helpers::parse_uuid,
 };
+use crate::application::AppError;
+use crate::application::use_cases::FeedbackInput;
+use crate::domain::error::DomainError;
+use crate::domain::prompt::{CallDetails, CallDetailsDraft};
 
 #[derive(Deserialize)]
 pub struct SubmitFeedbackRequest {
-    pub version_id: String,
+    /// Optional when `render_id` is given.
+    pub version_id: Option<String>,
+    /// `render_id` from a render response; fills in the test input and
+    /// render context from that render.
+    pub render_id: Option<String>,
     pub rating: u8,
     pub comment: Option<String>,
     pub test_input: Option<String>,
     pub test_actual_output: Option<String>,
     pub test_expected_output: Option<String>,
+    #[serde(flatten)]
+    pub call_details: CallDetailsRequest,
+}
+
+/// Details of the production call being rated, sent alongside the rating.
+#[derive(Deserialize)]
+pub struct CallDetailsRequest {
+    pub model: Option<String>,
+    pub latency_ms: Option<u64>,
+    pub input_tokens: Option<u32>,
+    pub output_tokens: Option<u32>,
+    pub cost_usd: Option<f64>,
+    pub render_context: Option<serde_json::Value>,
+    pub trace_id: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+impl TryFrom<CallDetailsRequest> for CallDetails {
+    type Error = DomainError;
+
+    fn try_from(request: CallDetailsRequest) -> Result<Self, Self::Error> {
+        CallDetails::new(CallDetailsDraft {
+            model: request.model,
+            latency_ms: request.latency_ms,
+            input_tokens: request.input_tokens,
+            output_tokens: request.output_tokens,
+            cost_usd: request.cost_usd,
+            render_context: request.render_context,
+            trace_id: request.trace_id,
+            metadata: request.metadata,
+        })
+    }
 }
 
 #[derive(Serialize)]
@@ -31,28 +73,33 @@ pub async fn submit_feedback(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(prompt_id): Path<String>,
-    Json(payload): Json<SubmitFeedbackRequest>,
-) -> Result<(StatusCode, Json<SubmitFeedbackResponse>), (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    ApiJson(payload): ApiJson<SubmitFeedbackRequest>,
+) -> Result<(StatusCode, [(header::HeaderName, String); 1], Json<SubmitFeedbackResponse>), ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
-    let version_uuid = parse_uuid(&payload.version_id, "version_id")?;
+    let expected_revision = if_match_revision(&headers)?;
+    let version_uuid = payload.version_id.as_deref().map(|id| parse_uuid(id, "version_id")).transpose()?;
+    let render_uuid = payload.render_id.as_deref().map(|id| parse_uuid(id, "render_id")).transpose()?;
+    let call_details = Some(CallDetails::try_from(payload.call_details).map_err(AppError::from)?)
+        .filter(|details| !details.is_empty());
 
-    let feedback_id = state
+    let input = FeedbackInput {
+        version_id: version_uuid,
+        render_id: render_uuid,
+        rating: payload.rating,
+        comment: payload.comment,
+        test_input: payload.test_input,
+        test_actual_output: payload.test_actual_output,
+        test_expected_output: payload.test_expected_output,
+        call_details,
+    };
+
+    let (feedback_id, revision) = state
         .submit_feedback
-        .execute(
-            prompt_uuid,
-            user_id,
-            version_uuid,
-            payload.rating,
-            payload.comment,
-            payload.test_input,
-            payload.test_actual_output,
-            payload.test_expected_output,
-        )
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
-
-    Ok((StatusCode::CREATED, Json(SubmitFeedbackResponse {
+        .execute(prompt_uuid, actor, input, expected_revision)
+        .await?;
+
+    Ok((StatusCode::CREATED, [(header::ETAG, prompt_etag(revision))], Json(SubmitFeedbackResponse {
         feedback_id: feedback_id.to_string()
     })))
 }
\ No newline at end of file
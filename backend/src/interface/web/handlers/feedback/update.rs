@@ -1,16 +1,18 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
-    Json,
+    http::{StatusCode, HeaderMap, header},
 };
 use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::{ApiError, ApiJson},
     app_state::AppState,
-    auth::extract_user_id_with_api_key,
+    auth::extract_actor_with_api_key,
+    http_cache::{if_match_revision, prompt_etag},
     uuid_helpers::parse_uuid,
 };
+use crate::application::use_cases::FeedbackUpdate;
 
 #[derive(Deserialize)]
 pub struct UpdateFeedbackRequest {
@@ -22,18 +24,25 @@ pub async fn update_feedback(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path((prompt_id, version_id, feedback_id)): Path<(String, String, String)>,
-    Json(payload): Json<UpdateFeedbackRequest>,
-) -> Result<StatusCode, (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    ApiJson(payload): ApiJson<UpdateFeedbackRequest>,
+) -> Result<(StatusCode, [(header::HeaderName, String); 1]), ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    let expected_revision = if_match_revision(&headers)?;
     let version_uuid = parse_uuid(&version_id, "version_id")?;
     let feedback_uuid = parse_uuid(&feedback_id, "feedback_id")?;
 
-    state
+    let revision = state
         .update_feedback
-        .execute(prompt_uuid, user_id, version_uuid, feedback_uuid, payload.rating, payload.comment)
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .execute(
+            prompt_uuid,
+            actor,
+            version_uuid,
+            feedback_uuid,
+            FeedbackUpdate { rating: payload.rating, comment: payload.comment },
+            expected_revision,
+        )
+        .await?;
 
-    Ok(StatusCode::OK)
+    Ok((StatusCode::OK, [(header::ETAG, prompt_etag(revision))]))
 }
\ No newline at end of file
@@ -0,0 +1,13 @@
+use axum::Json;
+use serde::Serialize;
+
+#[derive(Serialize)]
+pub struct LivenessResponse {
+    pub status: &'static str,
+}
+
+/// The process is up and serving requests. Dependencies are not checked so a
+/// database outage does not get healthy instances restarted.
+pub async fn get_liveness() -> Json<LivenessResponse> {
+    Json(LivenessResponse { status: "ok" })
+}
@@ -0,0 +1,5 @@
+pub mod live;
+pub mod ready;
+
+pub use live::get_liveness;
+pub use ready::get_readiness;
@@ -0,0 +1,53 @@
+use axum::{
+    extract::State,
+    http::StatusCode,
+    Json,
+};
+use futures_util::future::join_all;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::interface::web::handlers::app_state::AppState;
+
+/// Probes must answer well within the platform's check timeout, which is
+/// shorter than the pool's acquire timeout.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Serialize)]
+pub struct ReadinessResponse {
+    pub status: &'static str,
+    /// `ok` or the reason each dependency is not ready.
+    pub checks: BTreeMap<&'static str, String>,
+}
+
+/// 200 when every dependency is usable, 503 otherwise and while the server
+/// drains for shutdown, so load balancers stop routing to this instance.
+pub async fn get_readiness(
+    State(state): State<Arc<AppState>>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let results = join_all(state.readiness_probes.iter().map(|probe| async move {
+        let outcome = match tokio::time::timeout(PROBE_TIMEOUT, probe.check()).await {
+            Ok(outcome) => outcome,
+            Err(_) => Err("timed out".to_string()),
+        };
+        (probe.name(), outcome)
+    }))
+    .await;
+
+    let mut healthy = true;
+    let mut checks = BTreeMap::new();
+    for (name, outcome) in results {
+        healthy &= outcome.is_ok();
+        checks.insert(name, outcome.err().unwrap_or_else(|| "ok".to_string()));
+    }
+
+    let draining = *state.shutdown.borrow();
+    let (status, body_status) = match (draining, healthy) {
+        (true, _) => (StatusCode::SERVICE_UNAVAILABLE, "shutting_down"),
+        (false, false) => (StatusCode::SERVICE_UNAVAILABLE, "not_ready"),
+        (false, true) => (StatusCode::OK, "ready"),
+    };
+    (status, Json(ReadinessResponse { status: body_status, checks }))
+}
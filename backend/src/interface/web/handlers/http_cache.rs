@@ -0,0 +1,99 @@
+use axum::{
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use sha2::{Sha256, Digest};
+
+use crate::domain::prompt::PromptVersion;
+use crate::interface::web::handlers::api_error::ApiError;
+
+/// For version reads, whose content never changes under the same ETag.
+pub const CACHE_IMMUTABLE: &str = "public, max-age=31536000, immutable";
+/// For responses that may change (feedback, moving tags); caches must
+/// revalidate with `If-None-Match` before reuse.
+pub const CACHE_REVALIDATE: &str = "no-cache";
+
+/// Responses depend on the caller's credentials, so shared caches must key
+/// on them.
+const VARY: &str = "authorization, x-api-key";
+
+/// Strong ETag over the given parts.
+pub fn etag_for(parts: &[&[u8]]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update((part.len() as u64).to_be_bytes());
+        hasher.update(part);
+    }
+    format!("\"{}\"", hex::encode(hasher.finalize()))
+}
+
+/// ETag of a version's content: its id and digest, which stay the same
+/// across loads while `created_at` and feedback do not.
+pub fn version_etag(version: &PromptVersion) -> String {
+    etag_for(&[version.id().as_bytes(), version.digest().as_bytes()])
+}
+
+/// Weak comparison, as `If-None-Match` requires.
+pub fn if_none_match(headers: &HeaderMap, etag: &str) -> bool {
+    let etag = etag.trim_start_matches("W/");
+    headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| {
+            v.split(',')
+                .map(|candidate| candidate.trim().trim_start_matches("W/"))
+                .any(|candidate| candidate == etag || candidate == "*")
+        })
+}
+
+/// Responds `304 Not Modified` when the request's `If-None-Match` matches
+/// `etag`, otherwise with `body()` as JSON. Both carry the cache headers;
+/// the body is only built and serialized when it is sent.
+pub fn cached_json<T: Serialize>(
+    headers: &HeaderMap,
+    etag: String,
+    cache_control: &'static str,
+    body: impl FnOnce() -> T,
+) -> Response {
+    let mut response = if if_none_match(headers, &etag) {
+        StatusCode::NOT_MODIFIED.into_response()
+    } else {
+        Json(body()).into_response()
+    };
+
+    let response_headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&etag) {
+        response_headers.insert(header::ETAG, value);
+    }
+    response_headers.insert(header::CACHE_CONTROL, HeaderValue::from_static(cache_control));
+    response_headers.insert(header::VARY, HeaderValue::from_static(VARY));
+    response
+}
+
+/// ETag of a prompt at `revision`; mutations accept it back in `If-Match`.
+pub fn prompt_etag(revision: u64) -> String {
+    format!("\"r{}\"", revision)
+}
+
+/// Prompt revision a mutation is conditional on, from an `If-Match` header
+/// holding a `prompt_etag`. No header or `*` means unconditional.
+pub fn if_match_revision(headers: &HeaderMap) -> Result<Option<u64>, ApiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+
+    let value = value.to_str().unwrap_or_default().trim();
+    if value == "*" {
+        return Ok(None);
+    }
+
+    value
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .strip_prefix('r')
+        .and_then(|revision| revision.parse().ok())
+        .map(Some)
+        .ok_or_else(|| ApiError::bad_request("Invalid If-Match header; expected a prompt ETag").with_field("If-Match"))
+}
@@ -1,16 +1,21 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
+    http::{StatusCode, HeaderMap, header},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::{ApiError, ApiJson},
     app_state::AppState,
-    auth::extract_user_id_with_api_key,
+    auth::extract_actor_with_api_key,
+    http_cache::{if_match_revision, prompt_etag},
     uuid_helpers::parse_uuid,
 };
+use crate::application::AppError;
+use crate::application::use_cases::AcceptedVersion;
+use crate::domain::prompt::Version;
 
 #[derive(Deserialize)]
 pub struct AcceptImprovementSuggestionRequest {
@@ -27,20 +32,32 @@ pub async fn accept_improvement_suggestion(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path((prompt_id, version_id, suggestion_id)): Path<(String, String, String)>,
-    Json(payload): Json<AcceptImprovementSuggestionRequest>,
-) -> Result<(StatusCode, Json<AcceptImprovementSuggestionResponse>), (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    ApiJson(payload): ApiJson<AcceptImprovementSuggestionRequest>,
+) -> Result<(StatusCode, [(header::HeaderName, String); 1], Json<AcceptImprovementSuggestionResponse>), ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    let expected_revision = if_match_revision(&headers)?;
     let version_uuid = parse_uuid(&version_id, "version_id")?;
     let suggestion_uuid = parse_uuid(&suggestion_id, "suggestion_id")?;
 
-    let new_version_id = state
+    let accepted = AcceptedVersion {
+        version: Version::from_str(&payload.new_version).map_err(AppError::from)?,
+        changelog: payload.changelog,
+    };
+
+    let (new_version_id, revision) = state
         .accept_improvement_suggestion
-        .execute(prompt_uuid, user_id, version_uuid, suggestion_uuid, payload.new_version, payload.changelog)
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .execute(
+            prompt_uuid,
+            actor,
+            version_uuid,
+            suggestion_uuid,
+            accepted,
+            expected_revision,
+        )
+        .await?;
 
-    Ok((StatusCode::OK, Json(AcceptImprovementSuggestionResponse {
+    Ok((StatusCode::OK, [(header::ETAG, prompt_etag(revision))], Json(AcceptImprovementSuggestionResponse {
         new_version_id: new_version_id.to_string()
     })))
 }
\ No newline at end of file
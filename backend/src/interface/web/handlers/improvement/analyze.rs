@@ -1,14 +1,17 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
+    http::{StatusCode, HeaderMap, header},
     Json,
 };
 use serde::Serialize;
 use std::sync::Arc;
 
+use crate::application::AppError;
 use crate::interface::web::handlers::{
+    api_error::ApiError,
     app_state::AppState,
-    auth::extract_user_id_with_api_key,
+    auth::extract_actor_with_api_key,
+    http_cache::{if_match_revision, prompt_etag},
     uuid_helpers::parse_uuid,
 };
 
@@ -23,29 +26,28 @@ pub async fn analyze_feedback(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path((prompt_id, version_id)): Path<(String, String)>,
-) -> Result<(StatusCode, Json<AnalyzeFeedbackResponse>), (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+) -> Result<(StatusCode, [(header::HeaderName, String); 1], Json<AnalyzeFeedbackResponse>), ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    let expected_revision = if_match_revision(&headers)?;
     let version_uuid = parse_uuid(&version_id, "version_id")?;
 
-    let suggestion_id = state
+    let (suggestion_id, revision) = state
         .analyze_feedback_and_suggest
-        .execute(prompt_uuid, user_id, version_uuid)
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .execute(prompt_uuid, actor, version_uuid, expected_revision)
+        .await?;
 
     let prompt = state.get_prompt
-        .execute(prompt_uuid, user_id)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        .execute(prompt_uuid, actor.user_id)
+        .await?;
 
     let version = prompt.find_version_by_id(version_uuid)
-        .ok_or_else(|| (StatusCode::NOT_FOUND, "Version not found".to_string()))?;
+        .ok_or_else(|| ApiError::from(AppError::not_found("Version not found")))?;
 
     let suggestion = version.find_suggestion(suggestion_id)
-        .ok_or_else(|| (StatusCode::INTERNAL_SERVER_ERROR, "Suggestion not found after creation".to_string()))?;
+        .ok_or_else(|| ApiError::from(AppError::Internal("Suggestion not found after creation".to_string())))?;
 
-    Ok((StatusCode::CREATED, Json(AnalyzeFeedbackResponse {
+    Ok((StatusCode::CREATED, [(header::ETAG, prompt_etag(revision))], Json(AnalyzeFeedbackResponse {
         suggestion_id: suggestion_id.to_string(),
         suggested_content: suggestion.suggested_content().to_string(),
         ai_rationale: suggestion.ai_rationale().to_string(),
@@ -1,14 +1,16 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
+    http::{StatusCode, HeaderMap, header},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::{ApiError, ApiJson},
     app_state::AppState,
-    auth::extract_user_id_with_api_key,
+    auth::extract_actor_with_api_key,
+    http_cache::{if_match_revision, prompt_etag},
     uuid_helpers::parse_uuid,
 };
 
@@ -28,19 +30,19 @@ pub async fn create_improvement_suggestion(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(prompt_id): Path<String>,
-    Json(payload): Json<CreateImprovementSuggestionRequest>,
-) -> Result<(StatusCode, Json<CreateImprovementSuggestionResponse>), (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    ApiJson(payload): ApiJson<CreateImprovementSuggestionRequest>,
+) -> Result<(StatusCode, [(header::HeaderName, String); 1], Json<CreateImprovementSuggestionResponse>), ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    let expected_revision = if_match_revision(&headers)?;
     let version_uuid = parse_uuid(&payload.version_id, "version_id")?;
 
-    let suggestion_id = state
+    let (suggestion_id, revision) = state
         .create_improvement_suggestion
-        .execute(prompt_uuid, user_id, version_uuid, payload.suggested_content, payload.ai_rationale)
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .execute(prompt_uuid, actor, version_uuid, payload.suggested_content, payload.ai_rationale, expected_revision)
+        .await?;
 
-    Ok((StatusCode::CREATED, Json(CreateImprovementSuggestionResponse {
+    Ok((StatusCode::CREATED, [(header::ETAG, prompt_etag(revision))], Json(CreateImprovementSuggestionResponse {
         suggestion_id: suggestion_id.to_string()
     })))
 }
\ No newline at end of file
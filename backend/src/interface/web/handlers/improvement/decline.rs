@@ -1,14 +1,15 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
-    Json,
+    http::{StatusCode, HeaderMap, header},
 };
 use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::{ApiError, ApiJson},
     app_state::AppState,
-    auth::extract_user_id_with_api_key,
+    auth::extract_actor_with_api_key,
+    http_cache::{if_match_revision, prompt_etag},
     uuid_helpers::parse_uuid,
 };
 
@@ -21,18 +22,18 @@ pub async fn decline_improvement_suggestion(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path((prompt_id, version_id, suggestion_id)): Path<(String, String, String)>,
-    Json(payload): Json<DeclineImprovementSuggestionRequest>,
-) -> Result<StatusCode, (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    ApiJson(payload): ApiJson<DeclineImprovementSuggestionRequest>,
+) -> Result<(StatusCode, [(header::HeaderName, String); 1]), ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    let expected_revision = if_match_revision(&headers)?;
     let version_uuid = parse_uuid(&version_id, "version_id")?;
     let suggestion_uuid = parse_uuid(&suggestion_id, "suggestion_id")?;
 
-    state
+    let revision = state
         .decline_improvement_suggestion
-        .execute(prompt_uuid, user_id, version_uuid, suggestion_uuid, payload.reason)
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .execute(prompt_uuid, actor, version_uuid, suggestion_uuid, payload.reason, expected_revision)
+        .await?;
 
-    Ok(StatusCode::OK)
+    Ok((StatusCode::OK, [(header::ETAG, prompt_etag(revision))]))
 }
\ No newline at end of file
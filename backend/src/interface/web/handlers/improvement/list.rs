@@ -1,11 +1,12 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
+    http::HeaderMap,
     Json,
 };
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::ApiError,
     app_state::AppState,
     auth::extract_user_id_with_api_key,
     response_types::ImprovementSuggestionResponse,
@@ -16,7 +17,7 @@ pub async fn list_suggestions_for_version(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path((prompt_id, version_id)): Path<(String, String)>,
-) -> Result<Json<Vec<ImprovementSuggestionResponse>>, (StatusCode, String)> {
+) -> Result<Json<Vec<ImprovementSuggestionResponse>>, ApiError> {
     let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
     let version_uuid = parse_uuid(&version_id, "version_id")?;
@@ -24,8 +25,7 @@ pub async fn list_suggestions_for_version(
     let suggestions = state
         .get_suggestions_for_version
         .execute(prompt_uuid, user_id, version_uuid)
-        .await
-        .map_err(|e| (StatusCode::NOT_FOUND, e))?;
+        .await?;
 
     Ok(Json(suggestions.iter().map(ImprovementSuggestionResponse::from).collect()))
 }
\ No newline at end of file
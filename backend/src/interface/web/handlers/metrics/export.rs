@@ -0,0 +1,30 @@
+use axum::{
+    extract::State,
+    http::{HeaderMap, header},
+    response::IntoResponse,
+};
+use std::sync::Arc;
+
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    auth::require_metrics_token,
+};
+
+const PROMETHEUS_TEXT: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Prometheus scrape endpoint, behind the metrics token.
+pub async fn get_metrics(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, ApiError> {
+    require_metrics_token(&headers, state.metrics_token.as_deref())?;
+    Ok(get_internal_metrics(State(state)).await)
+}
+
+/// Prometheus scrape endpoint on the metrics listener, which is only
+/// reachable from the private network and so needs no token.
+pub async fn get_internal_metrics(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let cache = state.read_cache.as_ref().map(|cache| cache.stats());
+    ([(header::CONTENT_TYPE, PROMETHEUS_TEXT)], state.metrics.encode(cache))
+}
@@ -0,0 +1,5 @@
+pub mod export;
+pub mod track;
+
+pub use export::{get_internal_metrics, get_metrics};
+pub use track::track_requests;
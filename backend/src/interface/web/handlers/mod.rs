@@ -1,6 +1,9 @@
+pub mod api_error;
 pub mod app_state;
 pub mod auth;
+pub mod http_cache;
 pub mod response_types;
+pub mod sse;
 pub mod uuid_helpers;
 pub mod prompt;
 pub mod version;
@@ -8,7 +11,14 @@ pub mod tag;
 pub mod feedback;
 pub mod api_key;
 pub mod improvement;
+pub mod cache;
+pub mod changes;
+pub mod events;
+pub mod webhook;
+pub mod audit;
+pub mod health;
+pub mod metrics;
 
 pub use app_state::AppState;
-pub use auth::extract_user_id;
+pub use auth::{extract_user_id, enable_dev_user};
 pub use response_types::*;
\ No newline at end of file
@@ -1,12 +1,12 @@
 use axum::{
     extract::State,
-    http::{StatusCode, HeaderMap},
+    http::{StatusCode, HeaderMap, header},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
-use crate::interface::web::handlers::{app_state::AppState, auth::extract_user_id_with_api_key};
+use crate::interface::web::handlers::{api_error::{ApiError, ApiJson}, app_state::AppState, auth::extract_actor_with_api_key, http_cache::prompt_etag};
 use crate::domain::prompt::PromptType;
 
 #[derive(Deserialize)]
@@ -14,6 +14,7 @@ pub struct CreatePromptRequest {
     pub name: String,
     pub description: Option<String>,
     pub prompt_type: String,
+    pub max_render_tokens: Option<u32>,
 }
 
 #[derive(Serialize)]
@@ -24,23 +25,22 @@ pub struct CreatePromptResponse {
 pub async fn create_prompt(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-    Json(payload): Json<CreatePromptRequest>,
-) -> Result<(StatusCode, Json<CreatePromptResponse>), (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    ApiJson(payload): ApiJson<CreatePromptRequest>,
+) -> Result<(StatusCode, [(header::HeaderName, String); 1], Json<CreatePromptResponse>), ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
 
     let prompt_type = match payload.prompt_type.as_str() {
         "system" => PromptType::System,
         "user" => PromptType::User,
-        _ => return Err((StatusCode::BAD_REQUEST, "Invalid prompt_type. Must be 'system' or 'user'".to_string())),
+        _ => return Err(ApiError::validation("prompt_type", "Invalid prompt_type. Must be 'system' or 'user'")),
     };
 
-    let id = state
+    let (id, revision) = state
         .create_prompt
-        .execute(user_id, payload.name, payload.description, prompt_type)
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .execute(actor, payload.name, payload.description, prompt_type, payload.max_render_tokens)
+        .await?;
 
-    Ok((StatusCode::CREATED, Json(CreatePromptResponse {
+    Ok((StatusCode::CREATED, [(header::ETAG, prompt_etag(revision))], Json(CreatePromptResponse {
         id: id.to_string()
     })))
 }
\ No newline at end of file
@@ -5,8 +5,10 @@ use axum::{
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::ApiError,
     app_state::AppState,
-    auth::extract_user_id_with_api_key,
+    auth::extract_actor_with_api_key,
+    http_cache::if_match_revision,
     uuid_helpers::parse_uuid,
 };
 
@@ -14,15 +16,15 @@ pub async fn delete_prompt(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(prompt_id): Path<String>,
-) -> Result<StatusCode, (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+) -> Result<StatusCode, ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    let expected_revision = if_match_revision(&headers)?;
 
     state
         .delete_prompt
-        .execute(prompt_uuid, user_id)
-        .await
-        .map_err(|e| (StatusCode::NOT_FOUND, e))?;
+        .execute(prompt_uuid, actor, expected_revision)
+        .await?;
 
     Ok(StatusCode::NO_CONTENT)
 }
\ No newline at end of file
@@ -1,13 +1,15 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
+    http::{HeaderMap, header},
     Json,
 };
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::ApiError,
     app_state::AppState,
     auth::extract_user_id_with_api_key,
+    http_cache::prompt_etag,
     response_types::PromptResponse,
     uuid_helpers::parse_uuid,
 };
@@ -16,15 +18,17 @@ pub async fn get_prompt(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(prompt_id): Path<String>,
-) -> Result<Json<PromptResponse>, (StatusCode, String)> {
+) -> Result<([(header::HeaderName, String); 1], Json<PromptResponse>), ApiError> {
     let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
 
     let prompt = state
         .get_prompt
         .execute(prompt_uuid, user_id)
-        .await
-        .map_err(|e| (StatusCode::NOT_FOUND, e))?;
+        .await?;
 
-    Ok(Json(PromptResponse::from(&prompt)))
+    Ok((
+        [(header::ETAG, prompt_etag(prompt.revision()))],
+        Json(PromptResponse::new(&prompt, &state.pricing)),
+    ))
 }
\ No newline at end of file
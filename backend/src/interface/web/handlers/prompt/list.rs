@@ -1,11 +1,12 @@
 use axum::{
     extract::State,
-    http::{StatusCode, HeaderMap},
+    http::HeaderMap,
     Json,
 };
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::ApiError,
     app_state::AppState,
     auth::extract_user_id_with_api_key,
     response_types::PromptResponse,
@@ -14,14 +15,13 @@ use crate::interface::web::handlers::{
 pub async fn list_prompts(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
-) -> Result<Json<Vec<PromptResponse>>, (StatusCode, String)> {
+) -> Result<Json<Vec<PromptResponse>>, ApiError> {
     let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
 
     let prompts = state
         .list_prompts
         .execute(user_id)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        .await?;
 
-    Ok(Json(prompts.iter().map(PromptResponse::from).collect()))
+    Ok(Json(prompts.iter().map(|p| PromptResponse::new(p, &state.pricing)).collect()))
 }
\ No newline at end of file
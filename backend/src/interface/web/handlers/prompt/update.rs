@@ -1,14 +1,15 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
-    Json,
+    http::{StatusCode, HeaderMap, header},
 };
 use serde::Deserialize;
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::{ApiError, ApiJson},
     app_state::AppState,
-    auth::extract_user_id_with_api_key,
+    auth::extract_actor_with_api_key,
+    http_cache::{if_match_revision, prompt_etag},
     uuid_helpers::parse_uuid,
 };
 
@@ -16,22 +17,23 @@ use crate::interface::web::handlers::{
 pub struct UpdatePromptRequest {
     pub name: Option<String>,
     pub description: Option<Option<String>>,
+    pub max_render_tokens: Option<Option<u32>>,
 }
 
 pub async fn update_prompt(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(prompt_id): Path<String>,
-    Json(payload): Json<UpdatePromptRequest>,
-) -> Result<StatusCode, (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    ApiJson(payload): ApiJson<UpdatePromptRequest>,
+) -> Result<(StatusCode, [(header::HeaderName, String); 1]), ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    let expected_revision = if_match_revision(&headers)?;
 
-    state
+    let revision = state
         .update_prompt
-        .execute(prompt_uuid, user_id, payload.name, payload.description)
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .execute(prompt_uuid, actor, payload.name, payload.description, payload.max_render_tokens, expected_revision)
+        .await?;
 
-    Ok(StatusCode::OK)
+    Ok((StatusCode::OK, [(header::ETAG, prompt_etag(revision))]))
 }
\ No newline at end of file
@@ -1,7 +1,11 @@
 use serde::Serialize;
 use chrono::{DateTime, Utc};
+use crate::application::{PricingTable, CostEstimate};
+use crate::domain::actor::Actor;
 use crate::domain::api_key::ApiKey;
-use crate::domain::prompt::{Prompt, PromptVersion, Tag, Feedback, PromptType, ContentType, ImprovementSuggestion, SuggestionStatus};
+use crate::domain::audit::AuditEntry;
+use crate::domain::webhook::{Webhook, WebhookDelivery};
+use crate::domain::prompt::{Prompt, PromptVersion, Tag, Feedback, PromptType, ContentType, ImprovementSuggestion, SuggestionStatus, ModelConfig, ToolDefinition, OutputSchema, TokenCount};
 
 #[derive(Serialize)]
 pub struct PromptResponse {
@@ -10,8 +14,10 @@ pub struct PromptResponse {
     pub name: String,
     pub description: Option<String>,
     pub prompt_type: String,
+    pub max_render_tokens: Option<u32>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub revision: u64,
     pub versions: Vec<VersionResponse>,
     pub tags: Vec<TagResponse>,
 }
@@ -25,6 +31,12 @@ pub struct VersionResponse {
     pub content_type: String,
     pub variables: Option<Vec<String>>,
     pub changelog: Option<String>,
+    pub model_config: Option<ModelConfigResponse>,
+    pub tools: Vec<ToolDefinitionResponse>,
+    pub output_schema: Option<OutputSchemaResponse>,
+    pub token_count: Option<TokenCountResponse>,
+    pub estimated_input_cost: Option<CostEstimateResponse>,
+    pub created_by: Option<AuthorResponse>,
     pub created_at: DateTime<Utc>,
     pub average_rating: Option<f64>,
     pub feedback_count: usize,
@@ -32,6 +44,42 @@ pub struct VersionResponse {
     pub improvement_suggestions: Vec<ImprovementSuggestionResponse>,
 }
 
+#[derive(Serialize)]
+pub struct ModelConfigResponse {
+    pub provider: String,
+    pub model: String,
+    pub temperature: Option<f64>,
+    pub top_p: Option<f64>,
+    pub max_tokens: Option<u32>,
+    pub stop_sequences: Vec<String>,
+    pub seed: Option<i64>,
+}
+
+#[derive(Serialize)]
+pub struct ToolDefinitionResponse {
+    pub name: String,
+    pub description: Option<String>,
+    pub parameters: serde_json::Value,
+}
+
+#[derive(Serialize)]
+pub struct OutputSchemaResponse {
+    pub name: String,
+    pub schema: serde_json::Value,
+}
+
+#[derive(Serialize)]
+pub struct TokenCountResponse {
+    pub tokens: u32,
+    pub encoding: String,
+}
+
+#[derive(Serialize)]
+pub struct CostEstimateResponse {
+    pub input_cost_usd: f64,
+    pub input_per_million_tokens: f64,
+}
+
 #[derive(Serialize)]
 pub struct TagResponse {
     pub id: String,
@@ -45,19 +93,56 @@ pub struct TestScenarioResponse {
     pub input: String,
     pub actual_output: String,
     pub expected_output: Option<String>,
+    pub schema_violations: Option<Vec<String>>,
 }
 
 #[derive(Serialize)]
 pub struct FeedbackResponse {
     pub id: String,
+    pub version_id: String,
     pub rating: u8,
     pub comment: Option<String>,
     pub test_scenario: Option<TestScenarioResponse>,
+    pub call_details: Option<CallDetailsResponse>,
+    pub created_by: Option<AuthorResponse>,
     pub created_at: DateTime<Utc>,
 }
 
-impl From<&Prompt> for PromptResponse {
-    fn from(prompt: &Prompt) -> Self {
+#[derive(Serialize)]
+pub struct CallDetailsResponse {
+    pub model: Option<String>,
+    pub latency_ms: Option<u64>,
+    pub input_tokens: Option<u32>,
+    pub output_tokens: Option<u32>,
+    pub cost_usd: Option<f64>,
+    pub render_context: Option<serde_json::Value>,
+    pub trace_id: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+    pub render_id: Option<String>,
+}
+
+/// Who created a version, feedback or suggestion; `null` when it predates
+/// authorship tracking.
+#[derive(Serialize)]
+pub struct AuthorResponse {
+    pub user_id: String,
+    pub api_key_id: Option<String>,
+    /// `ui`, `api_key` or `integration`.
+    pub source: String,
+}
+
+impl From<Actor> for AuthorResponse {
+    fn from(actor: Actor) -> Self {
+        Self {
+            user_id: actor.user_id.to_string(),
+            api_key_id: actor.api_key_id.map(|id| id.to_string()),
+            source: actor.source.as_str().to_string(),
+        }
+    }
+}
+
+impl PromptResponse {
+    pub fn new(prompt: &Prompt, pricing: &PricingTable) -> Self {
         let prompt_type = match prompt.prompt_type() {
             PromptType::System => "system",
             PromptType::User => "user",
@@ -69,16 +154,18 @@ impl From<&Prompt> for PromptResponse {
             name: prompt.name().to_string(),
             description: prompt.description().map(|s| s.to_string()),
             prompt_type: prompt_type.to_string(),
+            max_render_tokens: prompt.max_render_tokens(),
             created_at: prompt.created_at(),
             updated_at: prompt.updated_at(),
-            versions: prompt.versions().iter().map(VersionResponse::from).collect(),
+            revision: prompt.revision(),
+            versions: prompt.versions().iter().map(|v| VersionResponse::new(v, pricing)).collect(),
             tags: prompt.tags().iter().map(TagResponse::from).collect(),
         }
     }
 }
 
-impl From<&PromptVersion> for VersionResponse {
-    fn from(version: &PromptVersion) -> Self {
+impl VersionResponse {
+    pub fn new(version: &PromptVersion, pricing: &PricingTable) -> Self {
         let content_type = match version.content_type() {
             ContentType::Static => "static",
             ContentType::Template => "template",
@@ -92,6 +179,16 @@ impl From<&PromptVersion> for VersionResponse {
             content_type: content_type.to_string(),
             variables: version.variables().map(|v| v.to_vec()),
             changelog: version.changelog().map(|s| s.to_string()),
+            model_config: version.model_config().map(ModelConfigResponse::from),
+            tools: version.tools().iter().map(ToolDefinitionResponse::from).collect(),
+            output_schema: version.output_schema().map(OutputSchemaResponse::from),
+            token_count: version.token_count().map(TokenCountResponse::from),
+            estimated_input_cost: version
+                .token_count()
+                .and_then(|t| pricing.estimate_input_cost(version.model_config(), t.tokens()))
+                .as_ref()
+                .map(CostEstimateResponse::from),
+            created_by: version.created_by().map(AuthorResponse::from),
             created_at: version.created_at(),
             average_rating: version.average_rating(),
             feedback_count: version.feedbacks().len(),
@@ -101,6 +198,57 @@ impl From<&PromptVersion> for VersionResponse {
     }
 }
 
+impl From<&ModelConfig> for ModelConfigResponse {
+    fn from(config: &ModelConfig) -> Self {
+        Self {
+            provider: config.provider().as_str().to_string(),
+            model: config.model().to_string(),
+            temperature: config.temperature(),
+            top_p: config.top_p(),
+            max_tokens: config.max_tokens(),
+            stop_sequences: config.stop_sequences().to_vec(),
+            seed: config.seed(),
+        }
+    }
+}
+
+impl From<&ToolDefinition> for ToolDefinitionResponse {
+    fn from(tool: &ToolDefinition) -> Self {
+        Self {
+            name: tool.name().to_string(),
+            description: tool.description().map(|s| s.to_string()),
+            parameters: tool.parameters().clone(),
+        }
+    }
+}
+
+impl From<&OutputSchema> for OutputSchemaResponse {
+    fn from(schema: &OutputSchema) -> Self {
+        Self {
+            name: schema.name().to_string(),
+            schema: schema.schema().clone(),
+        }
+    }
+}
+
+impl From<&TokenCount> for TokenCountResponse {
+    fn from(token_count: &TokenCount) -> Self {
+        Self {
+            tokens: token_count.tokens(),
+            encoding: token_count.encoding().to_string(),
+        }
+    }
+}
+
+impl From<&CostEstimate> for CostEstimateResponse {
+    fn from(estimate: &CostEstimate) -> Self {
+        Self {
+            input_cost_usd: estimate.input_cost_usd,
+            input_per_million_tokens: estimate.input_per_million_tokens,
+        }
+    }
+}
+
 impl From<&Tag> for TagResponse {
     fn from(tag: &Tag) -> Self {
         Self {
@@ -116,13 +264,27 @@ impl From<&Feedback> for FeedbackResponse {
     fn from(feedback: &Feedback) -> Self {
         Self {
             id: feedback.id().to_string(),
+            version_id: feedback.version_id().to_string(),
             rating: feedback.rating(),
             comment: feedback.comment().map(|s| s.to_string()),
             test_scenario: feedback.test_scenario().map(|ts| TestScenarioResponse {
                 input: ts.input().to_string(),
                 actual_output: ts.actual_output().to_string(),
                 expected_output: ts.expected_output().map(|s| s.to_string()),
+                schema_violations: ts.schema_violations().map(|v| v.to_vec()),
+            }),
+            call_details: feedback.call_details().map(|cd| CallDetailsResponse {
+                model: cd.model().map(|s| s.to_string()),
+                latency_ms: cd.latency_ms(),
+                input_tokens: cd.input_tokens(),
+                output_tokens: cd.output_tokens(),
+                cost_usd: cd.cost_usd(),
+                render_context: cd.render_context().cloned(),
+                trace_id: cd.trace_id().map(|s| s.to_string()),
+                metadata: cd.metadata().cloned(),
+                render_id: cd.render_id().map(|id| id.to_string()),
             }),
+            created_by: feedback.created_by().map(AuthorResponse::from),
             created_at: feedback.created_at(),
         }
     }
@@ -151,6 +313,60 @@ impl From<&ApiKey> for ApiKeyResponse {
     }
 }
 
+#[derive(Serialize)]
+pub struct WebhookResponse {
+    pub id: String,
+    pub prompt_id: Option<String>,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub is_active: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<&Webhook> for WebhookResponse {
+    fn from(webhook: &Webhook) -> Self {
+        Self {
+            id: webhook.id().to_string(),
+            prompt_id: webhook.prompt_id().map(|id| id.to_string()),
+            url: webhook.url().to_string(),
+            event_types: webhook.event_types().to_vec(),
+            is_active: webhook.is_active(),
+            created_at: webhook.created_at(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct WebhookDeliveryResponse {
+    pub id: String,
+    pub event_id: String,
+    pub event_type: String,
+    pub status: String,
+    pub attempts: u32,
+    pub response_status: Option<u16>,
+    pub last_error: Option<String>,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: &WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id().to_string(),
+            event_id: delivery.event_id().to_string(),
+            event_type: delivery.event_type().to_string(),
+            status: delivery.status().as_str().to_string(),
+            attempts: delivery.attempts(),
+            response_status: delivery.response_status(),
+            last_error: delivery.last_error().map(|e| e.to_string()),
+            next_attempt_at: delivery.next_attempt_at(),
+            created_at: delivery.created_at(),
+            updated_at: delivery.updated_at(),
+        }
+    }
+}
+
 #[derive(Serialize)]
 pub struct ImprovementSuggestionResponse {
     pub id: String,
@@ -161,6 +377,7 @@ pub struct ImprovementSuggestionResponse {
     pub created_at: DateTime<Utc>,
     pub resolved_at: Option<DateTime<Utc>>,
     pub resulting_version_id: Option<String>,
+    pub created_by: Option<AuthorResponse>,
 }
 
 impl From<&ImprovementSuggestion> for ImprovementSuggestionResponse {
@@ -180,6 +397,40 @@ impl From<&ImprovementSuggestion> for ImprovementSuggestionResponse {
             created_at: suggestion.created_at(),
             resolved_at: suggestion.resolved_at(),
             resulting_version_id: suggestion.resulting_version_id().map(|id| id.to_string()),
+            created_by: suggestion.created_by().map(AuthorResponse::from),
         }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Serialize)]
+pub struct AuditEntryResponse {
+    pub id: String,
+    pub occurred_at: DateTime<Utc>,
+    pub actor_user_id: String,
+    /// Set when the change was made with an API key rather than a session.
+    pub api_key_id: Option<String>,
+    /// `ui`, `api_key` or `integration`.
+    pub source: String,
+    pub action: String,
+    pub prompt_id: Option<String>,
+    pub target_id: Option<String>,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+impl From<&AuditEntry> for AuditEntryResponse {
+    fn from(entry: &AuditEntry) -> Self {
+        Self {
+            id: entry.id().to_string(),
+            occurred_at: entry.occurred_at(),
+            actor_user_id: entry.actor().user_id.to_string(),
+            api_key_id: entry.actor().api_key_id.map(|id| id.to_string()),
+            source: entry.actor().source.as_str().to_string(),
+            action: entry.action().as_str().to_string(),
+            prompt_id: entry.prompt_id().map(|id| id.to_string()),
+            target_id: entry.target_id().map(|id| id.to_string()),
+            before: entry.before().cloned(),
+            after: entry.after().cloned(),
+        }
+    }
+}
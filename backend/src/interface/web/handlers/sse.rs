@@ -0,0 +1,94 @@
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::stream::{self, Stream, StreamExt};
+use std::convert::Infallible;
+use tokio::sync::broadcast::{self, error::RecvError};
+use uuid::Uuid;
+
+use crate::application::PromptChange;
+use crate::domain::prompt::PromptEvent;
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    uuid_helpers::parse_uuid,
+};
+
+/// A message broadcast about one user's prompt.
+pub trait PromptMessage: Clone + Send + 'static {
+    fn user_id(&self) -> Uuid;
+    fn prompt_id(&self) -> Uuid;
+}
+
+impl PromptMessage for PromptEvent {
+    fn user_id(&self) -> Uuid {
+        PromptEvent::user_id(self)
+    }
+
+    fn prompt_id(&self) -> Uuid {
+        PromptEvent::prompt_id(self)
+    }
+}
+
+impl PromptMessage for PromptChange {
+    fn user_id(&self) -> Uuid {
+        self.user_id
+    }
+
+    fn prompt_id(&self) -> Uuid {
+        self.prompt_id
+    }
+}
+
+/// The messages a subscriber may see: those about the caller's prompts,
+/// optionally narrowed to one prompt.
+#[derive(Debug, Clone, Copy)]
+pub struct PromptScope {
+    user_id: Uuid,
+    prompt_id: Option<Uuid>,
+}
+
+impl PromptScope {
+    /// `prompt_id` is the raw `prompt_id` query parameter.
+    pub fn new(user_id: Uuid, prompt_id: Option<&str>) -> Result<Self, ApiError> {
+        let prompt_id = prompt_id.map(|id| parse_uuid(id, "prompt_id")).transpose()?;
+        Ok(Self { user_id, prompt_id })
+    }
+
+    fn contains(&self, message: &impl PromptMessage) -> bool {
+        message.user_id() == self.user_id && self.prompt_id.is_none_or(|id| id == message.prompt_id())
+    }
+}
+
+/// Streams the messages in `scope` as SSE events until the server shuts
+/// down. `to_event` turns a message into its event, or `None` to skip it. A
+/// `resync` event means messages were dropped and the client should refetch.
+pub fn prompt_stream<T, F>(
+    state: &AppState,
+    receiver: broadcast::Receiver<T>,
+    scope: PromptScope,
+    to_event: F,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>> + use<T, F>>
+where
+    T: PromptMessage,
+    F: FnMut(&T) -> Option<Event> + Send + 'static,
+{
+    let events = stream::unfold((receiver, to_event), move |(mut receiver, mut to_event)| async move {
+        loop {
+            match receiver.recv().await {
+                Ok(message) => {
+                    if !scope.contains(&message) {
+                        continue;
+                    }
+                    if let Some(event) = to_event(&message) {
+                        return Some((Ok(event), (receiver, to_event)));
+                    }
+                }
+                Err(RecvError::Lagged(_)) => {
+                    return Some((Ok(Event::default().event("resync").data("{}")), (receiver, to_event)));
+                }
+                Err(RecvError::Closed) => return None,
+            }
+        }
+    });
+
+    Sse::new(events.take_until(state.shutdown_signal())).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,85 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    response::Response,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    auth::extract_user_id_with_api_key,
+    http_cache::{cached_json, CACHE_REVALIDATE},
+};
+use crate::application::use_cases::TagBundle;
+use crate::domain::prompt::{PromptType, ContentType};
+
+#[derive(Serialize)]
+pub struct BundlePromptResponse {
+    pub prompt_id: String,
+    pub name: String,
+    pub prompt_type: String,
+    pub version_id: String,
+    pub version: String,
+    pub digest: String,
+    pub content: String,
+    pub content_type: String,
+    pub variables: Option<Vec<String>>,
+}
+
+#[derive(Serialize)]
+pub struct BundleResponse {
+    pub tag: String,
+    pub etag: String,
+    pub prompts: Vec<BundlePromptResponse>,
+}
+
+impl From<TagBundle> for BundleResponse {
+    fn from(bundle: TagBundle) -> Self {
+        let prompts = bundle.entries.iter().map(|entry| {
+            let prompt_type = match entry.prompt_type {
+                PromptType::System => "system",
+                PromptType::User => "user",
+            };
+            let content_type = match entry.version.content_type() {
+                ContentType::Static => "static",
+                ContentType::Template => "template",
+            };
+
+            BundlePromptResponse {
+                prompt_id: entry.prompt_id.to_string(),
+                name: entry.prompt_name.clone(),
+                prompt_type: prompt_type.to_string(),
+                version_id: entry.version.id().to_string(),
+                version: entry.version.version_string(),
+                digest: entry.version.digest().to_string(),
+                content: entry.version.content().to_string(),
+                content_type: content_type.to_string(),
+                variables: entry.version.variables().map(|v| v.to_vec()),
+            }
+        }).collect();
+
+        Self {
+            tag: bundle.tag_name,
+            etag: bundle.etag,
+            prompts,
+        }
+    }
+}
+
+pub async fn get_bundle(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(tag_name): Path<String>,
+) -> Result<Response, ApiError> {
+    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+
+    let bundle = state
+        .get_tag_bundle
+        .execute(user_id, tag_name)
+        .await?;
+
+    let etag = format!("\"{}\"", bundle.etag);
+    Ok(cached_json(&headers, etag, CACHE_REVALIDATE, || BundleResponse::from(bundle)))
+}
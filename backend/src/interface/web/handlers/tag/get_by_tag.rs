@@ -1,30 +1,33 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
-    Json,
+    http::HeaderMap,
+    response::Response,
 };
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::ApiError,
     app_state::AppState,
     auth::extract_user_id_with_api_key,
-    response_types::VersionResponse,
+    http_cache::CACHE_REVALIDATE,
     uuid_helpers::parse_uuid,
+    version::get::version_response,
 };
 
+/// The ETag follows the tag's target version, so it changes when the tag
+/// moves; caches revalidate since that can happen at any time.
 pub async fn get_version_by_tag(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path((prompt_id, tag_name)): Path<(String, String)>,
-) -> Result<Json<VersionResponse>, (StatusCode, String)> {
+) -> Result<Response, ApiError> {
     let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
 
     let version = state
         .get_version_by_tag
         .execute(prompt_uuid, user_id, tag_name)
-        .await
-        .map_err(|e| (StatusCode::NOT_FOUND, e))?;
+        .await?;
 
-    Ok(Json(VersionResponse::from(&version)))
-}
\ No newline at end of file
+    Ok(version_response(&headers, &version, &state.pricing, CACHE_REVALIDATE))
+}
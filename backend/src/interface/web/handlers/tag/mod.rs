@@ -1,7 +1,9 @@
+pub mod bundle;
 pub mod create;
 pub mod delete;
 pub mod get_by_tag;
 
+pub use bundle::get_bundle;
 pub use create::tag_version;
 pub use delete::delete_tag;
 pub use get_by_tag::get_version_by_tag;
\ No newline at end of file
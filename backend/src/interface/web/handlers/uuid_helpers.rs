@@ -1,20 +1,18 @@
-use axum::http::StatusCode;
 use uuid::Uuid;
 
+use crate::interface::web::handlers::api_error::ApiError;
+
 /// Parse a UUID from a string, returning a proper HTTP error on failure
-pub fn parse_uuid(id: &str, field_name: &str) -> Result<Uuid, (StatusCode, String)> {
+pub fn parse_uuid(id: &str, field_name: &str) -> Result<Uuid, ApiError> {
     Uuid::parse_str(id).map_err(|_| {
-        (
-            StatusCode::BAD_REQUEST,
-            format!("Invalid UUID format for {}", field_name),
-        )
+        ApiError::bad_request(format!("Invalid UUID format for {}", field_name)).with_field(field_name)
     })
 }
 
 /// Parse multiple UUIDs, short-circuiting on first error
 pub fn parse_uuids(
     ids: &[(&str, &str)], // (value, field_name) pairs
-) -> Result<Vec<Uuid>, (StatusCode, String)> {
+) -> Result<Vec<Uuid>, ApiError> {
     ids.iter()
         .map(|(id, name)| parse_uuid(id, name))
         .collect()
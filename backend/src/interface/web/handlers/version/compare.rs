@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    auth::extract_user_id_with_api_key,
+    response_types::{CostEstimateResponse, TokenCountResponse},
+    uuid_helpers::parse_uuid,
+};
+use crate::application::{CostEstimate, use_cases::VersionComparison};
+use crate::domain::prompt::PromptVersion;
+
+#[derive(Serialize)]
+pub struct ComparedVersionResponse {
+    pub id: String,
+    pub version: String,
+    pub digest: String,
+    pub model: Option<String>,
+    pub token_count: Option<TokenCountResponse>,
+    pub estimated_input_cost: Option<CostEstimateResponse>,
+}
+
+#[derive(Serialize)]
+pub struct CompareVersionsResponse {
+    pub base: ComparedVersionResponse,
+    pub target: ComparedVersionResponse,
+    pub token_delta: Option<i64>,
+    pub cost_delta_usd: Option<f64>,
+}
+
+impl ComparedVersionResponse {
+    fn new(version: &PromptVersion, cost: Option<&CostEstimate>) -> Self {
+        Self {
+            id: version.id().to_string(),
+            version: version.version_string(),
+            digest: version.digest().to_string(),
+            model: version.model_config().map(|c| c.model().to_string()),
+            token_count: version.token_count().map(TokenCountResponse::from),
+            estimated_input_cost: cost.map(CostEstimateResponse::from),
+        }
+    }
+}
+
+impl From<VersionComparison> for CompareVersionsResponse {
+    fn from(comparison: VersionComparison) -> Self {
+        Self {
+            base: ComparedVersionResponse::new(&comparison.base, comparison.base_cost.as_ref()),
+            target: ComparedVersionResponse::new(&comparison.target, comparison.target_cost.as_ref()),
+            token_delta: comparison.token_delta,
+            cost_delta_usd: comparison.cost_delta_usd,
+        }
+    }
+}
+
+pub async fn compare_versions(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path((prompt_id, version_id, other_version_id)): Path<(String, String, String)>,
+) -> Result<Json<CompareVersionsResponse>, ApiError> {
+    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    let version_uuid = parse_uuid(&version_id, "version_id")?;
+    let other_version_uuid = parse_uuid(&other_version_id, "other_version_id")?;
+
+    let comparison = state
+        .compare_versions
+        .execute(prompt_uuid, user_id, version_uuid, other_version_uuid)
+        .await?;
+
+    Ok(Json(CompareVersionsResponse::from(comparison)))
+}
@@ -1,17 +1,21 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
+    http::{StatusCode, HeaderMap, header},
     Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::{ApiError, ApiJson},
     app_state::AppState,
-    auth::extract_user_id_with_api_key,
+    auth::extract_actor_with_api_key,
+    http_cache::{if_match_revision, prompt_etag},
     uuid_helpers::parse_uuid,
 };
-use crate::domain::prompt::ContentType;
+use crate::application::AppError;
+use crate::domain::error::DomainError;
+use crate::domain::prompt::{ContentType, ModelConfig, ModelProvider, ToolDefinition, OutputSchema, Version, VersionDraft};
 
 #[derive(Deserialize)]
 pub struct CreateVersionRequest {
@@ -20,6 +24,67 @@ pub struct CreateVersionRequest {
     pub content_type: String,
     pub variables: Option<Vec<String>>,
     pub changelog: Option<String>,
+    pub model_config: Option<ModelConfigRequest>,
+    #[serde(default)]
+    pub tools: Vec<ToolDefinitionRequest>,
+    pub output_schema: Option<OutputSchemaRequest>,
+}
+
+#[derive(Deserialize)]
+pub struct ModelConfigRequest {
+    pub provider: String,
+    pub model: String,
+    pub temperature: Option<f64>,
+    pub top_p: Option<f64>,
+    pub max_tokens: Option<u32>,
+    #[serde(default)]
+    pub stop_sequences: Vec<String>,
+    pub seed: Option<i64>,
+}
+
+impl TryFrom<ModelConfigRequest> for ModelConfig {
+    type Error = DomainError;
+
+    fn try_from(request: ModelConfigRequest) -> Result<Self, Self::Error> {
+        ModelConfig::new(
+            ModelProvider::from_str(&request.provider)?,
+            request.model,
+            request.temperature,
+            request.top_p,
+            request.max_tokens,
+            request.stop_sequences,
+            request.seed,
+        )
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ToolDefinitionRequest {
+    pub name: String,
+    pub description: Option<String>,
+    pub parameters: serde_json::Value,
+}
+
+impl TryFrom<ToolDefinitionRequest> for ToolDefinition {
+    type Error = DomainError;
+
+    fn try_from(request: ToolDefinitionRequest) -> Result<Self, Self::Error> {
+        ToolDefinition::new(request.name, request.description, request.parameters)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct OutputSchemaRequest {
+    pub name: String,
+    pub schema: serde_json::Value,
+}
+
+impl TryFrom<OutputSchemaRequest> for OutputSchema {
+    type Error = DomainError;
+
+    fn try_from(request: OutputSchemaRequest) -> Result<Self, Self::Error> {
+        OutputSchema::new(request.name, request.schema)
+    }
 }
 
 #[derive(Serialize)]
@@ -31,32 +96,51 @@ pub async fn create_version(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path(prompt_id): Path<String>,
-    Json(payload): Json<CreateVersionRequest>,
-) -> Result<(StatusCode, Json<CreateVersionResponse>), (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    ApiJson(payload): ApiJson<CreateVersionRequest>,
+) -> Result<(StatusCode, [(header::HeaderName, String); 1], Json<CreateVersionResponse>), ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    let expected_revision = if_match_revision(&headers)?;
 
     let content_type = match payload.content_type.as_str() {
         "static" => ContentType::Static,
         "template" => ContentType::Template,
-        _ => return Err((StatusCode::BAD_REQUEST, "Invalid content_type. Must be 'static' or 'template'".to_string())),
+        _ => return Err(ApiError::validation("content_type", "Invalid content_type. Must be 'static' or 'template'")),
     };
 
-    let version_id = state
+    let model_config = payload.model_config
+        .map(ModelConfig::try_from)
+        .transpose()
+        .map_err(AppError::from)?;
+
+    let tools = payload.tools
+        .into_iter()
+        .map(ToolDefinition::try_from)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(AppError::from)?;
+
+    let output_schema = payload.output_schema
+        .map(OutputSchema::try_from)
+        .transpose()
+        .map_err(AppError::from)?;
+
+    let draft = VersionDraft {
+        version: Version::from_str(&payload.version).map_err(AppError::from)?,
+        content: payload.content,
+        content_type,
+        variables: payload.variables,
+        changelog: payload.changelog,
+        model_config,
+        tools,
+        output_schema,
+    };
+
+    let (version_id, revision) = state
         .create_version
-        .execute(
-            prompt_uuid,
-            user_id,
-            payload.version,
-            payload.content,
-            content_type,
-            payload.variables,
-            payload.changelog
-        )
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .execute(prompt_uuid, actor, draft, expected_revision)
+        .await?;
 
-    Ok((StatusCode::CREATED, Json(CreateVersionResponse {
+    Ok((StatusCode::CREATED, [(header::ETAG, prompt_etag(revision))], Json(CreateVersionResponse {
         version_id: version_id.to_string()
     })))
 }
\ No newline at end of file
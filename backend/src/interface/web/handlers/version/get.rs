@@ -1,13 +1,17 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
-    Json,
+    http::HeaderMap,
+    response::Response,
 };
 use std::sync::Arc;
 
+use crate::application::PricingTable;
+use crate::domain::prompt::PromptVersion;
 use crate::interface::web::handlers::{
+    api_error::ApiError,
     app_state::AppState,
     auth::extract_user_id_with_api_key,
+    http_cache::{cached_json, version_etag, CACHE_IMMUTABLE},
     response_types::VersionResponse,
     uuid_helpers::parse_uuid,
 };
@@ -16,7 +20,7 @@ pub async fn get_version(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path((prompt_id, version_id)): Path<(String, String)>,
-) -> Result<Json<VersionResponse>, (StatusCode, String)> {
+) -> Result<Response, ApiError> {
     let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
     let version_uuid = parse_uuid(&version_id, "version_id")?;
@@ -24,8 +28,59 @@ pub async fn get_version(
     let version = state
         .get_version
         .execute(prompt_uuid, user_id, version_uuid)
-        .await
-        .map_err(|e| (StatusCode::NOT_FOUND, e))?;
+        .await?;
 
-    Ok(Json(VersionResponse::from(&version)))
-}
\ No newline at end of file
+    Ok(version_response(&headers, &version, &state.pricing, CACHE_IMMUTABLE))
+}
+
+/// The version as JSON, or `304` when `If-None-Match` holds its ETag.
+pub fn version_response(
+    headers: &HeaderMap,
+    version: &PromptVersion,
+    pricing: &PricingTable,
+    cache_control: &'static str,
+) -> Response {
+    cached_json(headers, version_etag(version), cache_control, || VersionResponse::new(version, pricing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::actor::Actor;
+    use crate::domain::prompt::{ContentType, FeedbackDraft, Prompt, PromptType, Version, VersionDraft};
+    use axum::http::{header, HeaderValue, StatusCode};
+    use uuid::Uuid;
+
+    #[test]
+    fn revalidates_a_reloaded_version_with_new_feedback() {
+        let actor = Actor::user(Uuid::new_v4());
+        let mut prompt = Prompt::new(Uuid::new_v4(), actor.user_id, "greeting".to_string(), None, PromptType::System, None);
+        let version_id = Uuid::new_v4();
+        let draft = VersionDraft {
+            version: Version::new(1, 0, 0),
+            content: "Hello".to_string(),
+            content_type: ContentType::Static,
+            variables: None,
+            changelog: None,
+            model_config: None,
+            tools: Vec::new(),
+            output_schema: None,
+        };
+        prompt.add_version(version_id, draft, actor).unwrap();
+        let pricing = PricingTable::with_defaults();
+
+        let first = version_response(&HeaderMap::new(), prompt.find_version_by_id(version_id).unwrap(), &pricing, CACHE_IMMUTABLE);
+        assert_eq!(first.status(), StatusCode::OK);
+        let etag = first.headers()[header::ETAG].clone();
+
+        let feedback = FeedbackDraft { rating: 5, comment: None, test_scenario: None, call_details: None };
+        prompt.add_feedback(version_id, Uuid::new_v4(), feedback, actor).unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let second = version_response(&headers, prompt.find_version_by_id(version_id).unwrap(), &pricing, CACHE_IMMUTABLE);
+
+        assert_eq!(second.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(second.headers()[header::ETAG], etag);
+        assert_eq!(second.headers()[header::CACHE_CONTROL], HeaderValue::from_static(CACHE_IMMUTABLE));
+    }
+}
@@ -1,9 +1,13 @@
+pub mod compare;
 pub mod create;
 pub mod get;
 pub mod delete;
 pub mod render;
+pub mod render_batch;
 
+pub use compare::compare_versions;
 pub use create::create_version;
 pub use get::get_version;
 pub use delete::delete_version;
-pub use render::{render_version, render_version_by_tag};
\ No newline at end of file
+pub use render::{render_version, render_version_by_tag};
+pub use render_batch::render_batch;
\ No newline at end of file
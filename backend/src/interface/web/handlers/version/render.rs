@@ -1,64 +1,106 @@
 use axum::{
     extract::{Path, State},
-    http::{StatusCode, HeaderMap},
+    http::HeaderMap,
     Json,
 };
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
 use crate::interface::web::handlers::{
+    api_error::{ApiError, ApiJson},
     app_state::AppState,
-    auth::extract_user_id_with_api_key,
+    auth::extract_actor_with_api_key,
+    response_types::{ModelConfigResponse, ToolDefinitionResponse, OutputSchemaResponse, TokenCountResponse, CostEstimateResponse},
     uuid_helpers::parse_uuid,
 };
+use crate::application::use_cases::RenderedPrompt;
+use crate::domain::prompt::ModelProvider;
 
 #[derive(Deserialize)]
 pub struct RenderVersionRequest {
     pub context: Option<serde_json::Value>,
+    pub format: Option<String>,
+}
+
+impl RenderVersionRequest {
+    fn provider_format(&self) -> Result<Option<ModelProvider>, ApiError> {
+        self.format
+            .as_deref()
+            .map(ModelProvider::from_str)
+            .transpose()
+            .map_err(|_| ApiError::validation("format", "Invalid format. Must be 'openai', 'anthropic' or 'gemini'"))
+    }
 }
 
 #[derive(Serialize)]
 pub struct RenderVersionResponse {
+    /// Send back as `render_id` with feedback on the output of this render.
+    pub render_id: String,
     pub rendered_content: String,
+    pub version_id: String,
+    pub version: String,
+    pub digest: String,
+    pub token_count: TokenCountResponse,
+    pub estimated_input_cost: Option<CostEstimateResponse>,
+    pub model_config: Option<ModelConfigResponse>,
+    pub tools: Vec<ToolDefinitionResponse>,
+    pub output_schema: Option<OutputSchemaResponse>,
+    pub payload: Option<serde_json::Value>,
+}
+
+impl From<RenderedPrompt> for RenderVersionResponse {
+    fn from(rendered: RenderedPrompt) -> Self {
+        Self {
+            render_id: rendered.render_event.id().to_string(),
+            version_id: rendered.version.id().to_string(),
+            version: rendered.version.version_string(),
+            digest: rendered.version.digest().to_string(),
+            token_count: TokenCountResponse::from(&rendered.token_count),
+            estimated_input_cost: rendered.estimated_cost.as_ref().map(CostEstimateResponse::from),
+            model_config: rendered.version.model_config().map(ModelConfigResponse::from),
+            tools: rendered.version.tools().iter().map(ToolDefinitionResponse::from).collect(),
+            output_schema: rendered.version.output_schema().map(OutputSchemaResponse::from),
+            rendered_content: rendered.rendered_content,
+            payload: rendered.payload,
+        }
+    }
 }
 
 pub async fn render_version(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path((prompt_id, version_id)): Path<(String, String)>,
-    Json(payload): Json<RenderVersionRequest>,
-) -> Result<Json<RenderVersionResponse>, (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    ApiJson(payload): ApiJson<RenderVersionRequest>,
+) -> Result<Json<RenderVersionResponse>, ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
     let version_uuid = parse_uuid(&version_id, "version_id")?;
+    let format = payload.provider_format()?;
 
     let rendered = state
         .render_version
-        .execute(prompt_uuid, user_id, version_uuid, payload.context)
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .execute(prompt_uuid, actor, version_uuid, payload.context, format)
+        .await?;
+    state.metrics.record_render(prompt_uuid, None);
 
-    Ok(Json(RenderVersionResponse {
-        rendered_content: rendered
-    }))
+    Ok(Json(RenderVersionResponse::from(rendered)))
 }
 
 pub async fn render_version_by_tag(
     State(state): State<Arc<AppState>>,
     headers: HeaderMap,
     Path((prompt_id, tag_name)): Path<(String, String)>,
-    Json(payload): Json<RenderVersionRequest>,
-) -> Result<Json<RenderVersionResponse>, (StatusCode, String)> {
-    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    ApiJson(payload): ApiJson<RenderVersionRequest>,
+) -> Result<Json<RenderVersionResponse>, ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
     let prompt_uuid = parse_uuid(&prompt_id, "prompt_id")?;
+    let format = payload.provider_format()?;
 
     let rendered = state
         .render_version_by_tag
-        .execute(prompt_uuid, user_id, tag_name, payload.context)
-        .await
-        .map_err(|e| (StatusCode::BAD_REQUEST, e))?;
+        .execute(prompt_uuid, actor, tag_name.clone(), payload.context, format)
+        .await?;
+    state.metrics.record_render(prompt_uuid, Some(&tag_name));
 
-    Ok(Json(RenderVersionResponse {
-        rendered_content: rendered
-    }))
+    Ok(Json(RenderVersionResponse::from(rendered)))
 }
\ No newline at end of file
@@ -0,0 +1,200 @@
+use axum::{
+    body::{Body, Bytes},
+    extract::State,
+    http::{HeaderMap, header},
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::{stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::sync::Arc;
+
+use crate::interface::web::handlers::{
+    api_error::{ApiError, ApiJson, ErrorBody},
+    app_state::AppState,
+    auth::extract_actor_with_api_key,
+    version::render::RenderVersionResponse,
+};
+use crate::application::Metrics;
+use crate::application::use_cases::{BatchRenderItem, BatchRenderer, RenderedPrompt, MAX_BATCH_RENDER_ITEMS};
+use crate::domain::prompt::ModelProvider;
+
+/// Batches larger than this must be streamed as NDJSON.
+const MAX_JSON_BATCH_ITEMS: usize = 1_000;
+const NDJSON: &str = "application/x-ndjson";
+/// Streamed items are rendered, recorded and sent this many at a time.
+const NDJSON_CHUNK_ITEMS: usize = 100;
+
+/// Either one prompt/tag rendered with many `contexts`, or a list of `items`
+/// each naming its own prompt and tag.
+#[derive(Deserialize)]
+pub struct BatchRenderRequest {
+    pub prompt_id: Option<String>,
+    pub tag: Option<String>,
+    pub contexts: Option<Vec<serde_json::Value>>,
+    pub items: Option<Vec<BatchRenderItemRequest>>,
+    pub format: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BatchRenderItemRequest {
+    pub prompt_id: String,
+    pub tag: String,
+    pub context: Option<serde_json::Value>,
+    pub format: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct BatchRenderItemResponse {
+    pub index: usize,
+    pub result: Option<RenderVersionResponse>,
+    pub error: Option<ErrorBody>,
+}
+
+#[derive(Serialize)]
+pub struct BatchRenderResponse {
+    pub results: Vec<BatchRenderItemResponse>,
+}
+
+impl BatchRenderItemResponse {
+    fn new(index: usize, outcome: Result<RenderedPrompt, ApiError>) -> Self {
+        match outcome {
+            Ok(rendered) => Self { index, result: Some(RenderVersionResponse::from(rendered)), error: None },
+            Err(error) => Self { index, result: None, error: Some(error.body()) },
+        }
+    }
+}
+
+fn parse_format(format: Option<&str>) -> Result<Option<ModelProvider>, ApiError> {
+    format
+        .map(ModelProvider::from_str)
+        .transpose()
+        .map_err(|_| ApiError::validation("format", "Invalid format. Must be 'openai', 'anthropic' or 'gemini'"))
+}
+
+impl BatchRenderRequest {
+    /// Expands the request into one entry per item. Items that fail to parse
+    /// are kept as errors so they are reported at their index.
+    fn into_items(self) -> Result<Vec<Result<BatchRenderItem, ApiError>>, ApiError> {
+        let invalid_prompt_id = || ApiError::validation("prompt_id", "Invalid prompt_id");
+        let default_format = parse_format(self.format.as_deref())?;
+
+        match (self.prompt_id, self.tag, self.contexts, self.items) {
+            (Some(prompt_id), Some(tag_name), Some(contexts), None) => {
+                let prompt_id = uuid::Uuid::parse_str(&prompt_id)
+                    .map_err(|_| invalid_prompt_id())?;
+                Ok(contexts
+                    .into_iter()
+                    .map(|context| Ok(BatchRenderItem {
+                        prompt_id,
+                        tag_name: tag_name.clone(),
+                        context: Some(context),
+                        format: default_format,
+                    }))
+                    .collect())
+            }
+            (None, None, None, Some(items)) => Ok(items
+                .into_iter()
+                .map(|item| {
+                    let prompt_id = uuid::Uuid::parse_str(&item.prompt_id)
+                        .map_err(|_| invalid_prompt_id())?;
+                    let format = match item.format.as_deref() {
+                        Some(format) => parse_format(Some(format))?,
+                        None => default_format,
+                    };
+                    Ok(BatchRenderItem {
+                        prompt_id,
+                        tag_name: item.tag,
+                        context: item.context,
+                        format,
+                    })
+                })
+                .collect()),
+            _ => Err(ApiError::bad_request("Provide either prompt_id, tag and contexts, or items")),
+        }
+    }
+}
+
+/// Renders `items` and records their render events in one write before
+/// the results are returned.
+async fn render_chunk(
+    renderer: &BatchRenderer,
+    metrics: &Metrics,
+    items: Vec<(usize, Result<BatchRenderItem, ApiError>)>,
+) -> Vec<BatchRenderItemResponse> {
+    let outcomes: Vec<_> = items
+        .into_iter()
+        .map(|(index, item)| {
+            let outcome = item.and_then(|item| {
+                let rendered = renderer.render(&item)?;
+                metrics.record_render(item.prompt_id, Some(&item.tag_name));
+                Ok(rendered)
+            });
+            (index, outcome)
+        })
+        .collect();
+
+    let events: Vec<_> = outcomes
+        .iter()
+        .filter_map(|(_, outcome)| outcome.as_ref().ok().map(|rendered| rendered.render_event.clone()))
+        .collect();
+    renderer.record(&events);
+
+    outcomes
+        .into_iter()
+        .map(|(index, outcome)| BatchRenderItemResponse::new(index, outcome))
+        .collect()
+}
+
+pub async fn render_batch(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    ApiJson(payload): ApiJson<BatchRenderRequest>,
+) -> Result<Response, ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    let items = payload.into_items()?;
+
+    let stream_ndjson = headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|accept| accept.contains(NDJSON));
+
+    if items.len() > MAX_BATCH_RENDER_ITEMS {
+        return Err(ApiError::bad_request(format!("Batch cannot exceed {} items", MAX_BATCH_RENDER_ITEMS)));
+    }
+    if !stream_ndjson && items.len() > MAX_JSON_BATCH_ITEMS {
+        return Err(ApiError::bad_request(
+            format!("Batches over {} items must be requested with Accept: {}", MAX_JSON_BATCH_ITEMS, NDJSON),
+        ));
+    }
+
+    let prompt_ids: Vec<_> = items.iter().filter_map(|item| item.as_ref().ok().map(|i| i.prompt_id)).collect();
+    let renderer = Arc::new(state
+        .render_batch
+        .prepare(actor, prompt_ids)
+        .await?);
+
+    if !stream_ndjson {
+        let results = render_chunk(&renderer, &state.metrics, items.into_iter().enumerate().collect()).await;
+        return Ok(Json(BatchRenderResponse { results }).into_response());
+    }
+
+    let metrics = state.metrics.clone();
+    let lines = stream::iter(items.into_iter().enumerate())
+        .chunks(NDJSON_CHUNK_ITEMS)
+        .then(move |chunk| {
+            let renderer = renderer.clone();
+            let metrics = metrics.clone();
+            async move {
+                let mut lines = Vec::new();
+                for result in render_chunk(&renderer, &metrics, chunk).await {
+                    serde_json::to_writer(&mut lines, &result).expect("render result serializes to JSON");
+                    lines.push(b'\n');
+                }
+                Ok::<_, Infallible>(Bytes::from(lines))
+            }
+        });
+
+    Ok(([(header::CONTENT_TYPE, NDJSON)], Body::from_stream(lines)).into_response())
+}
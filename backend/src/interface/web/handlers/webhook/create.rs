@@ -0,0 +1,52 @@
+use axum::{
+    extract::State,
+    http::{StatusCode, HeaderMap},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+use crate::interface::web::handlers::{
+    api_error::{ApiError, ApiJson},
+    app_state::AppState,
+    auth::extract_actor_with_api_key,
+    response_types::WebhookResponse,
+    uuid_helpers::parse_uuid,
+};
+
+#[derive(Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub prompt_id: Option<String>,
+    #[serde(default)]
+    pub event_types: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct CreateWebhookResponse {
+    #[serde(flatten)]
+    pub webhook: WebhookResponse,
+    pub secret: String,  // Only returned once!
+}
+
+pub async fn create_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    ApiJson(payload): ApiJson<CreateWebhookRequest>,
+) -> Result<(StatusCode, Json<CreateWebhookResponse>), ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    let prompt_id = payload.prompt_id
+        .as_deref()
+        .map(|id| parse_uuid(id, "prompt_id"))
+        .transpose()?;
+
+    let webhook = state
+        .create_webhook
+        .execute(actor, prompt_id, payload.url, payload.event_types)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(CreateWebhookResponse {
+        webhook: WebhookResponse::from(&webhook),
+        secret: webhook.secret().to_string(),
+    })))
+}
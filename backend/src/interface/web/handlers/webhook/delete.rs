@@ -0,0 +1,28 @@
+use axum::{
+    extract::{Path, State},
+    http::{StatusCode, HeaderMap},
+};
+use std::sync::Arc;
+
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    auth::extract_actor_with_api_key,
+    uuid_helpers::parse_uuid,
+};
+
+pub async fn delete_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(webhook_id): Path<String>,
+) -> Result<StatusCode, ApiError> {
+    let actor = extract_actor_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    let webhook_uuid = parse_uuid(&webhook_id, "webhook_id")?;
+
+    state
+        .delete_webhook
+        .execute(webhook_uuid, actor)
+        .await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,30 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+    Json,
+};
+use std::sync::Arc;
+
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    auth::extract_user_id_with_api_key,
+    response_types::WebhookDeliveryResponse,
+    uuid_helpers::parse_uuid,
+};
+
+pub async fn list_webhook_deliveries(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    Path(webhook_id): Path<String>,
+) -> Result<Json<Vec<WebhookDeliveryResponse>>, ApiError> {
+    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+    let webhook_uuid = parse_uuid(&webhook_id, "webhook_id")?;
+
+    let deliveries = state
+        .list_webhook_deliveries
+        .execute(webhook_uuid, user_id)
+        .await?;
+
+    Ok(Json(deliveries.iter().map(WebhookDeliveryResponse::from).collect()))
+}
@@ -0,0 +1,27 @@
+use axum::{
+    extract::State,
+    http::HeaderMap,
+    Json,
+};
+use std::sync::Arc;
+
+use crate::interface::web::handlers::{
+    api_error::ApiError,
+    app_state::AppState,
+    auth::extract_user_id_with_api_key,
+    response_types::WebhookResponse,
+};
+
+pub async fn list_webhooks(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Json<Vec<WebhookResponse>>, ApiError> {
+    let user_id = extract_user_id_with_api_key(&headers, state.api_key_repository.clone()).await?;
+
+    let webhooks = state
+        .list_webhooks
+        .execute(user_id)
+        .await?;
+
+    Ok(Json(webhooks.iter().map(WebhookResponse::from).collect()))
+}
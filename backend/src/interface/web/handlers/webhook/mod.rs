@@ -0,0 +1,11 @@
+mod create;
+mod list;
+mod delete;
+mod deliveries;
+mod redeliver;
+
+pub use create::create_webhook;
+pub use list::list_webhooks;
+pub use delete::delete_webhook;
+pub use deliveries::list_webhook_deliveries;
+pub use redeliver::redeliver_webhook_delivery;
@@ -1,4 +1,5 @@
 pub mod routes;
 pub mod handlers;
+pub mod request_tracing;
 
-pub use routes::create_router;
\ No newline at end of file
+pub use routes::{create_metrics_router, create_router};
\ No newline at end of file
@@ -0,0 +1,37 @@
+use axum::{extract::{MatchedPath, Request}, http::HeaderName};
+use tracing::{field::Empty, Span};
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Root span of every request. Logs written while handling the request carry
+/// its fields; `user_id` is filled in once the caller is authenticated.
+pub fn request_span(request: &Request) -> Span {
+    let matched_path = request.extensions().get::<MatchedPath>().map(MatchedPath::as_str);
+    let request_id = request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        route = matched_path.unwrap_or("unmatched"),
+        request_id,
+        prompt_id = matched_path.and_then(|route| path_param(route, request.uri().path(), "{prompt_id}")),
+        user_id = Empty,
+    )
+}
+
+/// Records the authenticated caller on the current request span.
+pub fn record_user_id(user_id: uuid::Uuid) {
+    Span::current().record("user_id", tracing::field::display(user_id));
+}
+
+/// The segment of `path` at the position of `param` in the `route` template.
+fn path_param<'a>(route: &str, path: &'a str, param: &str) -> Option<&'a str> {
+    route
+        .split('/')
+        .zip(path.split('/'))
+        .find_map(|(template, segment)| (template == param).then_some(segment))
+}
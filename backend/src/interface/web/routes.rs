@@ -1,29 +1,46 @@
 use axum::{
     routing::{get, post, put, delete},
+    middleware,
     Router,
 };
 use std::sync::Arc;
-use http::Method;
-use tower_http::cors::{CorsLayer, Any};
+use http::{HeaderValue, Method, header};
+use tower_http::cors::{AllowOrigin, CorsLayer, Any};
+use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
+use tower_http::trace::{DefaultOnResponse, TraceLayer};
+use tracing::Level;
 
+use super::request_tracing::{request_span, REQUEST_ID_HEADER};
 use super::handlers::{
     AppState,
     prompt::{create_prompt, update_prompt, get_prompt, list_prompts, delete_prompt},
-    version::{create_version, get_version, delete_version, render_version, render_version_by_tag},
-    tag::{tag_version, delete_tag, get_version_by_tag},
-    feedback::{submit_feedback, update_feedback, delete_feedback},
+    version::{create_version, get_version, delete_version, render_version, render_version_by_tag, render_batch, compare_versions},
+    tag::{tag_version, delete_tag, get_version_by_tag, get_bundle},
+    feedback::{submit_feedback, list_feedback, update_feedback, delete_feedback},
     api_key::{create_api_key, list_api_keys, delete_api_key},
+    cache::get_cache_stats,
+    changes::stream_changes,
+    events::stream_events,
+    webhook::{create_webhook, list_webhooks, delete_webhook, list_webhook_deliveries, redeliver_webhook_delivery},
+    audit::{list_audit_entries, list_prompt_audit_entries},
+    health::{get_liveness, get_readiness},
+    metrics::{get_internal_metrics, get_metrics, track_requests},
     improvement::{create_improvement_suggestion, accept_improvement_suggestion, decline_improvement_suggestion, list_suggestions_for_version,analyze_feedback},
 };
 
-pub fn create_router(state: Arc<AppState>) -> Router {
-    let allowed_origin = std::env::var("ALLOWED_ORIGIN")
-        .unwrap_or_else(|_| "http://localhost:5173".to_string());
+/// `allowed_origins` are the browser origins CORS admits; `*` admits any.
+pub fn create_router(state: Arc<AppState>, allowed_origins: &[String]) -> Router {
+    let allow_origin = if allowed_origins.iter().any(|origin| origin == "*") {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(allowed_origins.iter().filter_map(|origin| origin.parse::<HeaderValue>().ok()))
+    };
 
     let cors = CorsLayer::new()
-        .allow_origin(allowed_origin.parse::<axum::http::HeaderValue>().unwrap())
+        .allow_origin(allow_origin)
         .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE])
-        .allow_headers(Any);
+        .allow_headers(Any)
+        .expose_headers([header::ETAG, REQUEST_ID_HEADER]);
 
     Router::new()
         .route("/prompts", post(create_prompt).get(list_prompts))
@@ -32,25 +49,60 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route("/prompts/{prompt_id}/versions", post(create_version))
         .route("/prompts/{prompt_id}/versions/{version_id}", get(get_version).delete(delete_version))
         .route("/prompts/{prompt_id}/versions/{version_id}/render", post(render_version))
+        .route("/prompts/{prompt_id}/versions/{version_id}/compare/{other_version_id}", get(compare_versions))
+
+        .route("/render/batch", post(render_batch))
+        .route("/bundles/{tag_name}", get(get_bundle))
 
         .route("/prompts/{prompt_id}/tags", post(tag_version))
         .route("/prompts/{prompt_id}/tags/{tag_name}", delete(delete_tag))
         .route("/prompts/{prompt_id}/tags/{tag_name}/version", get(get_version_by_tag))
         .route("/prompts/{prompt_id}/tags/{tag_name}/render", post(render_version_by_tag))
 
-        .route("/prompts/{prompt_id}/feedback", post(submit_feedback))
+        .route("/prompts/{prompt_id}/feedback", post(submit_feedback).get(list_feedback))
         .route("/prompts/{prompt_id}/versions/{version_id}/feedback/{feedback_id}",
                put(update_feedback).delete(delete_feedback))
 
         .route("/api-keys", post(create_api_key).get(list_api_keys))
         .route("/api-keys/{api_key_id}", delete(delete_api_key))
 
+        .route("/webhooks", post(create_webhook).get(list_webhooks))
+        .route("/webhooks/{webhook_id}", delete(delete_webhook))
+        .route("/webhooks/{webhook_id}/deliveries", get(list_webhook_deliveries))
+        .route("/webhooks/{webhook_id}/deliveries/{delivery_id}/redeliver", post(redeliver_webhook_delivery))
+
+        .route("/audit-log", get(list_audit_entries))
+        .route("/prompts/{prompt_id}/audit-log", get(list_prompt_audit_entries))
+
         .route("/prompts/{prompt_id}/improvements", post(create_improvement_suggestion))
         .route("/prompts/{prompt_id}/versions/{version_id}/improvements", get(list_suggestions_for_version))
         .route("/prompts/{prompt_id}/versions/{version_id}/improvements/{suggestion_id}/accept", post(accept_improvement_suggestion))
         .route("/prompts/{prompt_id}/versions/{version_id}/improvements/{suggestion_id}/decline", post(decline_improvement_suggestion))
         .route("/prompts/{prompt_id}/versions/{version_id}/analyze-feedback", post(analyze_feedback))
 
+        .route("/cache/stats", get(get_cache_stats))
+        .route("/changes", get(stream_changes))
+        .route("/events", get(stream_events))
+
+        .route("/healthz", get(get_liveness))
+        .route("/readyz", get(get_readiness))
+        .route("/metrics", get(get_metrics))
+
+        .layer(middleware::from_fn_with_state(state.clone(), track_requests))
+        // Requests without an `x-request-id` get a generated one, which is
+        // echoed in the response and recorded on the request span.
+        .layer(PropagateRequestIdLayer::new(REQUEST_ID_HEADER))
+        .layer(TraceLayer::new_for_http()
+            .make_span_with(request_span)
+            .on_response(DefaultOnResponse::new().level(Level::INFO)))
+        .layer(SetRequestIdLayer::new(REQUEST_ID_HEADER, MakeRequestUuid))
         .layer(cors)
         .with_state(state)
-}
\ No newline at end of file
+}
+
+/// Router for the metrics listener (`metrics.bind_address`).
+pub fn create_metrics_router(state: Arc<AppState>) -> Router {
+    Router::new()
+        .route("/metrics", get(get_internal_metrics))
+        .with_state(state)
+}
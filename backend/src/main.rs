@@ -1,40 +1,174 @@
+mod config;
+mod telemetry;
 mod domain;
 mod application;
 mod infrastructure;
 mod interface;
 
+use std::future::IntoFuture;
 use std::sync::Arc;
-use infrastructure::repositories::{PostgresPromptRepository, PostgresApiKeyRepository};
+use std::time::Duration;
+use config::{AiProvider, Config};
+use telemetry::Telemetry;
+use infrastructure::repositories::{PostgresPromptRepository, PostgresApiKeyRepository, PostgresWebhookRepository, PostgresAuditRepository, PostgresRenderRepository, CachedPromptRepository};
+use infrastructure::repositories::{SqlitePromptRepository, SqliteApiKeyRepository, SqliteWebhookRepository, SqliteAuditRepository, SqliteRenderRepository};
+use infrastructure::repositories::{InMemoryPromptRepository, InMemoryApiKeyRepository, InMemoryWebhookRepository, InMemoryAuditRepository, InMemoryRenderRepository};
 use application::use_cases::*;
-use application::AIService;
-use infrastructure::OpenAIService;
-use interface::web::{create_router, handlers::AppState};
+use application::{AIService, Tokenizer, PricingTable, PromptRepository, ApiKeyRepository, ReadCache, WebhookRepository, WebhookDispatcher, Metrics, ReadinessProbe, AuditRepository, RenderRepository, RenderLog};
+use infrastructure::{OpenAIService, FakeAIService, InstrumentedAIService, TiktokenTokenizer, PostgresChangeListener, HttpWebhookSender};
+use infrastructure::{PostgresReadiness, SqliteReadiness, POSTGRES_MIGRATOR, SQLITE_MIGRATOR};
+use interface::web::{create_metrics_router, create_router, handlers::{AppState, enable_dev_user}};
+use sqlx::postgres::PgPoolOptions;
+use sqlx::sqlite::SqlitePoolOptions;
+use tokio::sync::watch;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const DEV_USER_ID: Uuid = Uuid::from_u128(1);
 
 #[tokio::main]
 async fn main() {
     dotenvy::dotenv().ok();
+    let config = Config::load().unwrap_or_else(|e| panic!("Invalid configuration: {}", e));
+    let telemetry = Telemetry::init(&config.telemetry).unwrap_or_else(|e| panic!("{}", e));
 
-    let database_url = std::env::var("DATABASE_URL")
-        .expect("DATABASE_URL must be set in .env file");
+    // Dev mode needs neither a database nor OpenAI, and treats requests
+    // without credentials as coming from `DEV_USER_ID`.
+    let dev_mode = config.features.dev_mode;
 
-    println!("🔗 Connecting to Supabase PostgreSQL...");
-    let pool = sqlx::PgPool::connect(&database_url)
-        .await
-        .expect("Failed to connect to database");
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let (prompt_changes, _) = tokio::sync::broadcast::channel(1024);
+    let (prompt_events, _) = tokio::sync::broadcast::channel(1024);
 
-    println!("🔄 Running migrations...");
-    sqlx::migrate!("./migrations")
-        .run(&pool)
-        .await
-        .expect("Failed to run migrations");
+    // `sqlite:` URLs run a single self-contained instance on a local file;
+    // anything else is treated as PostgreSQL.
+    let database_url = config.database.url.as_deref().filter(|_| !dev_mode);
+    let acquire_timeout = Duration::from_secs(config.database.acquire_timeout_secs);
+    let mut postgres_pool = None;
+    let mut readiness_probes: Vec<Arc<dyn ReadinessProbe>> = Vec::new();
+    let storage_prompt_repository: Arc<dyn PromptRepository>;
+    let api_key_repository: Arc<dyn ApiKeyRepository>;
+    let webhook_repository: Arc<dyn WebhookRepository>;
+    let audit_repository: Arc<dyn AuditRepository>;
+    let render_repository: Arc<dyn RenderRepository>;
+    if let Some(database_url) = database_url && database_url.starts_with("sqlite:") {
+        info!("Opening SQLite database");
+        let options = database_url
+            .parse::<sqlx::sqlite::SqliteConnectOptions>()
+            .expect("Invalid SQLite DATABASE_URL")
+            .create_if_missing(true)
+            .journal_mode(sqlx::sqlite::SqliteJournalMode::Wal);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(config.database.max_connections)
+            .min_connections(config.database.min_connections)
+            .acquire_timeout(acquire_timeout)
+            .connect_with(options)
+            .await
+            .expect("Failed to open database");
+
+        info!("Running migrations");
+        SQLITE_MIGRATOR
+            .run(&pool)
+            .await
+            .expect("Failed to run migrations");
+
+        storage_prompt_repository = Arc::new(SqlitePromptRepository::new(pool.clone(), prompt_changes.clone(), prompt_events.clone()));
+        api_key_repository = Arc::new(SqliteApiKeyRepository::new(pool.clone()));
+        webhook_repository = Arc::new(SqliteWebhookRepository::new(pool.clone()));
+        audit_repository = Arc::new(SqliteAuditRepository::new(pool.clone()));
+        render_repository = Arc::new(SqliteRenderRepository::new(pool.clone()));
+        readiness_probes.push(Arc::new(SqliteReadiness::new(pool)));
+    } else if let Some(database_url) = database_url {
+        info!("Connecting to PostgreSQL");
+        let pool = PgPoolOptions::new()
+            .max_connections(config.database.max_connections)
+            .min_connections(config.database.min_connections)
+            .acquire_timeout(acquire_timeout)
+            .connect(database_url)
+            .await
+            .expect("Failed to connect to database");
+
+        info!("Running migrations");
+        POSTGRES_MIGRATOR
+            .run(&pool)
+            .await
+            .expect("Failed to run migrations");
 
-    println!("✅ Database connected and migrated");
+        storage_prompt_repository = Arc::new(PostgresPromptRepository::new(pool.clone()));
+        api_key_repository = Arc::new(PostgresApiKeyRepository::new(pool.clone()));
+        webhook_repository = Arc::new(PostgresWebhookRepository::new(pool.clone()));
+        audit_repository = Arc::new(PostgresAuditRepository::new(pool.clone()));
+        render_repository = Arc::new(PostgresRenderRepository::new(pool.clone()));
+        readiness_probes.push(Arc::new(PostgresReadiness::new(pool.clone())));
+        postgres_pool = Some(pool);
+    } else {
+        let audit = Arc::new(InMemoryAuditRepository::new());
+        let webhooks = Arc::new(InMemoryWebhookRepository::new(audit.clone()));
+        storage_prompt_repository = Arc::new(InMemoryPromptRepository::new(prompt_changes.clone(), prompt_events.clone(), audit.clone(), webhooks.clone()));
+        api_key_repository = Arc::new(InMemoryApiKeyRepository::new(audit.clone()));
+        webhook_repository = webhooks;
+        audit_repository = audit;
+        render_repository = Arc::new(InMemoryRenderRepository::new());
+    }
+
+    if dev_mode {
+        enable_dev_user(DEV_USER_ID);
+        info!(dev_user_id = %DEV_USER_ID, "Dev mode: in-memory storage, fake AI, unauthenticated requests act as the dev user");
+    } else {
+        info!("Database connected and migrated");
+    }
+
+    let prompt_cache = (config.cache.ttl_secs > 0).then(|| Arc::new(CachedPromptRepository::new(
+        storage_prompt_repository.clone(),
+        Duration::from_secs(config.cache.ttl_secs),
+        config.cache.max_entries,
+    )));
+    let prompt_repository: Arc<dyn PromptRepository> = match &prompt_cache {
+        Some(cache) => cache.clone(),
+        None => storage_prompt_repository,
+    };
+    let read_cache = prompt_cache.clone().map(|cache| cache as Arc<dyn ReadCache>);
+
+    if let Some(pool) = postgres_pool {
+        PostgresChangeListener::new(pool, prompt_cache, prompt_changes.clone(), prompt_events.clone())
+            .start()
+            .await
+            .expect("Failed to start prompt change listener");
+    }
+    let webhook_dispatcher = Arc::new(WebhookDispatcher::new(
+        webhook_repository.clone(),
+        Arc::new(HttpWebhookSender::new(config.allow_private_webhook_targets())),
+    ));
+    if config.features.webhook_delivery {
+        webhook_dispatcher.clone().start(prompt_events.subscribe());
+    } else {
+        info!("Webhook delivery is off on this instance");
+    }
+    let metrics = Arc::new(Metrics::new());
+    let ai_service: Option<(Arc<dyn AIService>, &str)> = if !config.features.ai_analysis {
+        None
+    } else if dev_mode || config.ai.provider == AiProvider::Fake {
+        Some((Arc::new(FakeAIService), "fake"))
+    } else if let Some(api_key) = config.ai.api_key.clone() {
+        Some((Arc::new(OpenAIService::new(api_key, config.ai.model.clone(), config.ai.base_url.clone())), "openai"))
+    } else {
+        warn!("OPENAI_API_KEY is not set; feedback analysis is disabled");
+        None
+    };
+    let ai_service = ai_service.map(|(service, provider)| {
+        Arc::new(InstrumentedAIService::new(service, provider, metrics.clone())) as Arc<dyn AIService>
+    });
+    let tokenizer: Arc<dyn Tokenizer> = Arc::new(TiktokenTokenizer::new());
 
-    let prompt_repository = Arc::new(PostgresPromptRepository::new(pool.clone()));
-    let api_key_repository = Arc::new(PostgresApiKeyRepository::new(pool.clone()));
-    let openai_api_key = std::env::var("OPENAI_API_KEY")
-        .expect("OPENAI_API_KEY must be set in .env file");
-    let ai_service: Arc<dyn AIService> = Arc::new(OpenAIService::new(openai_api_key));
+    let mut pricing = PricingTable::with_defaults();
+    if let Some(path) = &config.pricing_file {
+        let json = std::fs::read_to_string(path)
+            .unwrap_or_else(|e| panic!("Failed to read PRICING_FILE {}: {}", path, e));
+        pricing.apply_overrides(&json).expect("Failed to load PRICING_FILE");
+    }
+    let pricing = Arc::new(pricing);
+
+    let render_log = Arc::new(RenderLog::new(render_repository.clone(), config.render_log.store_context));
 
     let create_prompt = Arc::new(CreatePrompt::new(prompt_repository.clone()));
     let update_prompt = Arc::new(UpdatePrompt::new(prompt_repository.clone()));
@@ -42,33 +176,42 @@ async fn main() {
     let list_prompts = Arc::new(ListPrompts::new(prompt_repository.clone()));
     let delete_prompt = Arc::new(DeletePrompt::new(prompt_repository.clone()));
 
-    let create_version = Arc::new(CreateVersion::new(prompt_repository.clone()));
+    let create_version = Arc::new(CreateVersion::new(prompt_repository.clone(), tokenizer.clone()));
     let get_version = Arc::new(GetVersion::new(prompt_repository.clone()));
     let delete_version = Arc::new(DeleteVersion::new(prompt_repository.clone()));
-    let render_version = Arc::new(RenderVersion::new(prompt_repository.clone()));
-    let render_version_by_tag = Arc::new(RenderVersionByTag::new(prompt_repository.clone()));
+    let render_version = Arc::new(RenderVersion::new(prompt_repository.clone(), tokenizer.clone(), pricing.clone(), render_log.clone()));
+    let render_version_by_tag = Arc::new(RenderVersionByTag::new(prompt_repository.clone(), tokenizer.clone(), pricing.clone(), render_log.clone()));
+    let render_batch = Arc::new(RenderBatch::new(prompt_repository.clone(), tokenizer.clone(), pricing.clone(), render_log));
+    let compare_versions = Arc::new(CompareVersions::new(prompt_repository.clone(), pricing.clone()));
 
     let create_tag = Arc::new(CreateTag::new(prompt_repository.clone()));
     let delete_tag = Arc::new(DeleteTag::new(prompt_repository.clone()));
     let get_version_by_tag = Arc::new(GetVersionByTag::new(prompt_repository.clone()));
+    let get_tag_bundle = Arc::new(GetTagBundle::new(prompt_repository.clone()));
 
-    let submit_feedback = Arc::new(SubmitFeedback::new(prompt_repository.clone()));
+    let submit_feedback = Arc::new(SubmitFeedback::new(prompt_repository.clone(), render_repository));
     let update_feedback = Arc::new(UpdateFeedback::new(prompt_repository.clone()));
     let delete_feedback = Arc::new(DeleteFeedback::new(prompt_repository.clone()));
+    let list_feedback = Arc::new(ListFeedback::new(prompt_repository.clone()));
 
     let create_improvement_suggestion = Arc::new(CreateImprovementSuggestion::new(prompt_repository.clone()));
-    let accept_improvement_suggestion = Arc::new(AcceptImprovementSuggestion::new(prompt_repository.clone()));
+    let accept_improvement_suggestion = Arc::new(AcceptImprovementSuggestion::new(prompt_repository.clone(), tokenizer.clone()));
     let decline_improvement_suggestion = Arc::new(DeclineImprovementSuggestion::new(prompt_repository.clone()));
     let get_suggestions_for_version = Arc::new(GetSuggestionsForVersion::new(prompt_repository.clone()));
-    let analyze_feedback_and_suggest = Arc::new(AnalyzeFeedbackAndSuggest::new(
-        prompt_repository.clone(),
-        ai_service.clone(),
-    ));
+    let analyze_feedback_and_suggest = Arc::new(AnalyzeFeedbackAndSuggest::new(prompt_repository.clone(), ai_service));
 
     let create_api_key = Arc::new(CreateApiKey::new(api_key_repository.clone()));
     let list_api_keys = Arc::new(ListApiKeys::new(api_key_repository.clone()));
     let delete_api_key = Arc::new(DeleteApiKey::new(api_key_repository.clone()));
 
+    let create_webhook = Arc::new(CreateWebhook::new(webhook_repository.clone(), prompt_repository.clone(), config.allow_private_webhook_targets()));
+    let list_webhooks = Arc::new(ListWebhooks::new(webhook_repository.clone()));
+    let delete_webhook = Arc::new(DeleteWebhook::new(webhook_repository.clone()));
+    let list_webhook_deliveries = Arc::new(ListWebhookDeliveries::new(webhook_repository.clone()));
+    let redeliver_webhook_delivery = Arc::new(RedeliverWebhookDelivery::new(webhook_repository.clone(), webhook_dispatcher));
+
+    let list_audit_entries = Arc::new(ListAuditEntries::new(audit_repository));
+
     let app_state = Arc::new(AppState {
         create_prompt,
         update_prompt,
@@ -80,30 +223,113 @@ async fn main() {
         delete_version,
         render_version,
         render_version_by_tag,
+        render_batch,
+        compare_versions,
         create_tag,
         delete_tag,
         get_version_by_tag,
+        get_tag_bundle,
         submit_feedback,
         update_feedback,
         delete_feedback,
+        list_feedback,
         create_api_key,
         list_api_keys,
         delete_api_key,
+        create_webhook,
+        list_webhooks,
+        delete_webhook,
+        list_webhook_deliveries,
+        redeliver_webhook_delivery,
+        list_audit_entries,
         create_improvement_suggestion,
         accept_improvement_suggestion,
         decline_improvement_suggestion,
         get_suggestions_for_version,
         api_key_repository,
         analyze_feedback_and_suggest,
+        pricing,
+        read_cache,
+        prompt_changes,
+        prompt_events,
+        metrics,
+        metrics_token: config.metrics.token.clone(),
+        readiness_probes,
+        shutdown: shutdown_rx.clone(),
     });
 
-    let app = create_router(app_state);
+    if let Some(metrics_address) = config.metrics.bind_address {
+        let listener = tokio::net::TcpListener::bind(metrics_address)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to bind {}: {}", metrics_address, e));
+        info!(%metrics_address, "Metrics listening");
+
+        let metrics_app = create_metrics_router(app_state.clone());
+        let mut draining = shutdown_rx.clone();
+        tokio::spawn(async move {
+            let result = axum::serve(listener, metrics_app)
+                .with_graceful_shutdown(async move {
+                    let _ = draining.wait_for(|draining| *draining).await;
+                })
+                .await;
+            if let Err(e) = result {
+                error!(error = %e, "Metrics listener failed");
+            }
+        });
+    }
 
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000")
+    let app = create_router(app_state, &config.cors.allowed_origins);
+
+    let bind_address = config.server.bind_address;
+    let listener = tokio::net::TcpListener::bind(bind_address)
         .await
-        .unwrap();
+        .unwrap_or_else(|e| panic!("Failed to bind {}: {}", bind_address, e));
+
+    info!(%bind_address, "Prompt Kaizen listening");
+
+    // On SIGTERM stop accepting connections and let in-flight requests
+    // finish, but no longer than the configured timeout.
+    let server = axum::serve(listener, app)
+        .with_graceful_shutdown(async move {
+            wait_for_shutdown_signal().await;
+            info!("Shutting down, draining in-flight requests");
+            let _ = shutdown_tx.send(true);
+        })
+        .into_future();
+    let shutdown_timeout = config.shutdown_timeout();
+    let mut draining = shutdown_rx;
+    let drain_deadline = async move {
+        let _ = draining.wait_for(|draining| *draining).await;
+        tokio::time::sleep(shutdown_timeout).await;
+    };
+
+    tokio::select! {
+        result = server => result.expect("Server error"),
+        _ = drain_deadline => warn!(timeout_secs = shutdown_timeout.as_secs(), "Requests still in flight at the shutdown deadline; closing them"),
+    }
+    info!("Shutdown complete");
+    telemetry.shutdown();
+}
+
+/// Resolves on Ctrl+C or SIGTERM, which fly.io sends before stopping a
+/// machine.
+async fn wait_for_shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.expect("Failed to listen for Ctrl+C");
+    };
 
-    println!("🚀 Prompt Kaizen running on http://localhost:3000");
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
 
-    axum::serve(listener, app).await.unwrap();
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
 }
\ No newline at end of file
@@ -0,0 +1,68 @@
+use crate::config::{LogFormat, TelemetryConfig};
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{trace::SdkTracerProvider, Resource};
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+/// Keeps the trace exporter alive; call `shutdown` before exiting so
+/// buffered spans are flushed.
+pub struct Telemetry {
+    tracer_provider: Option<SdkTracerProvider>,
+}
+
+impl Telemetry {
+    /// Installs the global `tracing` subscriber: logs to stdout in the
+    /// configured format and, when an OTLP endpoint is set, exports spans.
+    pub fn init(config: &TelemetryConfig) -> Result<Self, String> {
+        let filter = EnvFilter::try_new(&config.log_filter)
+            .map_err(|e| format!("Invalid log filter {}: {}", config.log_filter, e))?;
+
+        let log_layer = match config.log_format {
+            LogFormat::Pretty => fmt::layer().boxed(),
+            LogFormat::Json => fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_span_list(false)
+                .boxed(),
+        };
+
+        let tracer_provider = config
+            .otlp_endpoint
+            .as_deref()
+            .map(|endpoint| tracer_provider(endpoint, &config.service_name))
+            .transpose()?;
+        let trace_layer = tracer_provider
+            .as_ref()
+            .map(|provider| tracing_opentelemetry::layer().with_tracer(provider.tracer("prompt-kaizen")));
+
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(log_layer)
+            .with(trace_layer)
+            .try_init()
+            .map_err(|e| format!("Failed to install tracing subscriber: {}", e))?;
+
+        Ok(Self { tracer_provider })
+    }
+
+    pub fn shutdown(self) {
+        if let Some(provider) = self.tracer_provider
+            && let Err(e) = provider.shutdown()
+        {
+            eprintln!("Failed to flush traces: {}", e);
+        }
+    }
+}
+
+fn tracer_provider(endpoint: &str, service_name: &str) -> Result<SdkTracerProvider, String> {
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()
+        .map_err(|e| format!("Failed to create OTLP exporter: {}", e))?;
+
+    Ok(SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(service_name.to_string()).build())
+        .build())
+}
@@ -17,3 +17,12 @@ apiClient.use({
     return request;
   },
 });
+
+/** Message from the backend's `{"error": {"code", "message"}}` body. */
+export function apiErrorMessage(error: unknown): string {
+  if (typeof error === "object" && error !== null && "error" in error) {
+    const body = (error as { error?: { message?: string } }).error;
+    if (body?.message) return body.message;
+  }
+  return typeof error === "string" ? error : "Request failed";
+}
@@ -1,5 +1,5 @@
 import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
-import { apiClient } from "../client";
+import { apiClient, apiErrorMessage } from "../client";
 import type { CreateApiKeyResponse } from "@/types";
 
 export const useListApiKeys = () => {
@@ -7,7 +7,7 @@ export const useListApiKeys = () => {
     queryKey: ["api-keys"],
     queryFn: async () => {
       const response = await apiClient.GET("/api-keys");
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
   });
@@ -19,7 +19,7 @@ export const useCreateApiKey = () => {
   return useMutation({
     mutationFn: async (data: { name: string }) => {
       const response = await apiClient.POST("/api-keys", { body: data });
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data as CreateApiKeyResponse;
     },
     onSuccess: () => {
@@ -36,7 +36,7 @@ export const useDeleteApiKey = () => {
       const response = await apiClient.DELETE("/api-keys/{api_key_id}", {
         params: { path: { api_key_id: apiKeyId } },
       });
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
@@ -1,5 +1,5 @@
 import { useMutation, useQueryClient } from "@tanstack/react-query";
-import { apiClient } from "../client";
+import { apiClient, apiErrorMessage } from "../client";
 import type { SubmitFeedbackRequest, UpdateFeedbackRequest } from "@/types";
 
 export const useSubmitFeedback = (promptId: string) => {
@@ -11,7 +11,7 @@ export const useSubmitFeedback = (promptId: string) => {
         params: { path: { prompt_id: promptId } },
         body: data,
       });
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
@@ -38,7 +38,7 @@ export const useUpdateFeedback = (promptId: string, versionId: string) => {
           body,
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
@@ -67,7 +67,7 @@ export const useDeleteFeedback = (promptId: string, versionId: string) => {
           },
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
@@ -1,5 +1,5 @@
 import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
-import { apiClient } from "../client";
+import { apiClient, apiErrorMessage } from "../client";
 import type {
   CreateImprovementSuggestionRequest,
   AcceptImprovementSuggestionRequest,
@@ -18,7 +18,7 @@ export const useCreateImprovementSuggestion = (promptId: string) => {
           body: data,
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: (_, variables) => {
@@ -45,7 +45,7 @@ export const useListSuggestionsForVersion = (
           params: { path: { prompt_id: promptId, version_id: versionId } },
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
   });
@@ -71,7 +71,7 @@ export const useAcceptImprovementSuggestion = (
           body,
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
@@ -104,7 +104,7 @@ export const useDeclineImprovementSuggestion = (
           body,
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
@@ -126,7 +126,7 @@ export const useAnalyzeFeedback = (promptId: string, versionId: string) => {
           params: { path: { prompt_id: promptId, version_id: versionId } },
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
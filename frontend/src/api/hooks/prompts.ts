@@ -1,5 +1,5 @@
 import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
-import { apiClient } from "../client";
+import { apiClient, apiErrorMessage } from "../client";
 import type { CreatePromptRequest } from "@/types";
 
 export const useListPrompts = () => {
@@ -7,7 +7,7 @@ export const useListPrompts = () => {
     queryKey: ["prompts"],
     queryFn: async () => {
       const response = await apiClient.GET("/prompts");
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
   });
@@ -20,7 +20,7 @@ export const useGetPrompt = (promptId: string) => {
       const response = await apiClient.GET("/prompts/{prompt_id}", {
         params: { path: { prompt_id: promptId } },
       });
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
   });
@@ -32,7 +32,7 @@ export const useCreatePrompt = () => {
   return useMutation({
     mutationFn: async (data: CreatePromptRequest) => {
       const response = await apiClient.POST("/prompts", { body: data });
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
@@ -53,7 +53,7 @@ export const useUpdatePrompt = (promptId: string) => {
         params: { path: { prompt_id: promptId } },
         body: data,
       });
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
@@ -71,7 +71,7 @@ export const useDeletePrompt = () => {
       const response = await apiClient.DELETE("/prompts/{prompt_id}", {
         params: { path: { prompt_id: promptId } },
       });
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
@@ -1,5 +1,5 @@
 import { useMutation } from "@tanstack/react-query";
-import { apiClient } from "../client";
+import { apiClient, apiErrorMessage } from "../client";
 import type { RenderVersionRequest } from "@/types";
 
 export const useRenderVersion = (promptId: string, versionId: string) => {
@@ -12,7 +12,7 @@ export const useRenderVersion = (promptId: string, versionId: string) => {
           body: data,
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
   });
@@ -28,7 +28,7 @@ export const useRenderVersionByTag = (promptId: string, tagName: string) => {
           body: data,
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
   });
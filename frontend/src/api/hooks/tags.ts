@@ -1,5 +1,5 @@
 import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
-import { apiClient } from "../client";
+import { apiClient, apiErrorMessage } from "../client";
 
 export const useGetVersionByTag = (promptId: string, tagName: string) => {
   return useQuery({
@@ -11,7 +11,7 @@ export const useGetVersionByTag = (promptId: string, tagName: string) => {
           params: { path: { prompt_id: promptId, tag_name: tagName } },
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data;
     },
   });
@@ -26,7 +26,7 @@ export const useTagVersion = (promptId: string) => {
         params: { path: { prompt_id: promptId } },
         body: data,
       });
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data;
     },
     onSuccess: () => {
@@ -47,7 +47,7 @@ export const useDeleteTag = (promptId: string) => {
           params: { path: { prompt_id: promptId, tag_name: tagName } },
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data;
     },
     onSuccess: () => {
@@ -1,5 +1,5 @@
 import { useMutation, useQuery, useQueryClient } from "@tanstack/react-query";
-import { apiClient } from "../client";
+import { apiClient, apiErrorMessage } from "../client";
 import type { CreateVersionRequest } from "@/types";
 
 export const useGetVersion = (promptId: string, versionId: string) => {
@@ -12,7 +12,7 @@ export const useGetVersion = (promptId: string, versionId: string) => {
           params: { path: { prompt_id: promptId, version_id: versionId } },
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
   });
@@ -27,7 +27,7 @@ export const useCreateVersion = (promptId: string) => {
         params: { path: { prompt_id: promptId } },
         body: data,
       });
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
@@ -48,7 +48,7 @@ export const useDeleteVersion = (promptId: string) => {
           params: { path: { prompt_id: promptId, version_id: versionId } },
         },
       );
-      if (response.error) throw new Error(response.error as string);
+      if (response.error) throw new Error(apiErrorMessage(response.error));
       return response.data!;
     },
     onSuccess: () => {
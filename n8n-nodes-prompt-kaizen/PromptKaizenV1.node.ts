@@ -173,6 +173,18 @@ export class PromptKaizenV1 implements INodeType {
 						},
 					},
 				},
+				{
+					displayName: 'Render ID',
+					name: 'renderId',
+					type: 'string',
+					default: '',
+					description: 'Render ID returned when the prompt was fetched; fills in the test input from that render',
+					displayOptions: {
+						show: {
+							operation: ['submitFeedback'],
+						},
+					},
+				},
 				{
 					displayName: 'Test Input',
 					name: 'testInput',
@@ -223,7 +235,7 @@ export class PromptKaizenV1 implements INodeType {
 				const response = await this.helpers.httpRequest({
 					method: 'GET',
 					url: `${baseUrl}/prompts`,
-					headers: { 'x-api-key': apiKey },
+					headers: { 'x-api-key': apiKey, 'x-integration': 'n8n' },
 					json: true,
 				});
 
@@ -245,7 +257,7 @@ export class PromptKaizenV1 implements INodeType {
 				const response = await this.helpers.httpRequest({
 					method: 'GET',
 					url: `${baseUrl}/prompts/${promptId}`,
-					headers: { 'x-api-key': apiKey },
+					headers: { 'x-api-key': apiKey, 'x-integration': 'n8n' },
 					json: true,
 				});
 
@@ -267,7 +279,7 @@ export class PromptKaizenV1 implements INodeType {
 				const response = await this.helpers.httpRequest({
 					method: 'GET',
 					url: `${baseUrl}/prompts/${promptId}`,
-					headers: { 'x-api-key': apiKey },
+					headers: { 'x-api-key': apiKey, 'x-integration': 'n8n' },
 					json: true,
 				});
 
@@ -310,7 +322,7 @@ export class PromptKaizenV1 implements INodeType {
 					promptData = await this.helpers.httpRequest({
 						method: 'GET',
 						url: `${baseUrl}/prompts/${promptId}`,
-						headers: { 'x-api-key': apiKey },
+						headers: { 'x-api-key': apiKey, 'x-integration': 'n8n' },
 						json: true,
 					});
 					versionData = promptData.versions[promptData.versions.length - 1];
@@ -319,13 +331,13 @@ export class PromptKaizenV1 implements INodeType {
 					versionData = await this.helpers.httpRequest({
 						method: 'GET',
 						url: `${baseUrl}/prompts/${promptId}/versions/${versionId}`,
-						headers: { 'x-api-key': apiKey },
+						headers: { 'x-api-key': apiKey, 'x-integration': 'n8n' },
 						json: true,
 					});
 					promptData = await this.helpers.httpRequest({
 						method: 'GET',
 						url: `${baseUrl}/prompts/${promptId}`,
-						headers: { 'x-api-key': apiKey },
+						headers: { 'x-api-key': apiKey, 'x-integration': 'n8n' },
 						json: true,
 					});
 				} else {
@@ -333,28 +345,30 @@ export class PromptKaizenV1 implements INodeType {
 					versionData = await this.helpers.httpRequest({
 						method: 'GET',
 						url: `${baseUrl}/prompts/${promptId}/tags/${tagName}/version`,
-						headers: { 'x-api-key': apiKey },
+						headers: { 'x-api-key': apiKey, 'x-integration': 'n8n' },
 						json: true,
 					});
 					promptData = await this.helpers.httpRequest({
 						method: 'GET',
 						url: `${baseUrl}/prompts/${promptId}`,
-						headers: { 'x-api-key': apiKey },
+						headers: { 'x-api-key': apiKey, 'x-integration': 'n8n' },
 						json: true,
 					});
 				}
 
 				let content = versionData.content;
+				let renderId: string | undefined;
 
 				if (versionData.content_type === 'template' && Object.keys(context).length > 0) {
 					const renderResponse = await this.helpers.httpRequest({
 						method: 'POST',
 						url: `${baseUrl}/prompts/${promptId}/versions/${versionData.id}/render`,
-						headers: { 'x-api-key': apiKey },
+						headers: { 'x-api-key': apiKey, 'x-integration': 'n8n' },
 						body: { context },
 						json: true,
 					});
 					content = renderResponse.rendered_content;
+					renderId = renderResponse.render_id;
 				}
 
 				responseData = {
@@ -367,12 +381,14 @@ export class PromptKaizenV1 implements INodeType {
 					raw_content: versionData.content,
 					variables: versionData.variables,
 					context_used: context,
+					render_id: renderId,
 				};
 
 			} else if (operation === 'submitFeedback') {
 				const versionId = this.getNodeParameter('feedbackVersionId', i) as string;
 				const rating = this.getNodeParameter('rating', i) as number;
 				const comment = this.getNodeParameter('comment', i, '') as string;
+				const renderId = this.getNodeParameter('renderId', i, '') as string;
 				const testInput = this.getNodeParameter('testInput', i, '') as string;
 				const testActualOutput = this.getNodeParameter('testActualOutput', i, '') as string;
 				const testExpectedOutput = this.getNodeParameter('testExpectedOutput', i, '') as string;
@@ -383,6 +399,7 @@ export class PromptKaizenV1 implements INodeType {
 				};
 
 				if (comment) body.comment = comment;
+				if (renderId) body.render_id = renderId;
 				if (testInput) body.test_input = testInput;
 				if (testActualOutput) body.test_actual_output = testActualOutput;
 				if (testExpectedOutput) body.test_expected_output = testExpectedOutput;
@@ -390,7 +407,7 @@ export class PromptKaizenV1 implements INodeType {
 				responseData = await this.helpers.httpRequest({
 					method: 'POST',
 					url: `${baseUrl}/prompts/${promptId}/feedback`,
-					headers: { 'x-api-key': apiKey },
+					headers: { 'x-api-key': apiKey, 'x-integration': 'n8n' },
 					body,
 					json: true,
 				});